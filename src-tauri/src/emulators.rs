@@ -0,0 +1,189 @@
+use crate::launcher::{find_in_path, LaunchTarget, SpawnOptions};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+/// A ROM file found by [`scan_roms`], launched by handing it to an emulator.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RomGame {
+    /// Display title cleaned up from the file name, e.g. `Super Metroid`.
+    pub title: String,
+    /// The ROM file itself.
+    pub rom: PathBuf,
+    /// Emulator program: an absolute path or a name looked up on `PATH`.
+    pub emulator: String,
+    /// Arguments placed before the ROM path, e.g. `["-L", "/cores/snes9x_libretro.so"]`.
+    pub emulator_args: Vec<String>,
+}
+
+impl RomGame {
+    /// Runs the emulator with its arguments followed by the ROM path. A bare emulator
+    /// name is resolved on `PATH` when found there.
+    pub fn launch_target(&self) -> LaunchTarget {
+        let mut args = self.emulator_args.clone();
+        args.push(self.rom.to_string_lossy().into_owned());
+        let emulator = find_in_path(&self.emulator, std::env::var_os("PATH").as_deref())
+            .map_or_else(
+                || self.emulator.clone(),
+                |p| p.to_string_lossy().into_owned(),
+            );
+        LaunchTarget::executable_with(
+            emulator,
+            SpawnOptions {
+                args,
+                ..Default::default()
+            },
+        )
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Public API
+// ---------------------------------------------------------------------------
+
+/// Walks `rom_dir` (including per-system subfolders) for files whose extension is one
+/// of `extensions` (case-insensitive, leading dot optional) and returns them sorted by
+/// title. Hidden files and directories are skipped.
+pub fn scan_roms(
+    emulator: &str,
+    emulator_args: &[String],
+    rom_dir: &Path,
+    extensions: &[String],
+) -> Vec<RomGame> {
+    let extensions: Vec<String> = extensions
+        .iter()
+        .map(|e| e.trim().trim_start_matches('.').to_lowercase())
+        .filter(|e| !e.is_empty())
+        .collect();
+    let mut games: Vec<RomGame> = WalkDir::new(rom_dir)
+        .into_iter()
+        .filter_entry(|e| e.depth() == 0 || !e.file_name().to_string_lossy().starts_with('.'))
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter(|e| {
+            e.path()
+                .extension()
+                .and_then(|x| x.to_str())
+                .is_some_and(|x| extensions.iter().any(|e| x.eq_ignore_ascii_case(e)))
+        })
+        .filter_map(|e| {
+            let stem = e.path().file_stem()?.to_string_lossy().into_owned();
+            Some(RomGame {
+                title: clean_title(&stem),
+                rom: e.into_path(),
+                emulator: emulator.to_string(),
+                emulator_args: emulator_args.to_vec(),
+            })
+        })
+        .collect();
+    games.sort_by_key(|g| g.title.to_lowercase());
+    log::info!("Found {} ROM(s) under {:?}", games.len(), rom_dir);
+    games
+}
+
+/// Turns a ROM file stem into a display title: tags in parentheses or brackets such as
+/// `(USA)`, `(Rev 1)` or `[!]` are dropped, underscores become spaces, and a trailing
+/// article (`Legend of Zelda, The`) moves to the front.
+pub fn clean_title(stem: &str) -> String {
+    let mut title = String::with_capacity(stem.len());
+    let mut depth = 0usize;
+    for c in stem.chars() {
+        match c {
+            '(' | '[' => depth += 1,
+            ')' | ']' => depth = depth.saturating_sub(1),
+            '_' if depth == 0 => title.push(' '),
+            c if depth == 0 => title.push(c),
+            _ => {}
+        }
+    }
+    let title = title.split_whitespace().collect::<Vec<_>>().join(" ");
+    for article in ["The", "A", "An"] {
+        if let Some(rest) = title.strip_suffix(&format!(", {article}")) {
+            return format!("{article} {rest}");
+        }
+    }
+    if title.is_empty() {
+        return stem.to_string();
+    }
+    title
+}
+
+// ============================================================
+// Tests
+// ============================================================
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn make_temp_dir() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("emulators_test_{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn cleans_region_and_dump_tags() {
+        assert_eq!(
+            clean_title("Super Mario Bros. 3 (USA) (Rev 1) [!]"),
+            "Super Mario Bros. 3"
+        );
+        assert_eq!(
+            clean_title("Legend of Zelda, The (USA)"),
+            "The Legend of Zelda"
+        );
+        assert_eq!(clean_title("chrono_trigger (Europe)"), "chrono trigger");
+        assert_eq!(clean_title("(Homebrew)"), "(Homebrew)");
+    }
+
+    #[test]
+    fn scans_matching_roms_recursively() {
+        let dir = make_temp_dir();
+        fs::create_dir_all(dir.join("snes")).unwrap();
+        fs::create_dir_all(dir.join(".cache")).unwrap();
+        fs::write(dir.join("snes/Super Metroid (Japan, USA).SFC"), "").unwrap();
+        fs::write(dir.join("Contra (USA).nes"), "").unwrap();
+        fs::write(dir.join("Contra (USA).srm"), "").unwrap();
+        fs::write(dir.join(".cache/Hidden.nes"), "").unwrap();
+
+        let args = vec!["-L".to_string(), "/cores/snes9x.so".to_string()];
+        let exts = vec![".nes".to_string(), "sfc".to_string()];
+        let games = scan_roms("retroarch", &args, &dir, &exts);
+        let titles: Vec<&str> = games.iter().map(|g| g.title.as_str()).collect();
+        assert_eq!(titles, ["Contra", "Super Metroid"]);
+        assert_eq!(
+            games[1].rom,
+            dir.join("snes/Super Metroid (Japan, USA).SFC")
+        );
+
+        fs::remove_dir_all(dir).ok();
+    }
+
+    #[test]
+    fn launches_emulator_with_rom_last() {
+        let game = RomGame {
+            title: "Contra".to_string(),
+            rom: PathBuf::from("/roms/Contra (USA).nes"),
+            emulator: "/opt/retroarch/retroarch".to_string(),
+            emulator_args: vec!["-L".to_string(), "/cores/nestopia.so".to_string()],
+        };
+        assert_eq!(
+            game.launch_target(),
+            LaunchTarget::executable_with(
+                "/opt/retroarch/retroarch",
+                SpawnOptions {
+                    args: vec![
+                        "-L".to_string(),
+                        "/cores/nestopia.so".to_string(),
+                        "/roms/Contra (USA).nes".to_string()
+                    ],
+                    ..Default::default()
+                }
+            )
+        );
+    }
+}
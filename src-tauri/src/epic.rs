@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use thiserror::Error;
 
@@ -20,6 +21,17 @@ pub struct EpicGame {
     pub catalog_item_id: String,
     /// Absolute path to a local cover image, or `None` when not found.
     pub cover_image: Option<PathBuf>,
+    /// The game's executable, from the manifest's `LaunchExecutable` (relative to the
+    /// install dir). Needed to run the game without the Epic launcher, e.g. through
+    /// Legendary or Wine; `None` when the manifest doesn't say.
+    #[serde(default)]
+    pub launch_executable: Option<PathBuf>,
+    /// Installed size in bytes, from the manifest's `InstallSize`.
+    #[serde(default)]
+    pub install_size: Option<u64>,
+    /// Installed build, from the manifest's `AppVersionString`.
+    #[serde(default)]
+    pub app_version: Option<String>,
 }
 
 impl EpicGame {
@@ -30,6 +42,38 @@ impl EpicGame {
             self.catalog_namespace, self.catalog_item_id, self.app_name
         )
     }
+
+    /// Stable identity for state kept about the game outside Epic: `epic:<app_name>`.
+    pub fn external_key(&self) -> String {
+        format!("epic:{}", self.app_name)
+    }
+
+    /// The game's Epic Games Store page. Manifests only carry catalog ids, and the store
+    /// has no page keyed by them, so this is a store search for the title: guessing the
+    /// product slug from the title breaks for editions, sequels and renamed games.
+    pub fn store_url(&self) -> String {
+        let mut query = String::new();
+        for byte in self.display_name.trim().bytes() {
+            match byte {
+                b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                    query.push(byte as char)
+                }
+                b' ' => query.push_str("%20"),
+                _ => query.push_str(&format!("%{:02X}", byte)),
+            }
+        }
+        format!(
+            "https://store.epicgames.com/browse?q={}&sortBy=relevancy",
+            query
+        )
+    }
+}
+
+/// Knobs for [`discover_games_from`]. The defaults match what the library grid wants.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct DiscoveryOptions {
+    /// Keep DLC and plugin manifests that belong to another game.
+    pub include_dlc: bool,
 }
 
 #[derive(Debug, Error)]
@@ -38,6 +82,8 @@ pub enum EpicError {
     NotFound,
     #[error("Failed to read Epic manifest directory: {0}")]
     Io(#[from] std::io::Error),
+    #[error("Failed to parse Legendary install list: {0}")]
+    Json(#[from] serde_json::Error),
 }
 
 // ---------------------------------------------------------------------------
@@ -52,6 +98,12 @@ struct Manifest {
     install_location: Option<String>,
     catalog_namespace: Option<String>,
     catalog_item_id: Option<String>,
+    launch_executable: Option<String>,
+    install_size: Option<u64>,
+    app_version_string: Option<String>,
+    main_game_app_name: Option<String>,
+    #[serde(default)]
+    app_categories: Vec<String>,
     #[serde(rename = "bIsApplication", default)]
     b_is_application: bool,
     #[serde(rename = "bIsExecutable", default)]
@@ -60,20 +112,71 @@ struct Manifest {
     b_is_incomplete_install: bool,
 }
 
+/// One entry of Legendary's `installed.json`, which maps app names to install metadata.
+#[derive(Deserialize)]
+struct LegendaryInstall {
+    app_name: Option<String>,
+    title: Option<String>,
+    install_path: Option<String>,
+    executable: Option<String>,
+    install_size: Option<u64>,
+    version: Option<String>,
+    #[serde(default)]
+    is_dlc: bool,
+}
+
 // ---------------------------------------------------------------------------
 // Public API
 // ---------------------------------------------------------------------------
 
 /// Returns all installed Epic games, or `Ok(vec![])` if the launcher is absent.
+///
+/// On Linux there is no Epic launcher, so games installed through Legendary (directly or
+/// via Heroic) are read from Legendary's `installed.json` instead.
 pub fn discover_games() -> Result<Vec<EpicGame>, EpicError> {
-    match manifest_dir() {
-        Some(dir) => discover_games_from(&dir),
-        None => Ok(vec![]),
+    let mut games = match manifest_dir() {
+        Some(dir) => discover_games_from(&dir, DiscoveryOptions::default())?,
+        None => vec![],
+    };
+    for path in legendary_installed_files() {
+        match discover_games_from_legendary(&path) {
+            Ok(found) => {
+                for game in found {
+                    if !games.iter().any(|g| g.app_name == game.app_name) {
+                        games.push(game);
+                    }
+                }
+            }
+            Err(e) => log::warn!("Skipping Legendary install list {:?}: {}", path, e),
+        }
     }
+    Ok(games)
+}
+
+/// Discovers Epic games from a Legendary `installed.json` file (used in tests).
+///
+/// Legendary doesn't record catalog ids there, so those fields are left empty; the
+/// launcher runs these games through `legendary launch` when available.
+pub fn discover_games_from_legendary(path: &Path) -> Result<Vec<EpicGame>, EpicError> {
+    if !path.exists() {
+        return Ok(vec![]);
+    }
+
+    let contents = std::fs::read_to_string(path)?;
+    let installed: HashMap<String, LegendaryInstall> = serde_json::from_str(&contents)?;
+    let mut games: Vec<EpicGame> = installed
+        .into_iter()
+        .filter_map(|(key, install)| parse_legendary_install(key, install))
+        .collect();
+    games.sort_by(|a, b| a.app_name.cmp(&b.app_name));
+    Ok(games)
 }
 
 /// Discovers Epic games from a specific manifest directory (used in tests).
-pub fn discover_games_from(manifest_dir: &Path) -> Result<Vec<EpicGame>, EpicError> {
+pub fn discover_games_from(
+    manifest_dir: &Path,
+    options: DiscoveryOptions,
+) -> Result<Vec<EpicGame>, EpicError> {
     if !manifest_dir.exists() {
         return Ok(vec![]);
     }
@@ -84,7 +187,7 @@ pub fn discover_games_from(manifest_dir: &Path) -> Result<Vec<EpicGame>, EpicErr
     for entry in entries.flatten() {
         let path = entry.path();
         if path.extension().and_then(|e| e.to_str()) == Some("item") {
-            if let Some(game) = parse_manifest(&path) {
+            if let Some(game) = parse_manifest(&path, options) {
                 games.push(game);
             }
         }
@@ -119,8 +222,58 @@ fn manifest_dir() -> Option<PathBuf> {
     }
 }
 
+/// Returns the Legendary `installed.json` files to scan: standalone Legendary and the
+/// copies bundled with Heroic (native and Flatpak).
+fn legendary_installed_files() -> Vec<PathBuf> {
+    #[cfg(target_os = "linux")]
+    {
+        let Ok(home) = std::env::var("HOME") else {
+            return vec![];
+        };
+        let home = PathBuf::from(home);
+        let config = std::env::var("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .unwrap_or_else(|_| home.join(".config"));
+        vec![
+            config.join("legendary/installed.json"),
+            config.join("heroic/legendaryConfig/legendary/installed.json"),
+            home.join(
+                ".var/app/com.heroicgameslauncher.hgl/config/heroic/legendaryConfig/legendary/installed.json",
+            ),
+        ]
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        vec![]
+    }
+}
+
+/// Converts one `installed.json` entry; returns `None` for DLC or incomplete entries.
+fn parse_legendary_install(key: String, install: LegendaryInstall) -> Option<EpicGame> {
+    if install.is_dlc {
+        return None;
+    }
+    let app_name = install.app_name.filter(|s| !s.is_empty()).unwrap_or(key);
+    let display_name = install.title.filter(|s| !s.is_empty())?;
+    let install_location = PathBuf::from(install.install_path.filter(|s| !s.is_empty())?);
+    let cover_image = find_cover_image(&install_location);
+    let launch_executable = resolve_executable(&install_location, install.executable);
+
+    Some(EpicGame {
+        app_name,
+        display_name,
+        install_location,
+        catalog_namespace: String::new(),
+        catalog_item_id: String::new(),
+        cover_image,
+        launch_executable,
+        install_size: install.install_size,
+        app_version: install.version.filter(|s| !s.is_empty()),
+    })
+}
+
 /// Parses a single `.item` manifest file; returns `None` if it should be skipped.
-fn parse_manifest(path: &Path) -> Option<EpicGame> {
+fn parse_manifest(path: &Path, options: DiscoveryOptions) -> Option<EpicGame> {
     let contents = std::fs::read_to_string(path).ok()?;
     let m: Manifest = serde_json::from_str(&contents).ok()?;
 
@@ -129,7 +282,11 @@ fn parse_manifest(path: &Path) -> Option<EpicGame> {
         return None;
     }
 
-    let app_name = m.app_name.filter(|s| !s.is_empty())?;
+    let app_name = m.app_name.clone().filter(|s| !s.is_empty())?;
+    if !options.include_dlc && is_add_on(&m, &app_name) {
+        log::info!("Skipping Epic DLC/plugin manifest: {:?}", path);
+        return None;
+    }
     let display_name = m.display_name.filter(|s| !s.is_empty())?;
     let install_location = m.install_location.filter(|s| !s.is_empty())?;
     let catalog_namespace = m.catalog_namespace.unwrap_or_default();
@@ -137,6 +294,7 @@ fn parse_manifest(path: &Path) -> Option<EpicGame> {
 
     let install_path = PathBuf::from(&install_location);
     let cover_image = find_cover_image(&install_path);
+    let launch_executable = resolve_executable(&install_path, m.launch_executable);
 
     Some(EpicGame {
         app_name,
@@ -145,23 +303,66 @@ fn parse_manifest(path: &Path) -> Option<EpicGame> {
         catalog_namespace,
         catalog_item_id,
         cover_image,
+        launch_executable,
+        install_size: m.install_size,
+        app_version: m.app_version_string.filter(|s| !s.is_empty()),
     })
 }
 
-/// Scans the game's install directory (depth 1) for the first PNG or JPEG file.
-fn find_cover_image(install_dir: &Path) -> Option<PathBuf> {
+/// Whether a manifest describes DLC or a plugin rather than a game: it names a different
+/// main game, or its categories mark it as an add-on without also marking it a game.
+fn is_add_on(m: &Manifest, app_name: &str) -> bool {
+    let has_category = |name: &str| {
+        m.app_categories
+            .iter()
+            .any(|c| c.eq_ignore_ascii_case(name))
+    };
+    m.main_game_app_name
+        .as_deref()
+        .is_some_and(|main| !main.is_empty() && main != app_name)
+        || ((has_category("addons") || has_category("plugins")) && !has_category("games"))
+}
+
+/// Joins a manifest's install-relative executable onto the install dir. Manifests
+/// written on Windows use backslashes, so those are treated as separators too.
+fn resolve_executable(install_dir: &Path, relative: Option<String>) -> Option<PathBuf> {
+    let relative = relative.filter(|s| !s.trim().is_empty())?;
+    Some(
+        relative
+            .split(['/', '\\'])
+            .filter(|part| !part.is_empty())
+            .fold(install_dir.to_path_buf(), |path, part| path.join(part)),
+    )
+}
+
+/// File-name hints that an image is box art rather than a splash screen or logo.
+const COVER_NAME_HINTS: &[&str] = &["cover", "box", "grid"];
+
+/// Scans the game's install directory (depth 1) for PNG, JPEG, WebP or AVIF files and
+/// picks the likeliest box art: a name hinting at a cover beats a portrait image, which
+/// beats anything else. Ties go to the first file by name.
+pub(crate) fn find_cover_image(install_dir: &Path) -> Option<PathBuf> {
     let entries = std::fs::read_dir(install_dir).ok()?;
-    entries.flatten().find_map(|e| {
-        let p = e.path();
-        if p.is_file() {
-            match p.extension().and_then(|ex| ex.to_str()) {
-                Some("png") | Some("jpg") | Some("jpeg") => Some(p),
-                _ => None,
-            }
-        } else {
-            None
-        }
-    })
+    let mut images: Vec<PathBuf> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| crate::library::has_cover_extension(p) && p.is_file())
+        .collect();
+    images.sort();
+    // `max_by_key` keeps the last of equal maxima, so walk backwards to favour the first.
+    images.into_iter().rev().max_by_key(|p| cover_score(p))
+}
+
+/// Ranks a candidate cover. Only the image header is read to get its dimensions;
+/// formats that can't be probed just don't count as portrait.
+fn cover_score(path: &Path) -> u8 {
+    let stem = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    let named = COVER_NAME_HINTS.iter().any(|hint| stem.contains(hint));
+    let portrait = image::image_dimensions(path).is_ok_and(|(width, height)| height > width);
+    u8::from(named) * 2 + u8::from(portrait)
 }
 
 // ============================================================
@@ -206,7 +407,8 @@ mod tests {
         write_manifest(&manifest_dir, "GameA", "");
         write_manifest(&manifest_dir, "GameB", "");
 
-        let games = discover_games_from(&manifest_dir).expect("should succeed");
+        let games = discover_games_from(&manifest_dir, DiscoveryOptions::default())
+            .expect("should succeed");
         assert_eq!(games.len(), 2);
 
         let mut names: Vec<String> = games.iter().map(|g| g.app_name.clone()).collect();
@@ -226,7 +428,8 @@ mod tests {
         // Ensure it does not exist
         let _ = fs::remove_dir_all(&missing);
 
-        let games = discover_games_from(&missing).expect("absent dir should return Ok");
+        let games = discover_games_from(&missing, DiscoveryOptions::default())
+            .expect("absent dir should return Ok");
         assert!(games.is_empty());
     }
 
@@ -237,7 +440,8 @@ mod tests {
         write_manifest(&manifest_dir, "GoodGame", "");
         fs::write(manifest_dir.join("bad.item"), b"not valid json at all {{{{").unwrap();
 
-        let games = discover_games_from(&manifest_dir).expect("should succeed despite bad file");
+        let games = discover_games_from(&manifest_dir, DiscoveryOptions::default())
+            .expect("should succeed despite bad file");
         assert_eq!(games.len(), 1);
         assert_eq!(games[0].app_name, "GoodGame");
 
@@ -264,7 +468,8 @@ mod tests {
         );
         fs::write(manifest_dir.join("incomplete.item"), content).unwrap();
 
-        let games = discover_games_from(&manifest_dir).expect("should succeed");
+        let games = discover_games_from(&manifest_dir, DiscoveryOptions::default())
+            .expect("should succeed");
         assert!(games.is_empty(), "incomplete installs must be excluded");
 
         fs::remove_dir_all(&manifest_dir).ok();
@@ -289,12 +494,231 @@ mod tests {
         );
         fs::write(manifest_dir.join("nonapp.item"), content).unwrap();
 
-        let games = discover_games_from(&manifest_dir).expect("should succeed");
+        let games = discover_games_from(&manifest_dir, DiscoveryOptions::default())
+            .expect("should succeed");
         assert!(games.is_empty(), "non-application entries must be excluded");
 
         fs::remove_dir_all(&manifest_dir).ok();
     }
 
+    // ------------------------------------------------------------------ LaunchExecutable
+    #[test]
+    fn launch_executable_joined_with_install_location() {
+        let manifest_dir = make_temp_dir("launch_exe");
+        write_manifest(
+            &manifest_dir,
+            "Nested",
+            r#", "LaunchExecutable": "Binaries\\Win64\\Game-Win64-Shipping.exe""#,
+        );
+        write_manifest(&manifest_dir, "Plain", "");
+
+        let mut games = discover_games_from(&manifest_dir, DiscoveryOptions::default())
+            .expect("should succeed");
+        games.sort_by(|a, b| a.app_name.cmp(&b.app_name));
+        assert_eq!(
+            games[0].launch_executable,
+            Some(
+                manifest_dir
+                    .join("Binaries")
+                    .join("Win64")
+                    .join("Game-Win64-Shipping.exe")
+            )
+        );
+        assert_eq!(games[1].app_name, "Plain");
+        assert_eq!(games[1].launch_executable, None);
+
+        fs::remove_dir_all(&manifest_dir).ok();
+    }
+
+    // ------------------------------------------------------------------ size & version
+    #[test]
+    fn install_size_and_version_parsed_when_present() {
+        let manifest_dir = make_temp_dir("size_version");
+        write_manifest(
+            &manifest_dir,
+            "Sized",
+            r#", "InstallSize": 53687091200, "AppVersionString": "++Fortnite+Release-28.10-CL-32116959-Windows""#,
+        );
+        write_manifest(&manifest_dir, "Bare", "");
+
+        let mut games = discover_games_from(&manifest_dir, DiscoveryOptions::default())
+            .expect("should succeed");
+        games.sort_by(|a, b| a.app_name.cmp(&b.app_name));
+        let (bare, sized) = (&games[0], &games[1]);
+        assert_eq!(sized.install_size, Some(53_687_091_200));
+        assert_eq!(
+            sized.app_version.as_deref(),
+            Some("++Fortnite+Release-28.10-CL-32116959-Windows")
+        );
+        assert_eq!(bare.install_size, None);
+        assert_eq!(bare.app_version, None);
+
+        fs::remove_dir_all(&manifest_dir).ok();
+    }
+
+    // ------------------------------------------------------------------ DLC
+    #[test]
+    fn dlc_manifests_skipped_unless_requested() {
+        let manifest_dir = make_temp_dir("dlc");
+        write_manifest(
+            &manifest_dir,
+            "Base",
+            r#", "MainGameAppName": "Base", "AppCategories": ["public", "games", "applications"]"#,
+        );
+        write_manifest(
+            &manifest_dir,
+            "BaseExpansion",
+            r#", "MainGameAppName": "Base", "AppCategories": ["public", "addons"]"#,
+        );
+        write_manifest(
+            &manifest_dir,
+            "EnginePlugin",
+            r#", "AppCategories": ["plugins", "engines"]"#,
+        );
+
+        let games = discover_games_from(&manifest_dir, DiscoveryOptions::default()).unwrap();
+        let names: Vec<&str> = games.iter().map(|g| g.app_name.as_str()).collect();
+        assert_eq!(names, ["Base"]);
+
+        let mut all = discover_games_from(&manifest_dir, DiscoveryOptions { include_dlc: true })
+            .unwrap()
+            .into_iter()
+            .map(|g| g.app_name)
+            .collect::<Vec<_>>();
+        all.sort();
+        assert_eq!(all, ["Base", "BaseExpansion", "EnginePlugin"]);
+
+        fs::remove_dir_all(&manifest_dir).ok();
+    }
+
+    // ------------------------------------------------------------------ Legendary
+    #[test]
+    fn legendary_installed_json_parsed() {
+        let dir = make_temp_dir("legendary");
+        let installed = dir.join("installed.json");
+        fs::write(
+            &installed,
+            r#"{
+  "Quail": {
+    "app_name": "Quail",
+    "title": "Hades",
+    "install_path": "/home/user/Games/Heroic/Hades",
+    "executable": "x64/Hades.exe",
+    "version": "1.38290",
+    "is_dlc": false
+  },
+  "Fortnite": {
+    "app_name": "Fortnite",
+    "title": "Fortnite",
+    "install_path": "/home/user/Games/Heroic/Fortnite",
+    "is_dlc": false
+  },
+  "HadesSoundtrack": {
+    "app_name": "HadesSoundtrack",
+    "title": "Hades Soundtrack",
+    "install_path": "/home/user/Games/Heroic/Hades",
+    "is_dlc": true
+  },
+  "Broken": { "app_name": "Broken", "title": "Broken" }
+}"#,
+        )
+        .unwrap();
+
+        let games = discover_games_from_legendary(&installed).expect("should parse");
+        let names: Vec<&str> = games.iter().map(|g| g.app_name.as_str()).collect();
+        assert_eq!(names, ["Fortnite", "Quail"]);
+        assert_eq!(games[1].display_name, "Hades");
+        assert_eq!(
+            games[1].install_location,
+            PathBuf::from("/home/user/Games/Heroic/Hades")
+        );
+        assert!(games[1].launch_uri().contains("Quail"));
+        assert_eq!(
+            games[1].launch_executable,
+            Some(PathBuf::from("/home/user/Games/Heroic/Hades/x64/Hades.exe"))
+        );
+        assert_eq!(games[0].launch_executable, None);
+        assert_eq!(games[1].app_version.as_deref(), Some("1.38290"));
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn legendary_missing_file_returns_empty() {
+        let missing = std::env::temp_dir().join("epic_test_absent_installed_99999.json");
+        let _ = fs::remove_file(&missing);
+        assert!(discover_games_from_legendary(&missing).unwrap().is_empty());
+    }
+
+    #[test]
+    fn legendary_malformed_file_is_an_error() {
+        let dir = make_temp_dir("legendary_bad");
+        let installed = dir.join("installed.json");
+        fs::write(&installed, "not json").unwrap();
+        assert!(matches!(
+            discover_games_from_legendary(&installed),
+            Err(EpicError::Json(_))
+        ));
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    // ------------------------------------------------------------------ cover images
+    #[test]
+    fn webp_cover_is_found() {
+        let dir = make_temp_dir("cover_webp");
+        fs::write(dir.join("readme.txt"), "").unwrap();
+        fs::write(dir.join("Cover.WEBP"), "").unwrap();
+        assert_eq!(find_cover_image(&dir), Some(dir.join("Cover.WEBP")));
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    fn write_image(path: &Path, width: u32, height: u32) {
+        image::RgbImage::new(width, height).save(path).unwrap();
+    }
+
+    #[test]
+    fn portrait_image_beats_splash_and_logo() {
+        let dir = make_temp_dir("cover_portrait");
+        write_image(&dir.join("a_splash.png"), 64, 36);
+        write_image(&dir.join("b_logo.png"), 40, 10);
+        write_image(&dir.join("c_art.png"), 60, 90);
+        assert_eq!(find_cover_image(&dir), Some(dir.join("c_art.png")));
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn cover_name_beats_aspect_ratio() {
+        let dir = make_temp_dir("cover_named");
+        write_image(&dir.join("a_tall.png"), 20, 80);
+        write_image(&dir.join("BoxArt.jpg"), 80, 60);
+        write_image(&dir.join("z_grid.png"), 60, 90);
+        // Both hinted names tie on the name; the portrait grid image wins on shape.
+        assert_eq!(find_cover_image(&dir), Some(dir.join("z_grid.png")));
+        fs::remove_file(dir.join("z_grid.png")).unwrap();
+        assert_eq!(find_cover_image(&dir), Some(dir.join("BoxArt.jpg")));
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn falls_back_to_first_image_by_name() {
+        let dir = make_temp_dir("cover_fallback");
+        write_image(&dir.join("b_screen.png"), 64, 36);
+        write_image(&dir.join("a_screen.png"), 64, 36);
+        fs::write(dir.join("broken.webp"), "not an image").unwrap();
+        assert_eq!(find_cover_image(&dir), Some(dir.join("a_screen.png")));
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn no_supported_cover_is_none() {
+        let dir = make_temp_dir("cover_none");
+        fs::write(dir.join("splash.bmp"), "").unwrap();
+        fs::write(dir.join("icon.gif"), "").unwrap();
+        fs::create_dir_all(dir.join("art.png")).unwrap();
+        assert_eq!(find_cover_image(&dir), None);
+        fs::remove_dir_all(&dir).ok();
+    }
+
     // ------------------------------------------------------------------ launch_uri helper
     #[test]
     fn launch_uri_format() {
@@ -306,11 +730,34 @@ mod tests {
             catalog_namespace: "fn".to_string(),
             catalog_item_id: "4fe75bbc5a674f4f9b356b5c90567da5".to_string(),
             cover_image: None,
+            launch_executable: None,
+            install_size: None,
+            app_version: None,
         };
         assert_eq!(
             game.launch_uri(),
             "com.epicgames.launcher://apps/fn%3A4fe75bbc5a674f4f9b356b5c90567da5%3AFortnite?action=launch&silent=true"
         );
+        assert_eq!(game.external_key(), "epic:Fortnite");
         fs::remove_dir_all(&dir).ok();
     }
+
+    #[test]
+    fn store_url_searches_for_the_title() {
+        let game = EpicGame {
+            app_name: "Kinglet".to_string(),
+            display_name: "Baldur's Gate 3: Director's Cut".to_string(),
+            install_location: PathBuf::from("/games/bg3"),
+            catalog_namespace: "ns".to_string(),
+            catalog_item_id: "item".to_string(),
+            cover_image: None,
+            launch_executable: None,
+            install_size: None,
+            app_version: None,
+        };
+        assert_eq!(
+            game.store_url(),
+            "https://store.epicgames.com/browse?q=Baldur%27s%20Gate%203%3A%20Director%27s%20Cut&sortBy=relevancy"
+        );
+    }
 }
@@ -0,0 +1,311 @@
+use crate::epic::EpicError;
+use crate::fs_explorer::BookmarkError;
+use crate::gog::GogError;
+use crate::itch::ItchError;
+use crate::launcher::LaunchError;
+use crate::library::LibraryError;
+use crate::settings::SettingsError;
+use crate::steam::SteamError;
+use serde::Serialize;
+use std::fmt;
+use std::io::ErrorKind;
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+/// Error returned by fallible Tauri commands: a stable `code` the frontend can switch on
+/// (e.g. `steam_not_found`, `permission_denied`) plus a human-readable `message`.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct CommandError {
+    pub code: String,
+    pub message: String,
+}
+
+impl CommandError {
+    pub fn new(code: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            code: code.into(),
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for CommandError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Conversions
+// ---------------------------------------------------------------------------
+
+/// Code for an I/O failure, distinguishing the kinds users can act on.
+fn io_code(e: &std::io::Error) -> &'static str {
+    match e.kind() {
+        ErrorKind::NotFound => "file_not_found",
+        ErrorKind::PermissionDenied => "permission_denied",
+        _ => "io_error",
+    }
+}
+
+impl From<std::io::Error> for CommandError {
+    fn from(e: std::io::Error) -> Self {
+        Self::new(io_code(&e), e.to_string())
+    }
+}
+
+impl From<serde_json::Error> for CommandError {
+    fn from(e: serde_json::Error) -> Self {
+        Self::new("parse_error", e.to_string())
+    }
+}
+
+impl From<SteamError> for CommandError {
+    fn from(e: SteamError) -> Self {
+        let code = match &e {
+            SteamError::NotFound => "steam_not_found",
+            SteamError::InvalidRoot(_) => "invalid_steam_root",
+            SteamError::Io(io) => io_code(io),
+        };
+        Self::new(code, e.to_string())
+    }
+}
+
+impl From<EpicError> for CommandError {
+    fn from(e: EpicError) -> Self {
+        let code = match &e {
+            EpicError::NotFound => "epic_not_found",
+            EpicError::Io(io) => io_code(io),
+            EpicError::Json(_) => "parse_error",
+        };
+        Self::new(code, e.to_string())
+    }
+}
+
+impl From<GogError> for CommandError {
+    fn from(e: GogError) -> Self {
+        let code = match &e {
+            GogError::Io(io) => io_code(io),
+        };
+        Self::new(code, e.to_string())
+    }
+}
+
+impl From<ItchError> for CommandError {
+    fn from(e: ItchError) -> Self {
+        let code = match &e {
+            ItchError::Db(_) => "database_error",
+        };
+        Self::new(code, e.to_string())
+    }
+}
+
+impl From<LibraryError> for CommandError {
+    fn from(e: LibraryError) -> Self {
+        let code = match &e {
+            LibraryError::NotFound(_) | LibraryError::CollectionNotFound(_) => "not_found",
+            LibraryError::Io(io) => io_code(io),
+            LibraryError::Json(_) => "parse_error",
+            LibraryError::ExecutableMissing(_) => "executable_missing",
+            LibraryError::Duplicate(_) => "duplicate",
+            LibraryError::InvalidCover(_) => "invalid_cover",
+            LibraryError::NewerVersion(_) => "newer_version",
+        };
+        Self::new(code, e.to_string())
+    }
+}
+
+impl From<LaunchError> for CommandError {
+    fn from(e: LaunchError) -> Self {
+        let code = match &e {
+            LaunchError::ExecutableNotFound(_) => "executable_missing",
+            LaunchError::SpawnFailed(io) if io.kind() == ErrorKind::PermissionDenied => {
+                "permission_denied"
+            }
+            LaunchError::SpawnFailed(_) => "spawn_failed",
+            LaunchError::EarlyExit(_) => "early_exit",
+            LaunchError::NotRunning(_) => "not_running",
+            LaunchError::NoRunner(_) => "no_runner",
+            LaunchError::NoUriHandler(_) => "no_uri_handler",
+            LaunchError::UriOpenFailed(..) => "uri_open_failed",
+            LaunchError::PreLaunchFailed(_) => "pre_launch_failed",
+            LaunchError::PathNotFound(_) => "path_not_found",
+        };
+        Self::new(code, e.to_string())
+    }
+}
+
+impl From<BookmarkError> for CommandError {
+    fn from(e: BookmarkError) -> Self {
+        let code = match &e {
+            BookmarkError::PathMissing(_) => "file_not_found",
+            BookmarkError::Duplicate(_) => "duplicate",
+            BookmarkError::NotFound(_) => "not_found",
+            BookmarkError::Io(io) => io_code(io),
+            BookmarkError::Json(_) => "parse_error",
+        };
+        Self::new(code, e.to_string())
+    }
+}
+
+impl From<SettingsError> for CommandError {
+    fn from(e: SettingsError) -> Self {
+        let code = match &e {
+            SettingsError::Io(io) => io_code(io),
+            SettingsError::Json(_) => "parse_error",
+        };
+        Self::new(code, e.to_string())
+    }
+}
+
+// ============================================================
+// Tests
+// ============================================================
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io;
+    use std::path::PathBuf;
+
+    fn code(e: impl Into<CommandError>) -> String {
+        e.into().code
+    }
+
+    fn io_err(kind: ErrorKind) -> io::Error {
+        io::Error::new(kind, "boom")
+    }
+
+    fn json_err() -> serde_json::Error {
+        serde_json::from_str::<u32>("nope").unwrap_err()
+    }
+
+    #[test]
+    fn serializes_code_and_message() {
+        let json = serde_json::to_value(CommandError::from(SteamError::NotFound)).unwrap();
+        assert_eq!(json["code"], "steam_not_found");
+        assert_eq!(json["message"], "Steam installation not found");
+    }
+
+    #[test]
+    fn io_errors_keep_actionable_kinds() {
+        assert_eq!(code(io_err(ErrorKind::NotFound)), "file_not_found");
+        assert_eq!(
+            code(io_err(ErrorKind::PermissionDenied)),
+            "permission_denied"
+        );
+        assert_eq!(code(io_err(ErrorKind::Other)), "io_error");
+    }
+
+    #[test]
+    fn steam_and_epic_codes() {
+        assert_eq!(code(SteamError::NotFound), "steam_not_found");
+        assert_eq!(
+            code(SteamError::InvalidRoot(PathBuf::from("/x"))),
+            "invalid_steam_root"
+        );
+        assert_eq!(
+            code(SteamError::Io(io_err(ErrorKind::PermissionDenied))),
+            "permission_denied"
+        );
+        assert_eq!(code(EpicError::NotFound), "epic_not_found");
+        assert_eq!(code(EpicError::Io(io_err(ErrorKind::Other))), "io_error");
+        assert_eq!(code(EpicError::Json(json_err())), "parse_error");
+    }
+
+    #[test]
+    fn gog_and_itch_codes() {
+        assert_eq!(
+            code(GogError::Io(io_err(ErrorKind::NotFound))),
+            "file_not_found"
+        );
+        let db = rusqlite::Error::InvalidQuery;
+        assert_eq!(code(ItchError::Db(db)), "database_error");
+    }
+
+    #[test]
+    fn library_codes() {
+        assert_eq!(code(LibraryError::NotFound("x".into())), "not_found");
+        assert_eq!(
+            code(LibraryError::CollectionNotFound("x".into())),
+            "not_found"
+        );
+        assert_eq!(code(LibraryError::Io(io_err(ErrorKind::Other))), "io_error");
+        assert_eq!(code(LibraryError::Json(json_err())), "parse_error");
+        assert_eq!(
+            code(LibraryError::ExecutableMissing(PathBuf::from("/g"))),
+            "executable_missing"
+        );
+        assert_eq!(
+            code(LibraryError::Duplicate(PathBuf::from("/g"))),
+            "duplicate"
+        );
+        assert_eq!(
+            code(LibraryError::InvalidCover(PathBuf::from("/c.gif"))),
+            "invalid_cover"
+        );
+        assert_eq!(code(LibraryError::NewerVersion(2)), "newer_version");
+    }
+
+    #[test]
+    fn launch_codes() {
+        assert_eq!(
+            code(LaunchError::ExecutableNotFound("/g".into())),
+            "executable_missing"
+        );
+        assert_eq!(
+            code(LaunchError::SpawnFailed(io_err(
+                ErrorKind::PermissionDenied
+            ))),
+            "permission_denied"
+        );
+        assert_eq!(
+            code(LaunchError::SpawnFailed(io_err(ErrorKind::Other))),
+            "spawn_failed"
+        );
+        assert_eq!(code(LaunchError::EarlyExit(1)), "early_exit");
+        assert_eq!(code(LaunchError::NotRunning("k".into())), "not_running");
+        assert_eq!(code(LaunchError::NoRunner("g.exe".into())), "no_runner");
+        assert_eq!(
+            code(LaunchError::NoUriHandler("xdg-open".into())),
+            "no_uri_handler"
+        );
+        assert_eq!(
+            code(LaunchError::UriOpenFailed("xdg-open".into(), 4)),
+            "uri_open_failed"
+        );
+        assert_eq!(code(LaunchError::PreLaunchFailed(1)), "pre_launch_failed");
+        assert_eq!(
+            code(LaunchError::PathNotFound("/g".into())),
+            "path_not_found"
+        );
+    }
+
+    #[test]
+    fn bookmark_codes() {
+        assert_eq!(
+            code(BookmarkError::PathMissing("/x".into())),
+            "file_not_found"
+        );
+        assert_eq!(code(BookmarkError::Duplicate("/x".into())), "duplicate");
+        assert_eq!(code(BookmarkError::NotFound("/x".into())), "not_found");
+        assert_eq!(code(BookmarkError::Json(json_err())), "parse_error");
+    }
+
+    #[test]
+    fn settings_codes() {
+        assert_eq!(
+            code(SettingsError::Io(io_err(ErrorKind::PermissionDenied))),
+            "permission_denied"
+        );
+        assert_eq!(code(SettingsError::Json(json_err())), "parse_error");
+    }
+
+    #[test]
+    fn message_is_the_source_error_text() {
+        let e = CommandError::from(LibraryError::NotFound("abc".into()));
+        assert_eq!(e.message, "Game not found: abc");
+        assert_eq!(e.to_string(), e.message);
+    }
+}
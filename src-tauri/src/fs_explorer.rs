@@ -1,5 +1,10 @@
-use serde::Serialize;
-use std::path::Path;
+use serde::{Deserialize, Serialize};
+use std::path::{Component, Path, PathBuf};
+use thiserror::Error;
+use walkdir::WalkDir;
+
+/// Upper bound on [`find_executables`] results, so scanning a huge tree can't stall the UI.
+pub const MAX_EXECUTABLE_RESULTS: usize = 200;
 
 #[derive(Debug, Serialize, Clone)]
 pub struct DirEntry {
@@ -10,50 +15,320 @@ pub struct DirEntry {
     pub is_executable: bool,
     /// True for macOS .app bundles (directory ending in ".app").
     pub is_app_bundle: bool,
+    /// True when the entry itself is a symbolic link; the other flags describe its target.
+    pub is_symlink: bool,
+    /// False when the entry's metadata can't be read, e.g. a dangling symlink or a file
+    /// we lack permission to stat; the other flags are then all false.
+    pub accessible: bool,
 }
 
+/// A slice of a sorted directory listing, for folders too large to send at once.
 #[derive(Debug, Serialize, Clone)]
+pub struct DirPage {
+    pub entries: Vec<DirEntry>,
+    /// Number of entries in the whole listing, across all pages.
+    pub total: usize,
+}
+
+/// Narrows a directory listing for pickers. Directories always pass so navigation works.
+#[derive(Debug, Clone, PartialEq)]
+pub enum EntryFilter {
+    /// Executable files and `.app` bundles.
+    Executables,
+    /// Files whose extension matches one of these (lowercase, without the dot).
+    Extensions(Vec<String>),
+}
+
+impl EntryFilter {
+    /// Parses `executables` or `extensions:[exe, sh]` (brackets and dots optional).
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let spec = spec.trim();
+        if spec.eq_ignore_ascii_case("executables") {
+            return Ok(Self::Executables);
+        }
+        let Some(list) = spec.strip_prefix("extensions:") else {
+            return Err(format!("Unknown directory filter: {spec}"));
+        };
+        let extensions = list
+            .trim()
+            .trim_start_matches('[')
+            .trim_end_matches(']')
+            .split(',')
+            .map(|e| e.trim().trim_start_matches('.').to_lowercase())
+            .filter(|e| !e.is_empty())
+            .collect();
+        Ok(Self::Extensions(extensions))
+    }
+
+    /// Returns whether `entry` should stay in the listing.
+    pub fn keep(&self, entry: &DirEntry) -> bool {
+        if entry.is_dir {
+            return true;
+        }
+        match self {
+            Self::Executables => entry.is_executable || entry.is_app_bundle,
+            Self::Extensions(extensions) => Path::new(&entry.name)
+                .extension()
+                .and_then(|e| e.to_str())
+                .is_some_and(|e| extensions.iter().any(|x| x.eq_ignore_ascii_case(e))),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct Bookmark {
     pub label: String,
     pub path: String,
 }
 
+#[derive(Debug, Error)]
+pub enum BookmarkError {
+    #[error("Path does not exist: {0}")]
+    PathMissing(String),
+    #[error("Already bookmarked: {0}")]
+    Duplicate(String),
+    #[error("Bookmark not found: {0}")]
+    NotFound(String),
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Serialization error: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+/// User-defined file explorer bookmarks, persisted to a JSON file.
+pub struct BookmarkStore {
+    path: PathBuf,
+    bookmarks: Vec<Bookmark>,
+}
+
+impl BookmarkStore {
+    /// Loads bookmarks from `path`, starting empty if the file doesn't exist. An
+    /// unreadable or corrupt file is logged and ignored, since bookmarks aren't worth
+    /// blocking startup over.
+    pub fn load(path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        let bookmarks = match std::fs::read_to_string(&path) {
+            Ok(contents) => serde_json::from_str(&contents).unwrap_or_else(|e| {
+                log::warn!("Ignoring corrupt bookmarks file {:?}: {}", path, e);
+                Vec::new()
+            }),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => {
+                log::warn!("Ignoring unreadable bookmarks file {:?}: {}", path, e);
+                Vec::new()
+            }
+        };
+        Self { path, bookmarks }
+    }
+
+    pub fn bookmarks(&self) -> &[Bookmark] {
+        &self.bookmarks
+    }
+
+    /// Adds a bookmark for an existing path; a path can only be bookmarked once.
+    pub fn add(&mut self, label: &str, path: &str) -> Result<&Bookmark, BookmarkError> {
+        if !Path::new(path).exists() {
+            return Err(BookmarkError::PathMissing(path.to_string()));
+        }
+        if self.bookmarks.iter().any(|b| b.path == path) {
+            return Err(BookmarkError::Duplicate(path.to_string()));
+        }
+        log::info!("Adding bookmark {:?} -> {:?}", label, path);
+        self.bookmarks.push(Bookmark {
+            label: label.to_string(),
+            path: path.to_string(),
+        });
+        self.persist()?;
+        Ok(self.bookmarks.last().unwrap())
+    }
+
+    pub fn remove(&mut self, path: &str) -> Result<Bookmark, BookmarkError> {
+        let index = self
+            .bookmarks
+            .iter()
+            .position(|b| b.path == path)
+            .ok_or_else(|| BookmarkError::NotFound(path.to_string()))?;
+        let removed = self.bookmarks.remove(index);
+        log::info!("Removed bookmark {:?}", removed.path);
+        self.persist()?;
+        Ok(removed)
+    }
+
+    fn persist(&self) -> Result<(), BookmarkError> {
+        let json = serde_json::to_string_pretty(&self.bookmarks)?;
+        crate::library::write_atomic(&self.path, &json)?;
+        Ok(())
+    }
+}
+
 /// Reads the entries of `path`, hides dot-files, sorts directories first
 /// then alphabetically, and follows symlinks for metadata.
-pub fn read_dir(path: &str) -> Result<Vec<DirEntry>, String> {
-    let iter = std::fs::read_dir(path).map_err(|e| e.to_string())?;
+pub fn read_dir(path: &str) -> std::io::Result<Vec<DirEntry>> {
+    read_dir_opts(path, false)
+}
+
+/// Like [`read_dir`], but includes hidden entries when `show_hidden` is set.
+/// Hidden means a leading `.` on Unix and the hidden file attribute on Windows.
+pub fn read_dir_opts(path: &str, show_hidden: bool) -> std::io::Result<Vec<DirEntry>> {
+    let iter = std::fs::read_dir(path)?;
 
     let mut entries: Vec<DirEntry> = iter
         .filter_map(|r| r.ok())
         .filter_map(|entry| {
             let name = entry.file_name().to_string_lossy().to_string();
-            if name.starts_with('.') {
+            if !show_hidden && is_hidden(&entry) {
                 return None;
             }
-            // Follow symlinks so .app bundles report is_dir = true
-            let meta = std::fs::metadata(entry.path()).ok()?;
-            let is_dir = meta.is_dir();
-            let is_app_bundle = is_dir && name.ends_with(".app");
-            let is_executable = !is_dir && check_executable(entry.path().as_path());
-            Some(DirEntry {
-                name,
-                path: entry.path().to_string_lossy().to_string(),
-                is_dir,
-                is_executable,
-                is_app_bundle,
-            })
+            Some(dir_entry(&entry.path(), name))
         })
         .collect();
 
+    // Names differing only in case are ordered exactly, so pages never shuffle.
     entries.sort_by(|a, b| match (a.is_dir, b.is_dir) {
         (true, false) => std::cmp::Ordering::Less,
         (false, true) => std::cmp::Ordering::Greater,
-        _ => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+        _ => a
+            .name
+            .to_lowercase()
+            .cmp(&b.name.to_lowercase())
+            .then_with(|| a.name.cmp(&b.name)),
     });
 
     Ok(entries)
 }
 
+/// Returns up to `limit` entries of an already sorted listing starting at `offset`,
+/// with the listing's total size. An offset past the end yields an empty page.
+pub fn page(entries: Vec<DirEntry>, offset: usize, limit: usize) -> DirPage {
+    let total = entries.len();
+    DirPage {
+        entries: entries.into_iter().skip(offset).take(limit).collect(),
+        total,
+    }
+}
+
+/// Total size in bytes of the files under `path` (or of `path` itself if it's a file).
+/// Symlinks aren't followed and unreadable entries are skipped.
+pub fn dir_size(path: &Path) -> u64 {
+    WalkDir::new(path)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter_map(|e| e.metadata().ok())
+        .map(|m| m.len())
+        .sum()
+}
+
+/// Walks `root` up to `max_depth` levels deep and returns every executable file (and, on
+/// macOS, every `.app` bundle), sorted by path. When there are more than
+/// [`MAX_EXECUTABLE_RESULTS`], the first ones by path are kept.
+/// Hidden entries are skipped, and unreadable subdirectories are passed over silently.
+/// On macOS a bundle's contents aren't searched, so its inner binary isn't reported twice.
+pub fn find_executables(root: &Path, max_depth: usize) -> Vec<DirEntry> {
+    find_executables_matching(root, max_depth, |_| true).0
+}
+
+/// Like [`find_executables`], but only keeps entries for which `keep` returns `true`,
+/// before the cap applies. Also returns whether the cap cut the results short.
+pub fn find_executables_matching(
+    root: &Path,
+    max_depth: usize,
+    keep: impl Fn(&DirEntry) -> bool,
+) -> (Vec<DirEntry>, bool) {
+    // Walking in name order makes the capped subset the same on every filesystem.
+    let mut found: Vec<DirEntry> = WalkDir::new(root)
+        .max_depth(max_depth)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|e| {
+            let inside_bundle = cfg!(target_os = "macos")
+                && e.path()
+                    .parent()
+                    .and_then(|p| p.extension())
+                    .is_some_and(|ext| ext == "app");
+            e.depth() == 0 || !(inside_bundle || e.file_name().to_string_lossy().starts_with('.'))
+        })
+        .filter_map(|e| match e {
+            Ok(e) => Some(e),
+            Err(err) => {
+                log::debug!("find_executables: skipping {}", err);
+                None
+            }
+        })
+        .map(|e| dir_entry(e.path(), e.file_name().to_string_lossy().to_string()))
+        .filter(|e| e.is_executable || (cfg!(target_os = "macos") && e.is_app_bundle))
+        .filter(|e| keep(e))
+        .take(MAX_EXECUTABLE_RESULTS + 1)
+        .collect();
+    let truncated = found.len() > MAX_EXECUTABLE_RESULTS;
+    if truncated {
+        log::info!(
+            "find_executables: stopped at {} results under {:?}",
+            MAX_EXECUTABLE_RESULTS,
+            root
+        );
+        found.truncate(MAX_EXECUTABLE_RESULTS);
+    }
+    found.sort_by(|a, b| a.path.cmp(&b.path));
+    (found, truncated)
+}
+
+/// Builds the [`DirEntry`] for `path`, following symlinks so `.app` bundles report
+/// `is_dir = true`. An entry whose metadata can't be read is kept but marked
+/// inaccessible, so the listing still shows everything in the directory.
+fn dir_entry(path: &Path, name: String) -> DirEntry {
+    let meta = std::fs::metadata(path)
+        .map_err(|e| log::debug!("Cannot read metadata of {:?}: {}", path, e))
+        .ok();
+    let is_dir = meta.as_ref().is_some_and(|m| m.is_dir());
+    let is_app_bundle = is_dir && name.ends_with(".app");
+    let is_executable = meta.is_some() && !is_dir && check_executable(path);
+    let is_symlink = std::fs::symlink_metadata(path).is_ok_and(|m| m.file_type().is_symlink());
+    DirEntry {
+        name,
+        path: path.to_string_lossy().to_string(),
+        is_dir,
+        is_executable,
+        is_app_bundle,
+        is_symlink,
+        accessible: meta.is_some(),
+    }
+}
+
+/// Lexically resolves `.` and `..` components, drops trailing and repeated separators,
+/// and joins with the OS separator. `..` never climbs above a root (`/`, `C:\`, or a
+/// UNC share); leading `..` on relative paths is kept. Symlinks are not resolved.
+pub fn normalize_path(path: &str) -> String {
+    let mut out = PathBuf::new();
+    for component in Path::new(path).components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => match out.components().next_back() {
+                Some(Component::Normal(_)) => {
+                    out.pop();
+                }
+                Some(Component::RootDir | Component::Prefix(_)) => {}
+                _ => out.push(".."),
+            },
+            other => out.push(other.as_os_str()),
+        }
+    }
+    if out.as_os_str().is_empty() {
+        return ".".to_string();
+    }
+    out.to_string_lossy().to_string()
+}
+
+/// Returns the normalized parent of `path`, or `None` at a filesystem root.
+pub fn parent_directory(path: &str) -> Option<String> {
+    let normalized = normalize_path(path);
+    let parent = Path::new(&normalized).parent()?;
+    if parent.as_os_str().is_empty() {
+        return None;
+    }
+    Some(parent.to_string_lossy().to_string())
+}
+
 /// Returns platform-appropriate starting locations for the file browser.
 pub fn get_bookmarks() -> Vec<Bookmark> {
     let mut bm: Vec<Bookmark> = Vec::new();
@@ -98,6 +373,54 @@ pub fn get_bookmarks() -> Vec<Bookmark> {
     bm
 }
 
+/// Returns mounted drives: drive roots on Windows, `/Volumes/*` on macOS, and removable
+/// media under `/media/<user>` and `/run/media/<user>` on Linux.
+pub fn list_drives() -> Vec<Bookmark> {
+    #[cfg(windows)]
+    {
+        (b'A'..=b'Z')
+            .map(|letter| format!("{}:\\", letter as char))
+            .filter(|root| Path::new(root).exists())
+            .map(|root| Bookmark {
+                label: root.trim_end_matches('\\').to_string(),
+                path: root,
+            })
+            .collect()
+    }
+    #[cfg(target_os = "macos")]
+    {
+        mounts_under(Path::new("/Volumes"))
+    }
+    #[cfg(not(any(windows, target_os = "macos")))]
+    {
+        let Ok(user) = std::env::var("USER") else {
+            return Vec::new();
+        };
+        ["/media", "/run/media"]
+            .iter()
+            .flat_map(|base| mounts_under(&Path::new(base).join(&user)))
+            .collect()
+    }
+}
+
+/// Lists the visible subdirectories of `dir` as bookmarks, sorted by name.
+#[cfg(unix)]
+fn mounts_under(dir: &Path) -> Vec<Bookmark> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut mounts: Vec<Bookmark> = entries
+        .flatten()
+        .filter(|e| !is_hidden(e) && e.path().is_dir())
+        .map(|e| Bookmark {
+            label: e.file_name().to_string_lossy().to_string(),
+            path: e.path().to_string_lossy().to_string(),
+        })
+        .collect();
+    mounts.sort_by_key(|m| m.label.to_lowercase());
+    mounts
+}
+
 fn push_if_exists(bookmarks: &mut Vec<Bookmark>, label: &str, path: &str) {
     if Path::new(path).exists() {
         bookmarks.push(Bookmark {
@@ -107,6 +430,21 @@ fn push_if_exists(bookmarks: &mut Vec<Bookmark>, label: &str, path: &str) {
     }
 }
 
+#[cfg(not(windows))]
+fn is_hidden(entry: &std::fs::DirEntry) -> bool {
+    entry.file_name().to_string_lossy().starts_with('.')
+}
+
+#[cfg(windows)]
+fn is_hidden(entry: &std::fs::DirEntry) -> bool {
+    use std::os::windows::fs::MetadataExt;
+    const FILE_ATTRIBUTE_HIDDEN: u32 = 0x2;
+    entry
+        .metadata()
+        .map(|m| m.file_attributes() & FILE_ATTRIBUTE_HIDDEN != 0)
+        .unwrap_or(false)
+}
+
 #[cfg(unix)]
 fn check_executable(path: &Path) -> bool {
     use std::os::unix::fs::PermissionsExt;
@@ -157,6 +495,7 @@ mod tests {
         fs::remove_dir_all(dir).ok();
     }
 
+    #[cfg(not(windows))]
     #[test]
     fn read_dir_hides_dotfiles() {
         let dir = tmp_dir();
@@ -170,6 +509,74 @@ mod tests {
         fs::remove_dir_all(dir).ok();
     }
 
+    #[cfg(not(windows))]
+    #[test]
+    fn read_dir_opts_shows_dotfiles_when_asked() {
+        let dir = tmp_dir();
+        fs::write(dir.join(".hidden"), "").unwrap();
+        fs::write(dir.join("visible"), "").unwrap();
+
+        let hidden_off = read_dir_opts(dir.to_str().unwrap(), false).unwrap();
+        assert_eq!(hidden_off.len(), 1);
+        assert_eq!(hidden_off[0].name, "visible");
+
+        let hidden_on = read_dir_opts(dir.to_str().unwrap(), true).unwrap();
+        let names: Vec<_> = hidden_on.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, [".hidden", "visible"]);
+
+        fs::remove_dir_all(dir).ok();
+    }
+
+    // --- EntryFilter ---
+
+    /// A directory with a subfolder, an executable, a script, an image and a text file.
+    fn mixed_dir() -> std::path::PathBuf {
+        let dir = tmp_dir();
+        fs::create_dir(dir.join("saves")).unwrap();
+        write_executable(&dir.join(exe_name("game")));
+        fs::write(dir.join("start.SH"), "").unwrap();
+        fs::write(dir.join("cover.png"), "").unwrap();
+        fs::write(dir.join("readme.txt"), "").unwrap();
+        dir
+    }
+
+    fn filtered(dir: &Path, filter: &EntryFilter) -> Vec<String> {
+        read_dir(dir.to_str().unwrap())
+            .unwrap()
+            .into_iter()
+            .filter(|e| filter.keep(e))
+            .map(|e| e.name)
+            .collect()
+    }
+
+    #[test]
+    fn executables_filter_keeps_dirs_and_executables() {
+        let dir = mixed_dir();
+        let filter = EntryFilter::parse("executables").unwrap();
+        assert_eq!(
+            filtered(&dir, &filter),
+            ["saves".to_string(), exe_name("game")]
+        );
+        fs::remove_dir_all(dir).ok();
+    }
+
+    #[test]
+    fn extensions_filter_matches_case_insensitively() {
+        let dir = mixed_dir();
+        let filter = EntryFilter::parse("extensions:[.sh, PNG]").unwrap();
+        assert_eq!(
+            filter,
+            EntryFilter::Extensions(vec!["sh".to_string(), "png".to_string()])
+        );
+        assert_eq!(filtered(&dir, &filter), ["saves", "cover.png", "start.SH"]);
+        fs::remove_dir_all(dir).ok();
+    }
+
+    #[test]
+    fn unknown_filter_is_rejected() {
+        assert!(EntryFilter::parse("images").is_err());
+    }
+
     #[test]
     fn read_dir_is_alphabetical_within_type() {
         let dir = tmp_dir();
@@ -187,6 +594,105 @@ mod tests {
         fs::remove_dir_all(dir).ok();
     }
 
+    #[cfg(unix)]
+    #[test]
+    fn dangling_symlink_is_listed_as_inaccessible() {
+        let dir = tmp_dir();
+        fs::write(dir.join("game.sh"), "").unwrap();
+        std::os::unix::fs::symlink(dir.join("gone"), dir.join("broken")).unwrap();
+
+        let entries = read_dir(dir.to_str().unwrap()).unwrap();
+        let names: Vec<&str> = entries.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, ["broken", "game.sh"]);
+        assert!(!entries[0].accessible);
+        assert!(entries[0].is_symlink);
+        assert!(!entries[0].is_dir && !entries[0].is_executable);
+        assert!(entries[1].accessible);
+
+        fs::remove_dir_all(dir).ok();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlinks_are_flagged_and_describe_their_target() {
+        use std::os::unix::fs::PermissionsExt;
+        let dir = tmp_dir();
+        fs::create_dir(dir.join("Games")).unwrap();
+        fs::write(dir.join("run.sh"), "").unwrap();
+        fs::set_permissions(dir.join("run.sh"), fs::Permissions::from_mode(0o755)).unwrap();
+        std::os::unix::fs::symlink(dir.join("Games"), dir.join("games_link")).unwrap();
+        std::os::unix::fs::symlink(dir.join("run.sh"), dir.join("run_link")).unwrap();
+
+        let entries = read_dir(dir.to_str().unwrap()).unwrap();
+        let entry = |name: &str| entries.iter().find(|e| e.name == name).unwrap();
+        let dir_link = entry("games_link");
+        assert!(dir_link.is_symlink && dir_link.is_dir && dir_link.accessible);
+        let file_link = entry("run_link");
+        assert!(file_link.is_symlink && file_link.is_executable && !file_link.is_dir);
+        assert!(!entry("Games").is_symlink);
+        assert!(!entry("run.sh").is_symlink);
+
+        fs::remove_dir_all(dir).ok();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn unstattable_file_is_listed_as_inaccessible() {
+        use std::os::unix::fs::PermissionsExt;
+        let dir = tmp_dir();
+        let locked = dir.join("locked");
+        fs::create_dir(&locked).unwrap();
+        fs::write(locked.join("save.dat"), "").unwrap();
+        // Readable but not searchable: names can be listed, but not stat'ed.
+        fs::set_permissions(&locked, fs::Permissions::from_mode(0o400)).unwrap();
+
+        // Root ignores permissions, so there's nothing to test there.
+        if fs::metadata(locked.join("save.dat")).is_err() {
+            let entries = read_dir(locked.to_str().unwrap()).unwrap();
+            assert_eq!(entries.len(), 1);
+            assert_eq!(entries[0].name, "save.dat");
+            assert!(!entries[0].accessible);
+        }
+
+        fs::set_permissions(&locked, fs::Permissions::from_mode(0o755)).unwrap();
+        fs::remove_dir_all(dir).ok();
+    }
+
+    #[test]
+    fn pages_cover_large_listing_in_order() {
+        let dir = tmp_dir();
+        for i in 0..5 {
+            fs::create_dir(dir.join(format!("dir_{i}"))).unwrap();
+        }
+        for i in 0..245 {
+            fs::write(dir.join(format!("file_{i:03}.bin")), "").unwrap();
+        }
+        let path = dir.to_str().unwrap();
+
+        let mut paged = Vec::new();
+        for offset in (0..250).step_by(100) {
+            let page = page(read_dir(path).unwrap(), offset, 100);
+            assert_eq!(page.total, 250);
+            assert_eq!(page.entries.len(), (250 - offset).min(100));
+            paged.extend(page.entries.into_iter().map(|e| e.name));
+        }
+        let all: Vec<String> = read_dir(path)
+            .unwrap()
+            .into_iter()
+            .map(|e| e.name)
+            .collect();
+        assert_eq!(paged, all, "pages concatenate to the full listing");
+        assert_eq!(paged[4], "dir_4");
+        assert_eq!(paged[5], "file_000.bin");
+        assert_eq!(paged[100], "file_095.bin");
+
+        let past_end = page(read_dir(path).unwrap(), 300, 100);
+        assert!(past_end.entries.is_empty());
+        assert_eq!(past_end.total, 250);
+
+        fs::remove_dir_all(dir).ok();
+    }
+
     #[cfg(target_os = "macos")]
     #[test]
     fn app_bundle_directory_marked_as_app_bundle() {
@@ -203,6 +709,268 @@ mod tests {
         fs::remove_dir_all(dir).ok();
     }
 
+    // --- find_executables ---
+
+    /// Writes a file that counts as executable on this platform.
+    fn write_executable(path: &Path) {
+        fs::write(path, "#!/bin/sh\n").unwrap();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(path, fs::Permissions::from_mode(0o755)).unwrap();
+        }
+    }
+
+    fn exe_name(stem: &str) -> String {
+        if cfg!(unix) {
+            stem.to_string()
+        } else {
+            format!("{stem}.exe")
+        }
+    }
+
+    #[test]
+    fn find_executables_walks_nested_tree() {
+        let dir = tmp_dir();
+        fs::create_dir_all(dir.join("game/bin/x64")).unwrap();
+        fs::create_dir_all(dir.join(".cache")).unwrap();
+        write_executable(&dir.join(exe_name("launcher")));
+        write_executable(&dir.join("game/bin/x64").join(exe_name("game")));
+        write_executable(&dir.join(".cache").join(exe_name("hidden")));
+        fs::write(dir.join("game/readme.txt"), "").unwrap();
+        fs::write(dir.join("game/bin/data.pak"), "").unwrap();
+
+        let found = find_executables(&dir, 8);
+        let names: Vec<_> = found.iter().map(|e| e.name.clone()).collect();
+        assert_eq!(names, [exe_name("game"), exe_name("launcher")]);
+        assert!(found.iter().all(|e| e.is_executable && !e.is_dir));
+
+        fs::remove_dir_all(dir).ok();
+    }
+
+    #[test]
+    fn find_executables_respects_max_depth() {
+        let dir = tmp_dir();
+        fs::create_dir_all(dir.join("a/b")).unwrap();
+        write_executable(&dir.join(exe_name("top")));
+        write_executable(&dir.join("a/b").join(exe_name("deep")));
+
+        let found = find_executables(&dir, 2);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].name, exe_name("top"));
+
+        fs::remove_dir_all(dir).ok();
+    }
+
+    #[test]
+    fn find_executables_matching_filters_before_the_cap() {
+        let dir = tmp_dir();
+        for i in 0..=MAX_EXECUTABLE_RESULTS {
+            write_executable(&dir.join(exe_name(&format!("tool{i:03}"))));
+        }
+        write_executable(&dir.join(exe_name("game")));
+
+        let (all, truncated) = find_executables_matching(&dir, 1, |_| true);
+        assert_eq!(all.len(), MAX_EXECUTABLE_RESULTS);
+        assert!(truncated);
+        assert_eq!(all[0].name, exe_name("game"));
+        assert_eq!(
+            all.last().unwrap().name,
+            exe_name(&format!("tool{:03}", MAX_EXECUTABLE_RESULTS - 2)),
+            "the cap keeps the first executables by path"
+        );
+
+        let (games, truncated) =
+            find_executables_matching(&dir, 1, |e| !e.name.starts_with("tool"));
+        assert_eq!(games.len(), 1);
+        assert!(!truncated);
+
+        fs::remove_dir_all(dir).ok();
+    }
+
+    #[test]
+    fn find_executables_missing_root_is_empty() {
+        assert!(find_executables(Path::new("/no/such/path_xyzzy_test"), 4).is_empty());
+    }
+
+    // --- dir_size ---
+
+    #[test]
+    fn dir_size_sums_nested_files() {
+        let dir = tmp_dir();
+        fs::create_dir_all(dir.join("data/paks")).unwrap();
+        fs::write(dir.join("game.exe"), vec![0u8; 100]).unwrap();
+        fs::write(dir.join("data/paks/a.pak"), vec![0u8; 250]).unwrap();
+        assert_eq!(dir_size(&dir), 350);
+        assert_eq!(dir_size(&dir.join("game.exe")), 100);
+        assert_eq!(dir_size(&dir.join("missing")), 0);
+        fs::remove_dir_all(dir).ok();
+    }
+
+    // --- normalize_path / parent_directory ---
+
+    #[test]
+    fn normalize_resolves_dot_components() {
+        let expected = Path::new("a").join("c").to_string_lossy().to_string();
+        assert_eq!(normalize_path("a/b/../c"), expected);
+        assert_eq!(normalize_path("a/./c/"), expected);
+        assert_eq!(normalize_path("a/.."), ".");
+        assert_eq!(
+            normalize_path("../x"),
+            Path::new("..").join("x").to_string_lossy()
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn normalize_never_climbs_above_root() {
+        assert_eq!(normalize_path("/../.."), "/");
+        assert_eq!(normalize_path("//home//user/../games/"), "/home/games");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn parent_directory_of_nested_and_root_paths() {
+        assert_eq!(
+            parent_directory("/home/user/games/").as_deref(),
+            Some("/home/user")
+        );
+        assert_eq!(parent_directory("/home").as_deref(), Some("/"));
+        assert_eq!(parent_directory("/"), None);
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn windows_drive_and_unc_roots() {
+        assert_eq!(
+            normalize_path("C:/Games/../Program Files"),
+            r"C:\Program Files"
+        );
+        assert_eq!(parent_directory(r"C:\Games\").as_deref(), Some(r"C:\"));
+        assert_eq!(parent_directory(r"C:\"), None);
+        assert_eq!(
+            parent_directory(r"\\server\share\games").as_deref(),
+            Some(r"\\server\share\")
+        );
+        assert_eq!(parent_directory(r"\\server\share\"), None);
+    }
+
+    // --- user bookmarks ---
+
+    #[test]
+    fn user_bookmarks_persist_across_reload() {
+        let dir = tmp_dir();
+        let file = dir.join("bookmarks.json");
+        let target = dir.join("Games");
+        fs::create_dir(&target).unwrap();
+        let target = target.to_string_lossy().to_string();
+
+        let mut store = BookmarkStore::load(&file);
+        store.add("My Games", &target).unwrap();
+
+        let reloaded = BookmarkStore::load(&file);
+        assert_eq!(
+            reloaded.bookmarks(),
+            [Bookmark {
+                label: "My Games".to_string(),
+                path: target.clone(),
+            }]
+        );
+
+        let mut reloaded = reloaded;
+        reloaded.remove(&target).unwrap();
+        assert!(BookmarkStore::load(&file).bookmarks().is_empty());
+
+        fs::remove_dir_all(dir).ok();
+    }
+
+    #[test]
+    fn add_bookmark_rejects_missing_path_and_duplicates() {
+        let dir = tmp_dir();
+        let mut store = BookmarkStore::load(dir.join("bookmarks.json"));
+        assert!(matches!(
+            store.add("Nope", "/no/such/path_xyzzy_test"),
+            Err(BookmarkError::PathMissing(_))
+        ));
+
+        let path = dir.to_string_lossy().to_string();
+        store.add("Temp", &path).unwrap();
+        assert!(matches!(
+            store.add("Temp again", &path),
+            Err(BookmarkError::Duplicate(_))
+        ));
+        assert_eq!(store.bookmarks().len(), 1);
+
+        fs::remove_dir_all(dir).ok();
+    }
+
+    #[test]
+    fn remove_unknown_bookmark_returns_error() {
+        let dir = tmp_dir();
+        let mut store = BookmarkStore::load(dir.join("bookmarks.json"));
+        assert!(matches!(
+            store.remove("/not/bookmarked"),
+            Err(BookmarkError::NotFound(_))
+        ));
+        fs::remove_dir_all(dir).ok();
+    }
+
+    #[test]
+    fn bad_bookmark_files_load_empty_and_are_replaced_atomically() {
+        let dir = tmp_dir();
+        let file = dir.join("bookmarks.json");
+        fs::write(&file, "{ not json").unwrap();
+        let mut store = BookmarkStore::load(&file);
+        assert!(store.bookmarks().is_empty());
+
+        let target = dir.to_string_lossy().to_string();
+        store.add("Temp", &target).unwrap();
+        assert_eq!(BookmarkStore::load(&file).bookmarks().len(), 1);
+        assert!(!dir.join("bookmarks.json.tmp").exists());
+
+        // A directory where the file should be can't be read at all.
+        let unreadable = BookmarkStore::load(&dir);
+        assert!(unreadable.bookmarks().is_empty());
+
+        fs::remove_dir_all(dir).ok();
+    }
+
+    // --- list_drives ---
+
+    #[cfg(unix)]
+    #[test]
+    fn mounts_under_lists_visible_mount_dirs() {
+        let media = tmp_dir();
+        fs::create_dir(media.join("USB Stick")).unwrap();
+        fs::create_dir(media.join("games-ssd")).unwrap();
+        fs::create_dir(media.join(".trash")).unwrap();
+        fs::write(media.join("stray-file"), "").unwrap();
+
+        let mounts = mounts_under(&media);
+        let labels: Vec<_> = mounts.iter().map(|m| m.label.as_str()).collect();
+        assert_eq!(labels, ["games-ssd", "USB Stick"]);
+        assert_eq!(mounts[0].path, media.join("games-ssd").to_string_lossy());
+
+        fs::remove_dir_all(media).ok();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn mounts_under_missing_dir_is_empty() {
+        assert!(mounts_under(Path::new("/no/such/path_xyzzy_test")).is_empty());
+    }
+
+    #[test]
+    fn all_drive_paths_exist() {
+        for drive in list_drives() {
+            assert!(
+                Path::new(&drive.path).exists(),
+                "missing drive: {}",
+                drive.path
+            );
+        }
+    }
+
     #[test]
     fn bookmarks_are_non_empty() {
         assert!(!get_bookmarks().is_empty());
@@ -0,0 +1,558 @@
+use crate::epic::EpicGame;
+use crate::library::CustomGame;
+use crate::steam::SteamGame;
+use serde::{Serialize, Serializer};
+use std::path::Path;
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+/// A game from any source, serialized with a `source` discriminator so the frontend
+/// can tell them apart, and its [`Game::key`]:
+/// `{"key": "steam:440", "source": "steam", "app_id": 440, ...}`.
+// A listing holds at most a few thousand games; the padding isn't worth boxing every variant.
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Clone, PartialEq)]
+pub enum Game {
+    Steam(SteamGame),
+    Epic(EpicGame),
+    Custom(CustomGame),
+}
+
+impl Game {
+    /// The display title regardless of source.
+    pub fn title(&self) -> &str {
+        match self {
+            Self::Steam(g) => &g.name,
+            Self::Epic(g) => &g.display_name,
+            Self::Custom(g) => &g.title,
+        }
+    }
+
+    /// The `source` discriminator value: `steam`, `epic` or `custom`.
+    pub fn source(&self) -> &'static str {
+        match self {
+            Self::Steam(_) => "steam",
+            Self::Epic(_) => "epic",
+            Self::Custom(_) => "custom",
+        }
+    }
+
+    /// Where the game lives on disk; for custom games, the executable.
+    pub fn install_path(&self) -> &Path {
+        match self {
+            Self::Steam(g) => &g.install_dir,
+            Self::Epic(g) => &g.install_location,
+            Self::Custom(g) => &g.executable,
+        }
+    }
+
+    /// Unix timestamp of the last session: Steam's own record, or the end of the last
+    /// tracked launch for custom games. Epic keeps no such record locally.
+    pub fn last_played(&self) -> Option<u64> {
+        match self {
+            Self::Steam(g) => g.last_played,
+            Self::Epic(_) => None,
+            Self::Custom(g) => g.last_played,
+        }
+    }
+
+    /// The key favorites and collections refer to the game by: the store's external key
+    /// (`steam:<appid>`, `steam-shortcut:<id>`, `epic:<app_name>`) or `custom-<id>`.
+    pub fn key(&self) -> String {
+        match self {
+            Self::Steam(g) => g.external_key(),
+            Self::Epic(g) => g.external_key(),
+            Self::Custom(g) => format!("custom-{}", g.id),
+        }
+    }
+
+    /// Bytes on disk as reported by the store: Steam's `SizeOnDisk` or Epic's
+    /// `InstallSize`. Custom games have no recorded size.
+    pub fn store_size(&self) -> Option<u64> {
+        match self {
+            Self::Steam(g) => g.size_on_disk,
+            Self::Epic(g) => g.install_size,
+            Self::Custom(_) => None,
+        }
+    }
+
+    /// User tags; only custom games carry them.
+    pub fn tags(&self) -> &[String] {
+        match self {
+            Self::Custom(g) => &g.tags,
+            _ => &[],
+        }
+    }
+}
+
+impl Serialize for Game {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        #[derive(Serialize)]
+        #[serde(tag = "source", rename_all = "lowercase")]
+        enum Tagged<'a> {
+            Steam(&'a SteamGame),
+            Epic(&'a EpicGame),
+            Custom(&'a CustomGame),
+        }
+
+        #[derive(Serialize)]
+        struct Keyed<'a> {
+            key: String,
+            #[serde(flatten)]
+            game: Tagged<'a>,
+        }
+
+        let game = match self {
+            Self::Steam(g) => Tagged::Steam(g),
+            Self::Epic(g) => Tagged::Epic(g),
+            Self::Custom(g) => Tagged::Custom(g),
+        };
+        Keyed {
+            key: self.key(),
+            game,
+        }
+        .serialize(serializer)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Search
+// ---------------------------------------------------------------------------
+
+/// Returns whether `query` is a case-insensitive substring of the game's title, or of a
+/// custom game's tags or notes. An empty query matches everything.
+pub fn matches(game: &Game, query: &str) -> bool {
+    let query = query.trim().to_lowercase();
+    title_position(game, &query).is_some() || matches_metadata(game, &query)
+}
+
+/// Filters `games` by `query` and orders the hits by where the query appears in the title
+/// (title hits before tag/notes hits). When nothing matches as a substring, falls back to
+/// titles containing the query's characters in order, ranked by how early and tightly
+/// they match.
+pub fn search(games: Vec<Game>, query: &str) -> Vec<Game> {
+    let query = query.trim().to_lowercase();
+    if query.is_empty() {
+        return games;
+    }
+
+    let mut hits: Vec<(usize, Game)> = games
+        .iter()
+        .filter(|g| matches(g, &query))
+        .map(|g| (title_position(g, &query).unwrap_or(usize::MAX), g.clone()))
+        .collect();
+
+    if hits.is_empty() {
+        hits = games
+            .into_iter()
+            .filter_map(|g| {
+                let (start, span) = subsequence_match(&g.title().to_lowercase(), &query)?;
+                Some((start + span, g))
+            })
+            .collect();
+    }
+
+    hits.sort_by_key(|(rank, _)| *rank);
+    hits.into_iter().map(|(_, g)| g).collect()
+}
+
+/// Char position of `query` (already lowercased) in the game's title.
+fn title_position(game: &Game, query: &str) -> Option<usize> {
+    let title = game.title().to_lowercase();
+    let byte = title.find(query)?;
+    Some(title[..byte].chars().count())
+}
+
+fn matches_metadata(game: &Game, query: &str) -> bool {
+    let Game::Custom(g) = game else {
+        return false;
+    };
+    g.tags.iter().any(|t| t.to_lowercase().contains(query))
+        || g.notes
+            .as_deref()
+            .is_some_and(|n| n.to_lowercase().contains(query))
+}
+
+// ---------------------------------------------------------------------------
+// Recently played
+// ---------------------------------------------------------------------------
+
+/// Orders games by their last-played timestamp, newest first, and keeps the first `limit`.
+/// Games without a timestamp sort after all others, keeping their relative order.
+pub fn recently_played(mut games: Vec<(Game, Option<u64>)>, limit: usize) -> Vec<Game> {
+    games.sort_by_key(|(_, played)| std::cmp::Reverse(*played));
+    games.into_iter().take(limit).map(|(g, _)| g).collect()
+}
+
+// ---------------------------------------------------------------------------
+// Storage
+// ---------------------------------------------------------------------------
+
+/// Disk usage per source, from [`storage_usage`].
+#[derive(Debug, Clone, Default, Serialize, PartialEq)]
+pub struct StorageUsage {
+    pub steam_bytes: u64,
+    pub epic_bytes: u64,
+    pub custom_bytes: u64,
+    pub total_bytes: u64,
+    /// Games whose size is unknown and left out of the sums, e.g. Steam shortcuts or
+    /// custom games when their folders weren't measured.
+    pub games_without_size: usize,
+}
+
+/// Sums each game's size (paired with it, as bytes) into per-source totals.
+pub fn storage_usage(games: &[(Game, Option<u64>)]) -> StorageUsage {
+    let mut usage = StorageUsage::default();
+    for (game, size) in games {
+        let Some(size) = *size else {
+            usage.games_without_size += 1;
+            continue;
+        };
+        match game {
+            Game::Steam(_) => usage.steam_bytes += size,
+            Game::Epic(_) => usage.epic_bytes += size,
+            Game::Custom(_) => usage.custom_bytes += size,
+        }
+        usage.total_bytes += size;
+    }
+    usage
+}
+
+// ---------------------------------------------------------------------------
+// Export
+// ---------------------------------------------------------------------------
+
+/// Serializes `games` as a pretty-printed JSON array, in the same shape `get_all_games` returns.
+pub fn to_json(games: &[Game]) -> serde_json::Result<String> {
+    serde_json::to_string_pretty(games)
+}
+
+/// Serializes `games` as CSV with a `title,source,install_path,tags` header. Tags are
+/// joined with `;`. Fields containing commas, quotes or line breaks are quoted per RFC 4180.
+pub fn to_csv(games: &[Game]) -> String {
+    let mut csv = String::from("title,source,install_path,tags\n");
+    for game in games {
+        let fields = [
+            game.title().to_string(),
+            game.source().to_string(),
+            game.install_path().to_string_lossy().into_owned(),
+            game.tags().join(";"),
+        ];
+        let row: Vec<String> = fields.iter().map(|f| csv_field(f)).collect();
+        csv.push_str(&row.join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+/// Quotes a CSV field when needed, doubling any embedded quotes.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Finds `query`'s chars in order within `text`; returns the start position and the
+/// span between the first and last matched chars.
+fn subsequence_match(text: &str, query: &str) -> Option<(usize, usize)> {
+    let mut wanted = query.chars().filter(|c| !c.is_whitespace()).peekable();
+    let mut start = None;
+    let mut end = 0;
+    for (i, c) in text.chars().enumerate() {
+        let Some(&next) = wanted.peek() else {
+            break;
+        };
+        if c == next {
+            start.get_or_insert(i);
+            end = i;
+            wanted.next();
+        }
+    }
+    if wanted.peek().is_some() {
+        return None;
+    }
+    let start = start?;
+    Some((start, end - start))
+}
+
+// ============================================================
+// Tests
+// ============================================================
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn mixed_games() -> Vec<Game> {
+        vec![
+            Game::Steam(SteamGame {
+                app_id: 440,
+                name: "Team Fortress 2".to_string(),
+                install_dir: PathBuf::from("/steam/common/tf2"),
+                is_shortcut: false,
+                size_on_disk: None,
+                last_played: None,
+                cover_image: None,
+                owner: None,
+            }),
+            Game::Epic(EpicGame {
+                app_name: "Quail".to_string(),
+                display_name: "Hades".to_string(),
+                install_location: PathBuf::from("/epic/Hades"),
+                catalog_namespace: "ns".to_string(),
+                catalog_item_id: "id".to_string(),
+                cover_image: None,
+                launch_executable: None,
+                install_size: None,
+                app_version: None,
+            }),
+            Game::Custom(CustomGame::new(
+                "Celeste",
+                "/games/celeste",
+                None,
+                vec![],
+                None,
+            )),
+        ]
+    }
+
+    #[test]
+    fn serializes_source_discriminator() {
+        let json = serde_json::to_value(mixed_games()).unwrap();
+        let sources: Vec<&str> = json
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|g| g["source"].as_str().unwrap())
+            .collect();
+        assert_eq!(sources, ["steam", "epic", "custom"]);
+    }
+
+    #[test]
+    fn variant_fields_are_flattened_next_to_source() {
+        let json = serde_json::to_value(mixed_games()).unwrap();
+        assert_eq!(json[0]["app_id"], 440);
+        assert_eq!(json[1]["app_name"], "Quail");
+        assert_eq!(json[2]["title"], "Celeste");
+    }
+
+    #[test]
+    fn serializes_stable_key_per_source() {
+        let games = mixed_games();
+        let json = serde_json::to_value(&games).unwrap();
+        assert_eq!(json[0]["key"], "steam:440");
+        assert_eq!(json[1]["key"], "epic:Quail");
+        let Game::Custom(custom) = &games[2] else {
+            unreachable!()
+        };
+        assert_eq!(json[2]["key"], format!("custom-{}", custom.id));
+    }
+
+    // --- search ---
+
+    fn titles(games: &[Game]) -> Vec<&str> {
+        games.iter().map(Game::title).collect()
+    }
+
+    #[test]
+    fn matches_title_case_insensitively_for_each_source() {
+        let games = mixed_games();
+        assert!(matches(&games[0], "fortress"));
+        assert!(matches(&games[1], "HADES"));
+        assert!(matches(&games[2], " cel "));
+        assert!(!matches(&games[0], "hades"));
+    }
+
+    #[test]
+    fn matches_custom_tags_and_notes() {
+        let mut game = CustomGame::new("Celeste", "/games/celeste", None, vec![], None);
+        game.tags = vec!["Platformer".to_string()];
+        game.notes = Some("Chapter 7 B-side left".to_string());
+        let game = Game::Custom(game);
+        assert!(matches(&game, "platform"));
+        assert!(matches(&game, "b-side"));
+        assert!(!matches(&game, "roguelike"));
+    }
+
+    #[test]
+    fn search_ranks_by_match_position() {
+        let mut games = mixed_games();
+        games.push(Game::Custom(CustomGame::new(
+            "Fortress Forever",
+            "/games/ff",
+            None,
+            vec![],
+            None,
+        )));
+        let hits = search(games, "fortress");
+        assert_eq!(titles(&hits), ["Fortress Forever", "Team Fortress 2"]);
+    }
+
+    #[test]
+    fn search_falls_back_to_subsequence() {
+        let hits = search(mixed_games(), "tf2");
+        assert_eq!(titles(&hits), ["Team Fortress 2"]);
+        assert!(search(mixed_games(), "zzz").is_empty());
+    }
+
+    #[test]
+    fn empty_query_returns_everything() {
+        assert_eq!(search(mixed_games(), "  ").len(), 3);
+    }
+
+    // --- export ---
+
+    #[test]
+    fn csv_has_header_and_one_row_per_game() {
+        let mut games = mixed_games();
+        if let Game::Custom(g) = &mut games[2] {
+            g.tags = vec!["platformer".to_string(), "indie".to_string()];
+        }
+        let csv = to_csv(&games);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(
+            lines,
+            [
+                "title,source,install_path,tags",
+                "Team Fortress 2,steam,/steam/common/tf2,",
+                "Hades,epic,/epic/Hades,",
+                "Celeste,custom,/games/celeste,platformer;indie",
+            ]
+        );
+    }
+
+    #[test]
+    fn csv_quotes_commas_quotes_and_newlines() {
+        let games = vec![
+            Game::Custom(CustomGame::new(
+                "Papers, Please",
+                "/g/pp",
+                None,
+                vec![],
+                None,
+            )),
+            Game::Custom(CustomGame::new(
+                r#"The "Best" Game"#,
+                "/g/b",
+                None,
+                vec![],
+                None,
+            )),
+            Game::Custom(CustomGame::new("Line\nBreak", "/g/l", None, vec![], None)),
+        ];
+        let csv = to_csv(&games);
+        assert!(csv.contains("\"Papers, Please\",custom,/g/pp,\n"));
+        assert!(csv.contains(r#""The ""Best"" Game",custom,/g/b,"#));
+        assert!(csv.contains("\"Line\nBreak\",custom,/g/l,\n"));
+    }
+
+    #[test]
+    fn json_export_round_trips_source_tags() {
+        let json = to_json(&mixed_games()).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value.as_array().unwrap().len(), 3);
+        assert_eq!(value[1]["source"], "epic");
+    }
+
+    #[test]
+    fn title_reads_each_source() {
+        let titles: Vec<_> = mixed_games()
+            .iter()
+            .map(|g| g.title().to_string())
+            .collect();
+        assert_eq!(titles, ["Team Fortress 2", "Hades", "Celeste"]);
+    }
+
+    // --- recently_played ---
+
+    #[test]
+    fn recently_played_orders_newest_first_with_unplayed_last() {
+        let games = mixed_games();
+        let (tf2, hades, celeste) = (games[0].clone(), games[1].clone(), games[2].clone());
+        let mut portal = CustomGame::new("Portal", "/games/portal", None, vec![], None);
+        portal.last_played = Some(1_700_000_500);
+        let portal = Game::Custom(portal);
+
+        let ranked = recently_played(
+            vec![
+                (hades.clone(), None),
+                (tf2.clone(), Some(1_700_000_000)),
+                (celeste.clone(), None),
+                (portal.clone(), Some(1_700_000_500)),
+            ],
+            10,
+        );
+        assert_eq!(ranked, [portal, tf2, hades, celeste]);
+    }
+
+    #[test]
+    fn recently_played_respects_limit() {
+        let pairs: Vec<(Game, Option<u64>)> = mixed_games()
+            .into_iter()
+            .zip([Some(1), Some(3), None])
+            .collect();
+        let titles: Vec<String> = recently_played(pairs, 2)
+            .iter()
+            .map(|g| g.title().to_string())
+            .collect();
+        assert_eq!(titles, ["Hades", "Team Fortress 2"]);
+        assert!(recently_played(vec![], 5).is_empty());
+    }
+
+    #[test]
+    fn last_played_comes_from_steam_and_custom_games() {
+        let mut games = mixed_games();
+        if let Game::Steam(g) = &mut games[0] {
+            g.last_played = Some(42);
+        }
+        let played: Vec<Option<u64>> = games.iter().map(Game::last_played).collect();
+        assert_eq!(played, [Some(42), None, None]);
+    }
+
+    // --- storage_usage ---
+
+    #[test]
+    fn storage_usage_sums_sizes_per_source() {
+        let mut games = mixed_games();
+        if let Game::Steam(g) = &mut games[0] {
+            g.size_on_disk = Some(25_000_000_000);
+        }
+        if let Game::Epic(g) = &mut games[1] {
+            g.install_size = Some(15_000_000_000);
+        }
+        let sizes: Vec<Option<u64>> = games.iter().map(Game::store_size).collect();
+        assert_eq!(sizes, [Some(25_000_000_000), Some(15_000_000_000), None]);
+
+        let pairs: Vec<(Game, Option<u64>)> = games
+            .into_iter()
+            .zip([Some(25_000_000_000), Some(15_000_000_000), Some(1_200_000)])
+            .collect();
+        assert_eq!(
+            storage_usage(&pairs),
+            StorageUsage {
+                steam_bytes: 25_000_000_000,
+                epic_bytes: 15_000_000_000,
+                custom_bytes: 1_200_000,
+                total_bytes: 40_001_200_000,
+                games_without_size: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn storage_usage_counts_games_without_size() {
+        let pairs: Vec<(Game, Option<u64>)> = mixed_games()
+            .into_iter()
+            .zip([None, Some(500), None])
+            .collect();
+        let usage = storage_usage(&pairs);
+        assert_eq!(usage.steam_bytes, 0);
+        assert_eq!(usage.total_bytes, 500);
+        assert_eq!(usage.games_without_size, 2);
+        assert_eq!(storage_usage(&[]), StorageUsage::default());
+    }
+}
@@ -0,0 +1,261 @@
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GogGame {
+    /// GOG product id from the `.info` file `gameId` field.
+    pub id: String,
+    /// Human-readable title from the `.info` file `name` field.
+    pub title: String,
+    /// Absolute path to the directory containing the `.info` file.
+    pub install_dir: PathBuf,
+    /// Absolute path to a local cover image, or `None` when not found.
+    pub cover_image: Option<PathBuf>,
+}
+
+impl GogGame {
+    /// Constructs the GOG Galaxy launch URI for this game.
+    pub fn launch_uri(&self) -> String {
+        format!("go:launchgame/{}", self.id)
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum GogError {
+    #[error("Failed to read GOG install directory: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+// ---------------------------------------------------------------------------
+// .info schema (only fields we care about)
+// ---------------------------------------------------------------------------
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct InfoFile {
+    game_id: Option<String>,
+    name: Option<String>,
+    /// Set on DLC to the id of the base game; equal to `gameId` for base games.
+    root_game_id: Option<String>,
+}
+
+// ---------------------------------------------------------------------------
+// Public API
+// ---------------------------------------------------------------------------
+
+/// Returns all installed GOG games found under the platform's known install roots.
+///
+/// Every GOG installer drops a `goggame-<id>.info` JSON file in the game directory,
+/// so scanning those works on every OS without reading Galaxy's SQLite database.
+pub fn discover_games() -> Result<Vec<GogGame>, GogError> {
+    let mut games = Vec::new();
+    for root in install_roots() {
+        games.extend(discover_games_from(&root)?);
+    }
+    Ok(games)
+}
+
+/// Discovers GOG games from a specific install root (used in tests).
+///
+/// Each immediate subdirectory of `dir` is treated as a game install.
+pub fn discover_games_from(dir: &Path) -> Result<Vec<GogGame>, GogError> {
+    if !dir.exists() {
+        return Ok(vec![]);
+    }
+
+    let mut games = Vec::new();
+    for entry in std::fs::read_dir(dir)?.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            games.extend(find_info_file(&path).and_then(|info| parse_info(&info)));
+        }
+    }
+
+    Ok(games)
+}
+
+// ---------------------------------------------------------------------------
+// Private helpers
+// ---------------------------------------------------------------------------
+
+/// Returns the platform-appropriate directories GOG installs games into.
+fn install_roots() -> Vec<PathBuf> {
+    let mut roots = Vec::new();
+
+    #[cfg(target_os = "windows")]
+    {
+        roots.push(PathBuf::from(r"C:\Program Files (x86)\GOG Galaxy\Games"));
+        roots.push(PathBuf::from(r"C:\GOG Games"));
+    }
+    #[cfg(not(target_os = "windows"))]
+    {
+        if let Ok(home) = std::env::var("HOME") {
+            let home = PathBuf::from(home);
+            roots.push(home.join("GOG Games"));
+            roots.push(home.join("Games/GOG"));
+            roots.push(home.join("Games/Heroic"));
+        }
+    }
+    #[cfg(target_os = "macos")]
+    {
+        roots.push(PathBuf::from("/Applications"));
+    }
+
+    roots
+}
+
+/// Returns the first `goggame-*.info` file directly inside `install_dir`.
+fn find_info_file(install_dir: &Path) -> Option<PathBuf> {
+    let entries = std::fs::read_dir(install_dir).ok()?;
+    entries.flatten().map(|e| e.path()).find(|p| {
+        p.file_name()
+            .and_then(|n| n.to_str())
+            .map(|n| n.starts_with("goggame-") && n.ends_with(".info"))
+            .unwrap_or(false)
+    })
+}
+
+/// Parses a single `.info` file; returns `None` if it should be skipped.
+fn parse_info(path: &Path) -> Option<GogGame> {
+    let contents = std::fs::read_to_string(path).ok()?;
+    let info: InfoFile = serde_json::from_str(&contents).ok()?;
+
+    let id = info.game_id.filter(|s| !s.is_empty())?;
+    let title = info.name.filter(|s| !s.is_empty())?;
+
+    // DLC ships its own .info pointing back at the base game
+    if info.root_game_id.is_some_and(|root| root != id) {
+        return None;
+    }
+
+    let install_dir = path.parent()?.to_path_buf();
+    let cover_image = crate::epic::find_cover_image(&install_dir);
+
+    Some(GogGame {
+        id,
+        title,
+        install_dir,
+        cover_image,
+    })
+}
+
+// ============================================================
+// Tests
+// ============================================================
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn make_temp_dir(label: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("gog_test_{}_{}", label, uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    // Writes `root/<folder>/goggame-<id>.info` with the given JSON body.
+    fn write_info(root: &Path, folder: &str, id: &str, body: &str) -> PathBuf {
+        let game_dir = root.join(folder);
+        fs::create_dir_all(&game_dir).unwrap();
+        fs::write(game_dir.join(format!("goggame-{id}.info")), body).unwrap();
+        game_dir
+    }
+
+    #[test]
+    fn discovers_games_from_info_files() {
+        let root = make_temp_dir("happy");
+        write_info(
+            &root,
+            "Witcher 3",
+            "1207664663",
+            r#"{ "gameId": "1207664663", "rootGameId": "1207664663", "name": "The Witcher 3: Wild Hunt" }"#,
+        );
+        write_info(
+            &root,
+            "Celeste",
+            "1206927480",
+            r#"{ "gameId": "1206927480", "name": "Celeste", "playTasks": [] }"#,
+        );
+
+        let mut games = discover_games_from(&root).expect("should succeed");
+        games.sort_by(|a, b| a.title.cmp(&b.title));
+        assert_eq!(games.len(), 2);
+        assert_eq!(games[0].title, "Celeste");
+        assert_eq!(games[0].install_dir, root.join("Celeste"));
+        assert_eq!(games[1].id, "1207664663");
+
+        fs::remove_dir_all(&root).ok();
+    }
+
+    #[test]
+    fn missing_root_returns_empty() {
+        let missing = std::env::temp_dir().join("gog_test_absent_dir_99999");
+        let _ = fs::remove_dir_all(&missing);
+
+        let games = discover_games_from(&missing).expect("absent dir should return Ok");
+        assert!(games.is_empty());
+    }
+
+    #[test]
+    fn malformed_and_nameless_info_skipped() {
+        let root = make_temp_dir("malformed");
+        write_info(&root, "Broken", "1", "not json {{");
+        write_info(&root, "Nameless", "2", r#"{ "gameId": "2" }"#);
+        write_info(
+            &root,
+            "Good",
+            "3",
+            r#"{ "gameId": "3", "name": "Good Game" }"#,
+        );
+
+        let games = discover_games_from(&root).expect("should succeed");
+        assert_eq!(games.len(), 1);
+        assert_eq!(games[0].title, "Good Game");
+
+        fs::remove_dir_all(&root).ok();
+    }
+
+    #[test]
+    fn dlc_info_skipped() {
+        let root = make_temp_dir("dlc");
+        write_info(
+            &root,
+            "Expansion",
+            "1640424747",
+            r#"{ "gameId": "1640424747", "rootGameId": "1207664663", "name": "Blood and Wine" }"#,
+        );
+
+        let games = discover_games_from(&root).expect("should succeed");
+        assert!(games.is_empty(), "DLC entries must be excluded");
+
+        fs::remove_dir_all(&root).ok();
+    }
+
+    #[test]
+    fn cover_image_picked_up_from_install_dir() {
+        let root = make_temp_dir("cover");
+        let game_dir = write_info(&root, "Game", "9", r#"{ "gameId": "9", "name": "Game" }"#);
+        fs::write(game_dir.join("cover.png"), b"png").unwrap();
+
+        let games = discover_games_from(&root).unwrap();
+        assert_eq!(games[0].cover_image, Some(game_dir.join("cover.png")));
+
+        fs::remove_dir_all(&root).ok();
+    }
+
+    #[test]
+    fn launch_uri_format() {
+        let game = GogGame {
+            id: "1207664663".to_string(),
+            title: "The Witcher 3".to_string(),
+            install_dir: PathBuf::from("/games/witcher3"),
+            cover_image: None,
+        };
+        assert_eq!(game.launch_uri(), "go:launchgame/1207664663");
+    }
+}
@@ -0,0 +1,248 @@
+use rusqlite::{Connection, OpenFlags};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ItchGame {
+    /// butler cave id — stable per install.
+    pub id: String,
+    /// Human-readable title from the `games` table.
+    pub title: String,
+    /// Absolute path to the install folder.
+    pub install_path: PathBuf,
+    /// Absolute path to the launch candidate butler picked, or `None` when unknown.
+    /// Launched via [`crate::launcher::spawn_executable_with`].
+    pub executable: Option<PathBuf>,
+    /// Remote cover art URL from the itch.io catalog, when present.
+    pub cover_image: Option<String>,
+}
+
+#[derive(Debug, Error)]
+pub enum ItchError {
+    #[error("Failed to read itch database: {0}")]
+    Db(#[from] rusqlite::Error),
+}
+
+// ---------------------------------------------------------------------------
+// Verdict schema (only fields we care about)
+// ---------------------------------------------------------------------------
+
+/// butler's JSON summary of an install, stored in `caves.verdict`.
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+struct Verdict {
+    base_path: Option<String>,
+    #[serde(default)]
+    candidates: Vec<Candidate>,
+}
+
+#[derive(Deserialize)]
+struct Candidate {
+    path: String,
+}
+
+// ---------------------------------------------------------------------------
+// Public API
+// ---------------------------------------------------------------------------
+
+/// Returns all installed itch.io games, or `Ok(vec![])` if the itch app is absent.
+pub fn discover_games() -> Result<Vec<ItchGame>, ItchError> {
+    match db_path() {
+        Some(path) if path.exists() => discover_games_from(&path),
+        _ => Ok(vec![]),
+    }
+}
+
+/// Discovers itch.io games from a specific `butler.db` (used in tests).
+pub fn discover_games_from(db_path: &Path) -> Result<Vec<ItchGame>, ItchError> {
+    let conn = Connection::open_with_flags(db_path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+    let mut stmt = conn.prepare(
+        "SELECT caves.id, games.title, games.cover_url, caves.verdict,
+                install_locations.path, caves.install_folder_name
+         FROM caves
+         JOIN games ON games.id = caves.game_id
+         LEFT JOIN install_locations ON install_locations.id = caves.install_location_id",
+    )?;
+
+    let rows = stmt.query_map([], |row| {
+        Ok(CaveRow {
+            id: row.get(0)?,
+            title: row.get(1)?,
+            cover_url: row.get(2)?,
+            verdict: row.get(3)?,
+            location: row.get(4)?,
+            folder: row.get(5)?,
+        })
+    })?;
+
+    let mut games = Vec::new();
+    for row in rows {
+        match row {
+            Ok(row) => games.extend(row.into_game()),
+            Err(e) => log::warn!("Skipping unreadable itch cave row: {}", e),
+        }
+    }
+    Ok(games)
+}
+
+// ---------------------------------------------------------------------------
+// Private helpers
+// ---------------------------------------------------------------------------
+
+struct CaveRow {
+    id: String,
+    title: Option<String>,
+    cover_url: Option<String>,
+    verdict: Option<String>,
+    location: Option<String>,
+    folder: Option<String>,
+}
+
+impl CaveRow {
+    fn into_game(self) -> Option<ItchGame> {
+        let title = self.title.filter(|s| !s.is_empty())?;
+        let verdict: Verdict = self
+            .verdict
+            .and_then(|v| serde_json::from_str(&v).ok())
+            .unwrap_or_default();
+
+        // Prefer butler's recorded base path; otherwise rebuild it from the location.
+        let install_path = match (verdict.base_path, self.location, self.folder) {
+            (Some(base), _, _) if !base.is_empty() => PathBuf::from(base),
+            (_, Some(location), Some(folder)) => PathBuf::from(location).join(folder),
+            _ => return None,
+        };
+        let executable = verdict
+            .candidates
+            .first()
+            .map(|c| install_path.join(&c.path));
+
+        Some(ItchGame {
+            id: self.id,
+            title,
+            install_path,
+            executable,
+            cover_image: self.cover_url.filter(|s| !s.is_empty()),
+        })
+    }
+}
+
+/// Returns the platform-appropriate `butler.db` path.
+fn db_path() -> Option<PathBuf> {
+    #[cfg(target_os = "macos")]
+    {
+        let home = std::env::var("HOME").ok()?;
+        Some(PathBuf::from(home).join("Library/Application Support/itch/db/butler.db"))
+    }
+    #[cfg(target_os = "windows")]
+    {
+        let appdata = std::env::var("APPDATA").ok()?;
+        Some(PathBuf::from(appdata).join(r"itch\db\butler.db"))
+    }
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    {
+        let home = std::env::var("HOME").ok()?;
+        Some(PathBuf::from(home).join(".config/itch/db/butler.db"))
+    }
+}
+
+// ============================================================
+// Tests
+// ============================================================
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    /// Builds a sample `butler.db` with the subset of butler's schema we query.
+    fn sample_db() -> PathBuf {
+        let path = std::env::temp_dir().join(format!("itch_test_{}.db", uuid::Uuid::new_v4()));
+        let conn = Connection::open(&path).unwrap();
+        conn.execute_batch(
+            r#"
+            CREATE TABLE games (id INTEGER PRIMARY KEY, title TEXT, cover_url TEXT);
+            CREATE TABLE install_locations (id TEXT PRIMARY KEY, path TEXT);
+            CREATE TABLE caves (
+                id TEXT PRIMARY KEY,
+                game_id INTEGER,
+                install_location_id TEXT,
+                install_folder_name TEXT,
+                verdict TEXT
+            );
+            INSERT INTO install_locations VALUES ('loc1', '/home/user/.config/itch/apps');
+            INSERT INTO games VALUES (1, 'Celeste Classic', 'https://img.itch.zone/celeste.png');
+            INSERT INTO games VALUES (2, 'Baba Is You', NULL);
+            INSERT INTO games VALUES (3, 'Orphaned', NULL);
+            INSERT INTO caves VALUES ('cave-1', 1, 'loc1', 'celeste-classic',
+                '{"basePath": "/home/user/.config/itch/apps/celeste-classic",
+                  "candidates": [{"path": "bin/celeste", "flavor": "linux", "depth": 2}]}');
+            INSERT INTO caves VALUES ('cave-2', 2, 'loc1', 'baba-is-you', NULL);
+            INSERT INTO caves VALUES ('cave-3', 3, NULL, NULL, 'not json');
+            "#,
+        )
+        .unwrap();
+        path
+    }
+
+    #[test]
+    fn parses_caves_with_verdict() {
+        let db = sample_db();
+        let games = discover_games_from(&db).expect("should read sample db");
+
+        let celeste = games.iter().find(|g| g.id == "cave-1").unwrap();
+        assert_eq!(celeste.title, "Celeste Classic");
+        assert_eq!(
+            celeste.install_path,
+            PathBuf::from("/home/user/.config/itch/apps/celeste-classic")
+        );
+        assert_eq!(
+            celeste.executable,
+            Some(PathBuf::from(
+                "/home/user/.config/itch/apps/celeste-classic/bin/celeste"
+            ))
+        );
+        assert_eq!(
+            celeste.cover_image.as_deref(),
+            Some("https://img.itch.zone/celeste.png")
+        );
+
+        fs::remove_file(db).ok();
+    }
+
+    #[test]
+    fn falls_back_to_install_location_without_verdict() {
+        let db = sample_db();
+        let games = discover_games_from(&db).unwrap();
+
+        let baba = games.iter().find(|g| g.id == "cave-2").unwrap();
+        assert_eq!(
+            baba.install_path,
+            PathBuf::from("/home/user/.config/itch/apps/baba-is-you")
+        );
+        assert_eq!(baba.executable, None);
+        assert_eq!(baba.cover_image, None);
+
+        fs::remove_file(db).ok();
+    }
+
+    #[test]
+    fn cave_without_resolvable_path_skipped() {
+        let db = sample_db();
+        let games = discover_games_from(&db).unwrap();
+        assert_eq!(games.len(), 2);
+        assert!(games.iter().all(|g| g.id != "cave-3"));
+        fs::remove_file(db).ok();
+    }
+
+    #[test]
+    fn missing_db_is_an_error() {
+        let missing = std::env::temp_dir().join("itch_test_absent_99999.db");
+        let _ = fs::remove_file(&missing);
+        assert!(discover_games_from(&missing).is_err());
+    }
+}
@@ -1,5 +1,12 @@
-use std::path::Path;
-use std::process::Command;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::ffi::{OsStr, OsString};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -8,18 +15,165 @@ pub enum LaunchError {
     ExecutableNotFound(String),
     #[error("Failed to spawn process: {0}")]
     SpawnFailed(#[from] std::io::Error),
+    #[error("Game exited during startup with code {0}")]
+    EarlyExit(i32),
+    #[error("Game is not running: {0}")]
+    NotRunning(String),
+    #[error("No Wine/Proton runner configured for Windows executable: {0}")]
+    NoRunner(String),
+    #[error("No program to open links is installed (tried {0})")]
+    NoUriHandler(String),
+    #[error("{0} could not open the link (exit code {1})")]
+    UriOpenFailed(String, i32),
+    #[error("Pre-launch command failed with code {0}")]
+    PreLaunchFailed(i32),
+    #[error("Path not found: {0}")]
+    PathNotFound(String),
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum LaunchTarget {
-    Steam { app_id: u32 },
-    EpicGame { launch_uri: String },
-    Executable { path: String },
+    /// `flatpak` routes the launch through the Flatpak Steam client; see
+    /// [`LaunchTarget::through_flatpak`].
+    Steam {
+        app_id: u32,
+        flatpak: bool,
+    },
+    SteamShortcut {
+        app_id: u32,
+        flatpak: bool,
+    },
+    EpicGame {
+        launch_uri: String,
+    },
+    Executable {
+        path: String,
+        options: SpawnOptions,
+    },
+}
+
+/// How long `launch_game` waits in [`watch_startup`] for a game to crash on start.
+pub const EARLY_EXIT_WINDOW: Duration = Duration::from_secs(3);
+
+/// Attempts made for Steam/Epic URI launches from [`launch_and_track`], since the URI
+/// handler may not be registered yet right after login.
+const URI_LAUNCH_ATTEMPTS: u32 = 3;
+
+/// Wait before the first URI launch retry; doubled after each failed attempt.
+const URI_RETRY_DELAY: Duration = Duration::from_millis(500);
+
+/// How often the exit tracker checks whether a tracked child has finished.
+const EXIT_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Maximum bytes kept from each of stdout/stderr by [`spawn_executable_captured`].
+pub const CAPTURE_LIMIT: usize = 16 * 1024;
+
+/// The leading output of a captured child process, truncated to [`CAPTURE_LIMIT`] per stream.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct CapturedOutput {
+    pub stdout: String,
+    pub stderr: String,
+}
+
+/// A spawned game's process handle, shared between the exit tracker and [`ProcessRegistry`].
+pub type SharedChild = Arc<Mutex<Child>>;
+
+/// Directly spawned games keyed by their launch key, so they can be stopped later.
+#[derive(Default)]
+pub struct ProcessRegistry {
+    children: HashMap<String, SharedChild>,
+}
+
+impl ProcessRegistry {
+    /// Registers `child` under `key`, replacing any previous entry for that key.
+    pub fn insert(&mut self, key: impl Into<String>, child: SharedChild) {
+        self.children.insert(key.into(), child);
+    }
+
+    /// Returns the keys of games that are still running, sorted.
+    pub fn running(&mut self) -> Vec<String> {
+        self.prune();
+        let mut keys: Vec<String> = self.children.keys().cloned().collect();
+        keys.sort();
+        keys
+    }
+
+    /// Kills the game registered under `key` and reaps it.
+    pub fn stop(&mut self, key: &str) -> Result<(), LaunchError> {
+        self.prune();
+        let child = self
+            .children
+            .remove(key)
+            .ok_or_else(|| LaunchError::NotRunning(key.to_string()))?;
+        let mut child = child.lock().unwrap();
+        log::info!("Stopping game {:?} (pid {})", key, child.id());
+        child.kill()?;
+        child.wait()?;
+        Ok(())
+    }
+
+    /// Drops entries whose process has already exited.
+    fn prune(&mut self) {
+        self.children
+            .retain(|_, child| matches!(child.lock().unwrap().try_wait(), Ok(None)));
+    }
+}
+
+/// What launching a target would do, resolved without running anything: either a URI
+/// handed to the desktop's handler, or a program started with arguments and environment.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum LaunchPlan {
+    Uri {
+        uri: String,
+    },
+    Command {
+        program: String,
+        args: Vec<String>,
+        working_dir: Option<PathBuf>,
+        /// Only the variables set for this launch; the rest are inherited.
+        env: Vec<(String, String)>,
+    },
+}
+
+/// Extra process configuration applied when spawning a custom game's executable.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SpawnOptions {
+    /// Command-line arguments passed to the executable, in order.
+    pub args: Vec<String>,
+    /// Working directory for the child; inherits ours when `None`.
+    pub working_dir: Option<PathBuf>,
+    /// Variables added to or overriding the inherited environment; nothing is removed.
+    pub env: Vec<(String, String)>,
+    /// Starts the child in its own process group (or detached console on Windows) so it
+    /// keeps running when the launcher exits. Ignored for macOS `.app` bundles, which
+    /// `open` already hands off to launchd.
+    pub detached: bool,
+    /// Programs prepended to the command line on Linux, e.g. `["gamemoderun", "mangohud"]`.
+    /// Wrappers not found on `PATH` are skipped.
+    pub wrappers: Vec<String>,
+    /// Wine or Proton used to run Windows `.exe` files on Linux: a program name, a path to
+    /// a `proton` script, or a Proton build directory containing one.
+    pub runner: Option<String>,
+    /// Shell command run to completion before the game starts; a failure aborts the launch.
+    pub pre_launch: Option<String>,
+    /// Shell command run after a tracked game exits, e.g. to restore display settings.
+    pub post_exit: Option<String>,
 }
 
 impl LaunchTarget {
     pub fn steam(app_id: u32) -> Self {
-        Self::Steam { app_id }
+        Self::Steam {
+            app_id,
+            flatpak: false,
+        }
+    }
+
+    pub fn steam_shortcut(app_id: u32) -> Self {
+        Self::SteamShortcut {
+            app_id,
+            flatpak: false,
+        }
     }
 
     pub fn epic_game(launch_uri: impl Into<String>) -> Self {
@@ -27,71 +181,665 @@ impl LaunchTarget {
     }
 
     pub fn executable(path: impl Into<String>) -> Self {
-        Self::Executable { path: path.into() }
+        Self::executable_with(path, SpawnOptions::default())
+    }
+
+    pub fn executable_with(path: impl Into<String>, options: SpawnOptions) -> Self {
+        Self::Executable {
+            path: path.into(),
+            options,
+        }
+    }
+
+    /// Sends a Steam target's URI to the Flatpak client when `flatpak` is set, i.e. when
+    /// the Steam root in use is a Flatpak install. Other targets are returned unchanged.
+    pub fn through_flatpak(self, flatpak: bool) -> Self {
+        match self {
+            Self::Steam { app_id, .. } => Self::Steam { app_id, flatpak },
+            Self::SteamShortcut { app_id, .. } => Self::SteamShortcut { app_id, flatpak },
+            other => other,
+        }
     }
 
     /// Returns the Steam URI for a Steam target, or `None` for other targets.
     pub fn steam_uri(&self) -> Option<String> {
         match self {
-            Self::Steam { app_id } => Some(format!("steam://run/{}", app_id)),
+            Self::Steam { app_id, .. } => Some(format!("steam://run/{}", app_id)),
+            Self::SteamShortcut { app_id, .. } => Some(shortcut_uri(*app_id)),
             _ => None,
         }
     }
+
+    /// The URI a store launch opens, or the executable path for a direct launch, for
+    /// users to copy when debugging.
+    pub fn uri_or_path(&self) -> String {
+        match self {
+            Self::EpicGame { launch_uri } => launch_uri.clone(),
+            Self::Executable { path, .. } => path.clone(),
+            _ => self.steam_uri().unwrap_or_default(),
+        }
+    }
 }
 
 /// Launches the given target. For Steam and Epic games this opens the appropriate URI;
-/// for custom games it delegates to [`spawn_executable`] (child is discarded).
+/// for custom games it delegates to [`spawn_executable_with`] (child is discarded).
 pub fn launch(target: &LaunchTarget) -> Result<(), LaunchError> {
     match target {
-        LaunchTarget::Steam { app_id } => launch_steam(*app_id),
-        LaunchTarget::EpicGame { launch_uri } => open_uri(launch_uri),
-        LaunchTarget::Executable { path } => {
-            spawn_executable(path)?;
+        LaunchTarget::Steam { app_id, flatpak } => launch_steam(*app_id, *flatpak),
+        LaunchTarget::SteamShortcut { app_id, flatpak } => launch_steam_shortcut(*app_id, *flatpak),
+        LaunchTarget::EpicGame { launch_uri } => launch_epic(launch_uri),
+        LaunchTarget::Executable { path, options } => {
+            spawn_executable_with(path, options)?;
             Ok(())
         }
     }
 }
 
+/// Resolves what [`launch`] would do for `target` without spawning anything, for dry
+/// runs and "this will run: ..." previews. Missing executables aren't reported here;
+/// the plan shows what would be attempted.
+pub fn launch_plan(target: &LaunchTarget) -> LaunchPlan {
+    match target {
+        LaunchTarget::Steam { flatpak, .. } | LaunchTarget::SteamShortcut { flatpak, .. } => {
+            let uri = target.steam_uri().unwrap_or_default();
+            if *flatpak {
+                let mut cmd = Command::new("flatpak");
+                cmd.args(flatpak_steam_args(&uri));
+                return command_plan(&cmd);
+            }
+            LaunchPlan::Uri { uri }
+        }
+        LaunchTarget::EpicGame { launch_uri } => match legendary_command(launch_uri) {
+            Some(cmd) => command_plan(&cmd),
+            None => LaunchPlan::Uri {
+                uri: launch_uri.clone(),
+            },
+        },
+        LaunchTarget::Executable { path, options } => {
+            #[cfg(windows)]
+            if let Some((target, options)) = resolve_shortcut(path, options) {
+                return launch_plan(&LaunchTarget::executable_with(target, options));
+            }
+            if is_app_bundle(path) {
+                return command_plan(&app_bundle_command(path, options));
+            }
+            command_plan(&build_command(path, options))
+        }
+    }
+}
+
+/// Describes a built but unspawned `cmd` as a [`LaunchPlan::Command`].
+fn command_plan(cmd: &Command) -> LaunchPlan {
+    let lossy = |s: &OsStr| s.to_string_lossy().into_owned();
+    LaunchPlan::Command {
+        program: lossy(cmd.get_program()),
+        args: cmd.get_args().map(lossy).collect(),
+        working_dir: cmd.get_current_dir().map(Path::to_path_buf),
+        env: cmd
+            .get_envs()
+            .filter_map(|(k, v)| Some((lossy(k), lossy(v?))))
+            .collect(),
+    }
+}
+
+/// Launches the target like [`launch`], making up to `attempts` tries in total when
+/// spawning or opening the URI fails. Waits `delay` before the first retry and doubles
+/// it after each. Errors that a retry can't fix, like a missing executable, return at
+/// once; otherwise the last error is returned when every attempt fails.
+pub fn launch_with_retry(
+    target: &LaunchTarget,
+    attempts: u32,
+    delay: Duration,
+) -> Result<(), LaunchError> {
+    retry(attempts, delay, || launch(target))
+}
+
+/// Runs `attempt` until it succeeds, fails with a non-transient error, or has been
+/// tried `attempts` times (at least once). Spawn failures and openers that exit with
+/// an error, e.g. before the `steam://` handler is registered, are transient.
+fn retry<T>(
+    attempts: u32,
+    mut delay: Duration,
+    mut attempt: impl FnMut() -> Result<T, LaunchError>,
+) -> Result<T, LaunchError> {
+    let attempts = attempts.max(1);
+    let mut made = 1;
+    loop {
+        match attempt() {
+            Err(e @ (LaunchError::SpawnFailed(_) | LaunchError::UriOpenFailed(..)))
+                if made < attempts =>
+            {
+                log::warn!(
+                    "Launch attempt {}/{} failed: {}; retrying in {:?}",
+                    made,
+                    attempts,
+                    e,
+                    delay
+                );
+                std::thread::sleep(delay);
+                delay = delay.saturating_mul(2);
+                made += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Launches the target like [`launch`], but for directly spawned executables also starts a
+/// monitoring thread that waits for the child and calls `on_exit` with the session length.
+///
+/// URI-based targets (and macOS `.app` bundles handed to `open`) have no child to wait on,
+/// so `on_exit` is never called for them and `None` is returned. Otherwise the returned
+/// handle can be registered in a [`ProcessRegistry`] to stop the game later.
+pub fn launch_and_track<F>(
+    target: &LaunchTarget,
+    on_exit: F,
+) -> Result<Option<SharedChild>, LaunchError>
+where
+    F: FnOnce(Duration) + Send + 'static,
+{
+    let LaunchTarget::Executable { path, options } = target else {
+        return launch_with_retry(target, URI_LAUNCH_ATTEMPTS, URI_RETRY_DELAY).map(|_| None);
+    };
+    let Some(child) = spawn_executable_with(path, options)? else {
+        log::info!("No child handle for {}; playtime will not be tracked", path);
+        return Ok(None);
+    };
+    Ok(Some(track_child(child, path.clone(), options, on_exit)))
+}
+
+/// Like [`launch_and_track`], but also captures the child's stdout/stderr and passes the
+/// first [`CAPTURE_LIMIT`] bytes of each to `on_output` once both streams close.
+///
+/// Detached games are not captured, since their pipes would close with the launcher;
+/// `on_output` is never called for them.
+pub fn launch_and_track_captured<O, F>(
+    target: &LaunchTarget,
+    on_output: O,
+    on_exit: F,
+) -> Result<Option<SharedChild>, LaunchError>
+where
+    O: FnOnce(CapturedOutput) + Send + 'static,
+    F: FnOnce(Duration) + Send + 'static,
+{
+    let LaunchTarget::Executable { path, options } = target else {
+        return launch_with_retry(target, URI_LAUNCH_ATTEMPTS, URI_RETRY_DELAY).map(|_| None);
+    };
+    if is_app_bundle(path) || options.detached {
+        return launch_and_track(target, on_exit);
+    }
+    let child = spawn_executable_captured(path, options, on_output)?;
+    Ok(Some(track_child(child, path.clone(), options, on_exit)))
+}
+
+/// Watches `child` on a background thread and reports the session length to `on_exit`,
+/// after running the `post_exit` command from `options` if there is one.
+///
+/// The child is polled rather than blocked on so the returned handle stays usable for
+/// [`ProcessRegistry::stop`] while the game runs.
+fn track_child<F>(child: Child, path: String, options: &SpawnOptions, on_exit: F) -> SharedChild
+where
+    F: FnOnce(Duration) + Send + 'static,
+{
+    let post_exit = options
+        .post_exit
+        .as_deref()
+        .map(|command| hook_command(command, options));
+    let shared = Arc::new(Mutex::new(child));
+    let child = shared.clone();
+    let started = Instant::now();
+    std::thread::spawn(move || {
+        loop {
+            match child.lock().unwrap().try_wait() {
+                Ok(Some(_)) => break,
+                Ok(None) => {}
+                Err(e) => {
+                    log::warn!("Failed to wait on {}: {}", path, e);
+                    break;
+                }
+            }
+            std::thread::sleep(EXIT_POLL_INTERVAL);
+        }
+        let elapsed = started.elapsed();
+        log::info!("Process exited: {} after {}s", path, elapsed.as_secs());
+        if let Some(mut post_exit) = post_exit {
+            match post_exit.status() {
+                Ok(status) if status.success() => {}
+                Ok(status) => log::warn!("Post-exit command for {} failed: {}", path, status),
+                Err(e) => log::warn!("Failed to run post-exit command for {}: {}", path, e),
+            }
+        }
+        on_exit(elapsed);
+    });
+    shared
+}
+
+/// Watches a just-launched game for up to `window` and fails with
+/// [`LaunchError::EarlyExit`] if it exits with a failure status in that time (missing
+/// shared libraries, wrong architecture, ...).
+///
+/// A game still running when the window closes is treated as a successful launch and left
+/// running. Signal-terminated children report code `-1`.
+pub fn watch_startup(child: &SharedChild, window: Duration) -> Result<(), LaunchError> {
+    let deadline = Instant::now() + window;
+    while Instant::now() < deadline {
+        // The exit tracker may have reaped the child already; `try_wait` then returns
+        // the status it recorded.
+        if let Some(status) = child.lock().unwrap().try_wait()? {
+            if status.success() {
+                return Ok(());
+            }
+            let code = status.code().unwrap_or(-1);
+            log::warn!("Game exited during startup with code {}", code);
+            return Err(LaunchError::EarlyExit(code));
+        }
+        std::thread::sleep(Duration::from_millis(50));
+    }
+    log::info!(
+        "Game still running after {:?}; launch considered successful",
+        window
+    );
+    Ok(())
+}
+
 /// Opens the Steam URI for the given app ID using the OS default handler.
-pub fn launch_steam(app_id: u32) -> Result<(), LaunchError> {
+pub fn launch_steam(app_id: u32, flatpak: bool) -> Result<(), LaunchError> {
     let uri = format!("steam://run/{}", app_id);
     log::info!("Launching Steam game: app_id={} uri={}", app_id, uri);
-    open_uri(&uri)
+    warn_if_steam_stopped();
+    open_steam_uri(&uri, flatpak)
+}
+
+/// Opens the Steam URI for a non-Steam shortcut using the OS default handler.
+pub fn launch_steam_shortcut(app_id: u32, flatpak: bool) -> Result<(), LaunchError> {
+    let uri = shortcut_uri(app_id);
+    log::info!("Launching Steam shortcut: app_id={} uri={}", app_id, uri);
+    warn_if_steam_stopped();
+    open_steam_uri(&uri, flatpak)
+}
+
+/// Opens a `steam://` URI. For a Flatpak Steam the URI is passed to `flatpak run`,
+/// since the desktop's URI handler may not reach the sandboxed client.
+fn open_steam_uri(uri: &str, flatpak: bool) -> Result<(), LaunchError> {
+    if flatpak {
+        log::info!("Steam is a Flatpak; opening {} through flatpak run", uri);
+        Command::new("flatpak")
+            .args(flatpak_steam_args(uri))
+            .spawn()?;
+        return Ok(());
+    }
+    open_uri(uri)
+}
+
+/// Arguments for `flatpak` that hand `uri` to the Flatpak Steam client.
+fn flatpak_steam_args(uri: &str) -> [&str; 3] {
+    ["run", crate::steam::FLATPAK_STEAM_ID, uri]
+}
+
+/// Logs when Steam isn't running: the URI will cold-start it, which can take a while
+/// before the game appears.
+fn warn_if_steam_stopped() {
+    if !is_steam_running() {
+        log::warn!("Steam is not running; it will start before the game launches");
+    }
+}
+
+/// Launches an Epic game. On Linux, where the Epic launcher doesn't exist, this prefers
+/// `legendary launch <app_name>` when Legendary is on `PATH`; otherwise the URI is opened.
+fn launch_epic(uri: &str) -> Result<(), LaunchError> {
+    if let Some(mut cmd) = legendary_command(uri) {
+        log::info!("Launching Epic game via Legendary: {:?}", cmd.get_args());
+        cmd.spawn()?;
+        return Ok(());
+    }
+    log::info!("Launching Epic game: uri={}", uri);
+    open_uri(uri)
+}
+
+/// The `legendary launch <app_name>` command for an Epic URI, on Linux when Legendary
+/// is on `PATH`.
+fn legendary_command(uri: &str) -> Option<Command> {
+    if !cfg!(target_os = "linux") {
+        return None;
+    }
+    let app_name = epic_app_name(uri)?;
+    let legendary = find_in_path("legendary", std::env::var_os("PATH").as_deref())?;
+    let mut cmd = Command::new(legendary);
+    cmd.args(["launch", app_name]);
+    Some(cmd)
 }
 
-/// Spawns the game at `path` and returns the child process handle when available.
+/// Extracts the app name — the last `%3A`-separated segment — from an Epic launch URI.
+fn epic_app_name(uri: &str) -> Option<&str> {
+    let rest = uri.strip_prefix("com.epicgames.launcher://apps/")?;
+    let ids = rest.split('?').next()?;
+    ids.rsplit("%3A").next().filter(|s| !s.is_empty())
+}
+
+/// Builds the `steam://rungameid/` URI for a non-Steam shortcut.
+fn shortcut_uri(app_id: u32) -> String {
+    format!("steam://rungameid/{}", crate::steam::shortcut_game_id(app_id))
+}
+
+/// Spawns the game at `path` with `options` applied and returns the child process
+/// handle when available.
 ///
 /// On macOS, if `path` is a `.app` bundle directory the system `open` command is used
-/// — which hands off to launchd — so no direct child handle is returned (`None`).
-/// On all other platforms, or when `path` points to a regular executable, the process
-/// is spawned directly and `Some(child)` is returned.
-pub fn spawn_executable(path: &str) -> Result<Option<std::process::Child>, LaunchError> {
+/// — which hands off to launchd — so no direct child handle is returned (`None`), and
+/// the arguments are forwarded through `open --args`. On all other platforms, or when
+/// `path` points to a regular executable, the process is spawned directly and
+/// `Some(child)` is returned.
+pub fn spawn_executable_with(
+    path: &str,
+    options: &SpawnOptions,
+) -> Result<Option<std::process::Child>, LaunchError> {
+    #[cfg(windows)]
+    if let Some((target, options)) = resolve_shortcut(path, options) {
+        return spawn_executable_with(&target, &options);
+    }
+    check_executable(path, options)?;
+    run_pre_launch(options)?;
+
+    if is_app_bundle(path) {
+        log::info!("Launching macOS app bundle via open: {}", path);
+        app_bundle_command(path, options).spawn()?;
+        return Ok(None);
+    }
+
+    log::info!("Spawning executable: {} args={:?}", path, options.args);
+    Ok(Some(build_command(path, options).spawn()?))
+}
+
+/// Spawns `path` with stdout/stderr piped and drained on background threads.
+///
+/// Each stream is read to EOF so the child never blocks on a full pipe, but only the
+/// first [`CAPTURE_LIMIT`] bytes are kept. `on_output` runs once both streams close.
+pub fn spawn_executable_captured<O>(
+    path: &str,
+    options: &SpawnOptions,
+    on_output: O,
+) -> Result<Child, LaunchError>
+where
+    O: FnOnce(CapturedOutput) + Send + 'static,
+{
+    #[cfg(windows)]
+    if let Some((target, options)) = resolve_shortcut(path, options) {
+        return spawn_executable_captured(&target, &options, on_output);
+    }
+    check_executable(path, options)?;
+    run_pre_launch(options)?;
+
+    log::info!("Spawning executable with captured output: {}", path);
+    let mut child = build_command(path, options)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    let stdout = child.stdout.take().map(read_capped);
+    let stderr = child.stderr.take().map(read_capped);
+    std::thread::spawn(move || {
+        let collect =
+            |h: Option<JoinHandle<String>>| h.and_then(|h| h.join().ok()).unwrap_or_default();
+        on_output(CapturedOutput {
+            stdout: collect(stdout),
+            stderr: collect(stderr),
+        });
+    });
+    Ok(child)
+}
+
+/// For a `.lnk` path, returns the shortcut's target and `options` with the link's
+/// arguments and working directory filled in where `options` has none. Windows can't
+/// spawn a shortcut directly.
+#[cfg(windows)]
+fn resolve_shortcut(path: &str, options: &SpawnOptions) -> Option<(String, SpawnOptions)> {
+    if !crate::shell_link::is_shell_link(Path::new(path)) {
+        return None;
+    }
+    let link = crate::shell_link::resolve(Path::new(path))
+        .map_err(|e| log::warn!("Could not resolve shortcut {:?}: {}", path, e))
+        .ok()?;
+    let mut options = options.clone();
+    if options.args.is_empty() {
+        options.args = link.args;
+    }
+    if options.working_dir.is_none() {
+        options.working_dir = link.working_dir;
+    }
+    Some((link.target.to_string_lossy().into_owned(), options))
+}
+
+/// Reads `reader` to EOF on a new thread, keeping at most [`CAPTURE_LIMIT`] bytes.
+fn read_capped<R: Read + Send + 'static>(mut reader: R) -> JoinHandle<String> {
+    std::thread::spawn(move || {
+        let mut kept = Vec::new();
+        let mut buf = [0u8; 4096];
+        loop {
+            match reader.read(&mut buf) {
+                Ok(0) => break,
+                Ok(n) => {
+                    let room = CAPTURE_LIMIT.saturating_sub(kept.len());
+                    kept.extend_from_slice(&buf[..n.min(room)]);
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+                Err(_) => break,
+            }
+        }
+        String::from_utf8_lossy(&kept).into_owned()
+    })
+}
+
+/// `open <bundle> [--args <args>...]` for a macOS `.app` bundle.
+fn app_bundle_command(path: &str, options: &SpawnOptions) -> Command {
+    let mut cmd = Command::new("open");
+    cmd.arg(path);
+    if !options.args.is_empty() {
+        cmd.arg("--args").args(&options.args);
+    }
+    cmd
+}
+
+/// Returns `true` for macOS `.app` bundle directories, which are launched through `open`.
+fn is_app_bundle(path: &str) -> bool {
+    cfg!(target_os = "macos") && path.ends_with(".app") && Path::new(path).is_dir()
+}
+
+/// Fails early when `path` is missing, or is a Windows executable we have no runner for.
+fn check_executable(path: &str, options: &SpawnOptions) -> Result<(), LaunchError> {
     if !Path::new(path).exists() {
         log::warn!("Executable not found: {}", path);
         return Err(LaunchError::ExecutableNotFound(path.to_string()));
     }
+    if needs_runner(path) && options.runner.is_none() {
+        log::warn!("No runner configured for Windows executable: {}", path);
+        return Err(LaunchError::NoRunner(path.to_string()));
+    }
+    Ok(())
+}
 
-    #[cfg(target_os = "macos")]
-    if Path::new(path).is_dir() && path.ends_with(".app") {
-        log::info!("Launching macOS app bundle via open: {}", path);
-        Command::new("open").arg(path).spawn()?;
-        return Ok(None);
+/// Runs the `pre_launch` command from `options`, if any, and waits for it. A non-zero
+/// exit fails with [`LaunchError::PreLaunchFailed`] so the game isn't started.
+fn run_pre_launch(options: &SpawnOptions) -> Result<(), LaunchError> {
+    let Some(command) = options.pre_launch.as_deref() else {
+        return Ok(());
+    };
+    log::info!("Running pre-launch command: {}", command);
+    let status = hook_command(command, options).status()?;
+    if !status.success() {
+        let code = status.code().unwrap_or(-1);
+        log::warn!("Pre-launch command {:?} failed with code {}", command, code);
+        return Err(LaunchError::PreLaunchFailed(code));
+    }
+    Ok(())
+}
+
+/// Builds a shell invocation of a pre-launch/post-exit `command`, run with the game's
+/// working directory and environment.
+fn hook_command(command: &str, options: &SpawnOptions) -> Command {
+    let mut cmd = if cfg!(windows) {
+        let mut cmd = Command::new("cmd");
+        cmd.args(["/C", command]);
+        cmd
+    } else {
+        let mut cmd = Command::new("sh");
+        cmd.args(["-c", command]);
+        cmd
+    };
+    if let Some(dir) = &options.working_dir {
+        cmd.current_dir(dir);
+    }
+    cmd.envs(options.env.iter().map(|(k, v)| (k, v)));
+    cmd
+}
+
+/// Builds the `Command` for a directly spawned executable without running it.
+///
+/// On Linux, configured wrappers come first: `wrapper... -- <path> <args>`.
+fn build_command(path: &str, options: &SpawnOptions) -> Command {
+    let wrappers = if cfg!(target_os = "linux") {
+        resolve_wrappers(&options.wrappers, std::env::var_os("PATH").as_deref())
+    } else {
+        Vec::new()
+    };
+    let argv = runner_argv(path, options);
+    let mut cmd = match wrappers.split_first() {
+        Some((first, rest)) => {
+            let mut cmd = Command::new(first);
+            cmd.args(rest).arg("--").args(&argv);
+            cmd
+        }
+        None => {
+            let mut cmd = Command::new(&argv[0]);
+            cmd.args(&argv[1..]);
+            cmd
+        }
+    };
+    cmd.args(&options.args);
+    if let Some(dir) = &options.working_dir {
+        cmd.current_dir(dir);
+    }
+    cmd.envs(options.env.iter().map(|(k, v)| (k, v)));
+    if let Some(compat_data) = proton_compat_data(&argv, options) {
+        cmd.env("STEAM_COMPAT_DATA_PATH", compat_data);
+    }
+    if options.detached {
+        // Nobody reads a detached game's output once we exit, and writing to a closed pipe
+        // would kill it with SIGPIPE, so it gets no pipes at all.
+        cmd.stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null());
+        detach(&mut cmd);
+    }
+    cmd
+}
+
+/// Returns `true` for Windows `.exe` files on Linux, which must go through Wine/Proton.
+fn needs_runner(path: &str) -> bool {
+    cfg!(target_os = "linux")
+        && Path::new(path)
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("exe"))
+}
+
+/// Returns the program and leading arguments that run `path`: the path alone for native
+/// executables, `wine <exe>` or `proton run <exe>` for Windows ones on Linux.
+fn runner_argv(path: &str, options: &SpawnOptions) -> Vec<OsString> {
+    let Some(runner) = options.runner.as_deref().filter(|_| needs_runner(path)) else {
+        return vec![path.into()];
+    };
+    let runner = Path::new(runner);
+    let runner = if runner.is_dir() {
+        runner.join("proton")
+    } else {
+        runner.to_path_buf()
+    };
+    let mut argv = vec![runner.clone().into_os_string()];
+    if is_proton(&runner) {
+        argv.push("run".into());
     }
+    argv.push(path.into());
+    argv
+}
+
+fn is_proton(runner: &Path) -> bool {
+    runner.file_name() == Some(OsStr::new("proton"))
+}
 
-    log::info!("Spawning executable: {}", path);
-    Ok(Some(Command::new(path).spawn()?))
+/// Proton ignores `WINEPREFIX` and keeps its prefix under `STEAM_COMPAT_DATA_PATH/pfx`, so
+/// derive that variable from the game's `WINEPREFIX` unless it is set explicitly.
+fn proton_compat_data(argv: &[OsString], options: &SpawnOptions) -> Option<PathBuf> {
+    if !is_proton(Path::new(&argv[0])) {
+        return None;
+    }
+    let var = |name: &str| options.env.iter().find(|(k, _)| k == name).map(|(_, v)| v);
+    if var("STEAM_COMPAT_DATA_PATH").is_some() {
+        return None;
+    }
+    let prefix = Path::new(var("WINEPREFIX")?);
+    match prefix.file_name() {
+        Some(name) if name == "pfx" => prefix.parent().map(Path::to_path_buf),
+        _ => Some(prefix.to_path_buf()),
+    }
 }
 
+/// Resolves each wrapper against `search_path` (a `PATH`-style list), dropping missing ones.
+fn resolve_wrappers(wrappers: &[String], search_path: Option<&OsStr>) -> Vec<PathBuf> {
+    wrappers
+        .iter()
+        .filter_map(|name| {
+            let found = find_in_path(name, search_path);
+            if found.is_none() {
+                log::warn!("Launch wrapper {:?} not found on PATH; skipping", name);
+            }
+            found
+        })
+        .collect()
+}
+
+/// Looks `name` up in `search_path` (a `PATH`-style list). Names containing a path
+/// separator are used as-is when they exist.
+pub(crate) fn find_in_path(name: &str, search_path: Option<&OsStr>) -> Option<PathBuf> {
+    if name.contains(std::path::MAIN_SEPARATOR) {
+        return Some(PathBuf::from(name)).filter(|p| p.is_file());
+    }
+    std::env::split_paths(search_path?)
+        .map(|dir| dir.join(name))
+        .find(|p| p.is_file())
+}
+
+/// Moves the child out of our process group so closing the launcher doesn't take it down.
+#[cfg(unix)]
+fn detach(cmd: &mut Command) {
+    use std::os::unix::process::CommandExt;
+    cmd.process_group(0);
+}
+
+/// Gives the child its own process group and no inherited console.
+#[cfg(windows)]
+fn detach(cmd: &mut Command) {
+    use std::os::windows::process::CommandExt;
+    const DETACHED_PROCESS: u32 = 0x0000_0008;
+    const CREATE_NEW_PROCESS_GROUP: u32 = 0x0000_0200;
+    cmd.creation_flags(DETACHED_PROCESS | CREATE_NEW_PROCESS_GROUP);
+}
+
+#[cfg(not(any(unix, windows)))]
+fn detach(_cmd: &mut Command) {}
+
 /// Resolves the process name that the OS will report for the given executable path.
 ///
-/// For macOS `.app` bundles, this inspects `Contents/MacOS/` to find the actual
-/// binary name (e.g. `"/Applications/Aseprite.app"` → `"aseprite"`).
+/// For macOS `.app` bundles, this is the `CFBundleExecutable` declared in
+/// `Contents/Info.plist`, or failing that the first file in `Contents/MacOS/`
+/// (e.g. `"/Applications/Aseprite.app"` → `"aseprite"`).
 /// For plain executables on all platforms, this is simply the file name.
 pub fn resolve_process_name(exe_path: &str) -> String {
     let path = Path::new(exe_path);
 
     #[cfg(target_os = "macos")]
     if exe_path.ends_with(".app") {
+        if let Some(name) = bundle_executable(path) {
+            return name;
+        }
         let macos_dir = path.join("Contents/MacOS");
         if let Ok(entries) = std::fs::read_dir(&macos_dir) {
             // Return the first non-hidden, non-directory entry
@@ -120,25 +868,170 @@ pub fn resolve_process_name(exe_path: &str) -> String {
         .unwrap_or_default()
 }
 
-/// Opens a URI using the platform's default handler.
-fn open_uri(uri: &str) -> Result<(), LaunchError> {
-    #[cfg(target_os = "macos")]
-    {
-        Command::new("open").arg(uri).spawn()?;
+// ---------------------------------------------------------------------------
+// Process detection
+// ---------------------------------------------------------------------------
+
+/// Process names of the Steam client on Windows/Linux (`steam[.exe]`) and macOS.
+const STEAM_PROCESS_NAMES: &[&str] = &["steam", "steam_osx"];
+
+/// Linux reports process names truncated to this many bytes (the kernel's `comm` field).
+const LINUX_COMM_LEN: usize = 15;
+
+/// Names of all processes currently running, as reported by the OS.
+pub fn running_process_names() -> Vec<String> {
+    use sysinfo::{ProcessRefreshKind, ProcessesToUpdate, System};
+    let mut system = System::new();
+    system.refresh_processes_specifics(ProcessesToUpdate::All, true, ProcessRefreshKind::nothing());
+    system
+        .processes()
+        .values()
+        .map(|p| p.name().to_string_lossy().into_owned())
+        .collect()
+}
+
+/// Whether the Steam client is running right now.
+pub fn is_steam_running() -> bool {
+    steam_in(&running_process_names())
+}
+
+/// Whether a process named `process_name` (as from [`resolve_process_name`]) is running,
+/// e.g. a game started through Steam rather than spawned by us.
+pub fn is_process_running(process_name: &str) -> bool {
+    process_in(&running_process_names(), process_name)
+}
+
+/// Whether `processes` includes the Steam client. Helpers like `steamwebhelper` don't
+/// count, since they can linger after Steam exits.
+fn steam_in<S: AsRef<str>>(processes: &[S]) -> bool {
+    STEAM_PROCESS_NAMES
+        .iter()
+        .any(|steam| process_in(processes, steam))
+}
+
+/// Whether any of `processes` is `wanted`. Names compare case-insensitively with an
+/// optional `.exe` suffix on either side, and a 15-byte name also matches a longer
+/// `wanted` it's a prefix of, since Linux truncates process names to that length.
+fn process_in<S: AsRef<str>>(processes: &[S], wanted: &str) -> bool {
+    let wanted = strip_exe(wanted).to_lowercase();
+    if wanted.is_empty() {
+        return false;
     }
-    #[cfg(target_os = "linux")]
-    {
-        Command::new("xdg-open").arg(uri).spawn()?;
+    processes.iter().any(|name| {
+        let name = strip_exe(name.as_ref()).to_lowercase();
+        name == wanted
+            || (cfg!(target_os = "linux")
+                && name.len() == LINUX_COMM_LEN
+                && wanted.starts_with(&name))
+    })
+}
+
+/// `name` without a trailing `.exe` (any case).
+fn strip_exe(name: &str) -> &str {
+    match name.len().checked_sub(4).and_then(|i| name.get(i..)) {
+        Some(ext) if ext.eq_ignore_ascii_case(".exe") => &name[..name.len() - 4],
+        _ => name,
     }
-    #[cfg(target_os = "windows")]
-    {
-        Command::new("cmd").args(["/C", "start", "", uri]).spawn()?;
+}
+
+/// Reads the main executable's name from a bundle's `Contents/Info.plist`. Returns `None`
+/// when the plist is missing or unreadable, lacks `CFBundleExecutable`, or names a file
+/// that isn't in `Contents/MacOS/`.
+#[cfg(target_os = "macos")]
+fn bundle_executable(bundle: &Path) -> Option<String> {
+    let contents = bundle.join("Contents");
+    let info = plist::Value::from_file(contents.join("Info.plist"))
+        .map_err(|e| log::debug!("No readable Info.plist in {:?}: {}", bundle, e))
+        .ok()?;
+    let name = info
+        .as_dictionary()?
+        .get("CFBundleExecutable")?
+        .as_string()?
+        .to_string();
+    contents.join("MacOS").join(&name).is_file().then_some(name)
+}
+
+/// Opens a URI using the platform's default handler. Waits for the opener to exit, so a
+/// handler that isn't registered yet surfaces as [`LaunchError::UriOpenFailed`].
+pub fn open_uri(uri: &str) -> Result<(), LaunchError> {
+    open_uri_with(uri, URI_OPENERS, |program, args| {
+        let status = Command::new(program).args(args).status()?;
+        Ok(status.code().unwrap_or(-1))
+    })
+}
+
+/// Programs that open a URI with the desktop's default handler, tried in order, each
+/// with the arguments that precede the URI.
+#[cfg(target_os = "macos")]
+const URI_OPENERS: &[(&str, &[&str])] = &[("open", &[])];
+#[cfg(target_os = "linux")]
+const URI_OPENERS: &[(&str, &[&str])] = &[("xdg-open", &[]), ("gio", &["open"])];
+#[cfg(target_os = "windows")]
+const URI_OPENERS: &[(&str, &[&str])] = &[("cmd", &["/C", "start", ""])];
+#[cfg(not(any(target_os = "macos", target_os = "linux", target_os = "windows")))]
+const URI_OPENERS: &[(&str, &[&str])] = &[];
+
+/// Shows `path` in the system file manager. On macOS and Windows its folder opens with
+/// the item selected; on Linux, where there's no common way to select, the folder
+/// containing it is opened.
+pub fn reveal_in_file_manager(path: &Path) -> Result<(), LaunchError> {
+    if !path.exists() {
+        log::warn!("Cannot reveal missing path {:?}", path);
+        return Err(LaunchError::PathNotFound(path.display().to_string()));
     }
-    #[cfg(not(any(target_os = "macos", target_os = "linux", target_os = "windows")))]
-    {
-        let _ = uri;
+    log::info!("Revealing {:?} in the file manager", path);
+    // Not waited on: Explorer exits with 1 even when the folder opened.
+    open_uri_with(&reveal_target(path), REVEAL_OPENERS, |program, args| {
+        Command::new(program).args(args).spawn().map(|_| 0)
+    })
+}
+
+/// Programs that show a path in the file manager, in the same form as [`URI_OPENERS`].
+#[cfg(target_os = "macos")]
+const REVEAL_OPENERS: &[(&str, &[&str])] = &[("open", &["-R"])];
+#[cfg(target_os = "windows")]
+const REVEAL_OPENERS: &[(&str, &[&str])] = &[("explorer", &[])];
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+const REVEAL_OPENERS: &[(&str, &[&str])] = URI_OPENERS;
+
+/// The final argument for [`REVEAL_OPENERS`]: `/select,<path>` for Explorer, the path
+/// itself for `open -R`, and the parent directory for the Linux URI openers.
+fn reveal_target(path: &Path) -> String {
+    if cfg!(target_os = "windows") {
+        format!("/select,{}", path.display())
+    } else if cfg!(target_os = "macos") {
+        path.display().to_string()
+    } else {
+        path.parent().unwrap_or(path).display().to_string()
     }
-    Ok(())
+}
+
+/// Opens `uri` with the first of `openers` that `run` can start; `run` returns the
+/// opener's exit code. An opener whose program isn't installed falls through to the
+/// next; any other spawn failure is returned as is, and a non-zero exit code as
+/// [`LaunchError::UriOpenFailed`]. If none is installed the result is
+/// [`LaunchError::NoUriHandler`].
+fn open_uri_with(
+    uri: &str,
+    openers: &[(&str, &[&str])],
+    mut run: impl FnMut(&str, &[&str]) -> std::io::Result<i32>,
+) -> Result<(), LaunchError> {
+    for (program, args) in openers {
+        let argv: Vec<&str> = args.iter().copied().chain([uri]).collect();
+        match run(program, &argv) {
+            Ok(0) => return Ok(()),
+            Ok(code) => {
+                log::warn!("URI opener {:?} exited with code {}", program, code);
+                return Err(LaunchError::UriOpenFailed(program.to_string(), code));
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                log::warn!("URI opener {:?} is not installed", program);
+            }
+            Err(e) => return Err(e.into()),
+        }
+    }
+    let tried: Vec<&str> = openers.iter().map(|(program, _)| *program).collect();
+    Err(LaunchError::NoUriHandler(tried.join(", ")))
 }
 
 // ============================================================
@@ -172,6 +1065,109 @@ mod tests {
     /// This is exercised at the lib.rs command level; here we confirm LaunchTarget
     /// itself cannot be constructed ambiguously (the enum enforces one variant).
 
+    /// Shortcut targets build the `rungameid` URI and match `SteamGame::launch_uri`.
+    #[test]
+    fn steam_shortcut_uri_matches_steam_game() {
+        let game = crate::steam::SteamGame {
+            app_id: 3_000_000_000,
+            name: "My Emulator".to_string(),
+            install_dir: std::path::PathBuf::from("/fake"),
+            is_shortcut: true,
+            size_on_disk: None,
+            last_played: None,
+            cover_image: None,
+            owner: None,
+        };
+        let target = LaunchTarget::steam_shortcut(game.app_id);
+        assert_eq!(
+            target,
+            LaunchTarget::SteamShortcut {
+                app_id: 3_000_000_000,
+                flatpak: false
+            }
+        );
+        assert_eq!(target.steam_uri(), Some(game.launch_uri()));
+    }
+
+    #[test]
+    fn uri_or_path_for_each_target() {
+        assert_eq!(LaunchTarget::steam(440).uri_or_path(), "steam://run/440");
+        // (3_000_000_000 << 32) | 0x02000000
+        assert_eq!(
+            LaunchTarget::steam_shortcut(3_000_000_000).uri_or_path(),
+            "steam://rungameid/12884901888033554432"
+        );
+        let uri = "com.epicgames.launcher://apps/ns%3Aid%3AFortnite?action=launch&silent=true";
+        assert_eq!(LaunchTarget::epic_game(uri).uri_or_path(), uri);
+        assert_eq!(
+            LaunchTarget::executable("/games/quake/quake").uri_or_path(),
+            "/games/quake/quake"
+        );
+    }
+
+    #[test]
+    fn flatpak_steam_command_line() {
+        assert_eq!(
+            flatpak_steam_args("steam://run/440"),
+            ["run", "com.valvesoftware.Steam", "steam://run/440"]
+        );
+    }
+
+    /// Steam targets build the same URI as `SteamGame::launch_uri` for real games.
+    #[test]
+    fn steam_uri_matches_steam_game() {
+        let game = crate::steam::SteamGame {
+            app_id: 440,
+            name: "Team Fortress 2".to_string(),
+            install_dir: std::path::PathBuf::from("/fake"),
+            is_shortcut: false,
+            size_on_disk: None,
+            last_played: None,
+            cover_image: None,
+            owner: None,
+        };
+        assert_eq!(LaunchTarget::steam(game.app_id).steam_uri(), Some(game.launch_uri()));
+    }
+
+    /// Epic targets carry `EpicGame::launch_uri` through unchanged.
+    #[test]
+    fn epic_target_uri_matches_epic_game() {
+        let game = crate::epic::EpicGame {
+            app_name: "Fortnite".to_string(),
+            display_name: "Fortnite".to_string(),
+            install_location: std::path::PathBuf::from("/fake"),
+            catalog_namespace: "fn".to_string(),
+            catalog_item_id: "abc".to_string(),
+            cover_image: None,
+            launch_executable: None,
+            install_size: None,
+            app_version: None,
+        };
+        let target = LaunchTarget::epic_game(game.launch_uri());
+        assert_eq!(
+            target,
+            LaunchTarget::EpicGame { launch_uri: game.launch_uri() }
+        );
+    }
+
+    /// Legendary is launched by app name, recovered from the Epic URI.
+    #[test]
+    fn epic_app_name_parsed_from_uri() {
+        let game = crate::epic::EpicGame {
+            app_name: "Quail".to_string(),
+            display_name: "Hades".to_string(),
+            install_location: std::path::PathBuf::from("/fake"),
+            catalog_namespace: String::new(),
+            catalog_item_id: String::new(),
+            cover_image: None,
+            launch_executable: None,
+            install_size: None,
+            app_version: None,
+        };
+        assert_eq!(epic_app_name(&game.launch_uri()), Some("Quail"));
+        assert_eq!(epic_app_name("steam://run/440"), None);
+    }
+
     /// T019 (regression): Steam variant still builds and steam_uri() works.
     #[test]
     fn steam_regression_target_builds() {
@@ -185,7 +1181,10 @@ mod tests {
         let target = LaunchTarget::executable("/games/game.exe");
         assert_eq!(
             target,
-            LaunchTarget::Executable { path: "/games/game.exe".to_string() }
+            LaunchTarget::Executable {
+                path: "/games/game.exe".to_string(),
+                options: SpawnOptions::default(),
+            }
         );
     }
 
@@ -209,7 +1208,8 @@ mod tests {
         assert_eq!(
             target,
             LaunchTarget::Executable {
-                path: "/games/hollow_knight".to_string()
+                path: "/games/hollow_knight".to_string(),
+                options: SpawnOptions::default(),
             }
         );
     }
@@ -217,14 +1217,21 @@ mod tests {
     #[test]
     fn steam_target_stores_app_id() {
         let target = LaunchTarget::steam(570);
-        assert_eq!(target, LaunchTarget::Steam { app_id: 570 });
+        assert_eq!(
+            target,
+            LaunchTarget::Steam {
+                app_id: 570,
+                flatpak: false
+            }
+        );
     }
 
-    // --- spawn_executable ---
+    // --- spawn_executable_with ---
 
     #[test]
     fn spawn_nonexistent_returns_error() {
-        let err = spawn_executable("/absolutely/does/not/exist.exe").unwrap_err();
+        let err = spawn_executable_with("/absolutely/does/not/exist.exe", &SpawnOptions::default())
+            .unwrap_err();
         assert!(matches!(err, LaunchError::ExecutableNotFound(_)));
     }
 
@@ -234,7 +1241,7 @@ mod tests {
         if !Path::new("/usr/bin/true").exists() {
             return;
         }
-        let child = spawn_executable("/usr/bin/true")
+        let child = spawn_executable_with("/usr/bin/true", &SpawnOptions::default())
             .expect("should not error")
             .expect("direct binary should give Some(child)");
         drop(child); // let it clean up
@@ -255,6 +1262,590 @@ mod tests {
         assert!(!status.success());
     }
 
+    #[test]
+    fn build_command_applies_args_and_working_dir() {
+        let options = SpawnOptions {
+            args: vec!["--windowed".to_string(), "-novid".to_string()],
+            working_dir: Some(PathBuf::from("/games/hollow_knight")),
+            ..Default::default()
+        };
+        let cmd = build_command("/games/hollow_knight/hk", &options);
+        let args: Vec<_> = cmd.get_args().collect();
+        assert_eq!(args, ["--windowed", "-novid"]);
+        assert_eq!(
+            cmd.get_current_dir(),
+            Some(Path::new("/games/hollow_knight"))
+        );
+    }
+
+    #[test]
+    fn build_command_defaults_leave_command_untouched() {
+        let cmd = build_command("/games/hollow_knight/hk", &SpawnOptions::default());
+        assert_eq!(cmd.get_args().count(), 0);
+        assert_eq!(cmd.get_current_dir(), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn build_command_injects_env_vars() {
+        if !Path::new("/usr/bin/env").exists() {
+            return;
+        }
+        let options = SpawnOptions {
+            env: vec![("GAME_LIBRARY_TEST_VAR".to_string(), "mangohud".to_string())],
+            ..Default::default()
+        };
+        let output = build_command("/usr/bin/env", &options)
+            .output()
+            .expect("env should run");
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(stdout
+            .lines()
+            .any(|l| l == "GAME_LIBRARY_TEST_VAR=mangohud"));
+        // Inherited variables are kept alongside the injected ones.
+        assert!(stdout.lines().any(|l| l.starts_with("PATH=")));
+    }
+
+    #[cfg(unix)]
+    fn make_fake_bins(names: &[&str]) -> PathBuf {
+        use std::os::unix::fs::PermissionsExt;
+        let dir = std::env::temp_dir().join(format!("wrappers_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        for name in names {
+            let bin = dir.join(name);
+            std::fs::write(&bin, "#!/bin/sh\nexec \"$@\"\n").unwrap();
+            std::fs::set_permissions(&bin, std::fs::Permissions::from_mode(0o755)).unwrap();
+        }
+        dir
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn wrappers_resolved_from_search_path_in_order() {
+        let dir = make_fake_bins(&["gamemoderun", "mangohud"]);
+        let wrappers = vec!["gamemoderun".to_string(), "mangohud".to_string()];
+        let resolved = resolve_wrappers(&wrappers, Some(dir.as_os_str()));
+        assert_eq!(resolved, [dir.join("gamemoderun"), dir.join("mangohud")]);
+        std::fs::remove_dir_all(dir).ok();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn unknown_wrapper_skipped() {
+        let dir = make_fake_bins(&["mangohud"]);
+        let wrappers = vec!["not-a-real-wrapper".to_string(), "mangohud".to_string()];
+        let resolved = resolve_wrappers(&wrappers, Some(dir.as_os_str()));
+        assert_eq!(resolved, [dir.join("mangohud")]);
+        std::fs::remove_dir_all(dir).ok();
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn build_command_puts_wrappers_before_executable() {
+        let dir = make_fake_bins(&["gamemoderun", "mangohud"]);
+        let gamemode = dir.join("gamemoderun").to_string_lossy().into_owned();
+        let mangohud = dir.join("mangohud").to_string_lossy().into_owned();
+        let options = SpawnOptions {
+            args: vec!["-fullscreen".to_string()],
+            wrappers: vec![
+                gamemode.clone(),
+                "missing-wrapper".to_string(),
+                mangohud.clone(),
+            ],
+            ..Default::default()
+        };
+
+        let cmd = build_command("/games/quake/quake", &options);
+        assert_eq!(cmd.get_program(), OsStr::new(&gamemode));
+        let args: Vec<_> = cmd.get_args().collect();
+        assert_eq!(
+            args,
+            [
+                OsStr::new(&mangohud),
+                OsStr::new("--"),
+                OsStr::new("/games/quake/quake"),
+                OsStr::new("-fullscreen"),
+            ]
+        );
+        std::fs::remove_dir_all(dir).ok();
+    }
+
+    // --- Launch plans ---
+
+    #[test]
+    fn steam_plans_open_the_run_uri() {
+        for (target, uri) in [
+            (LaunchTarget::steam(440), "steam://run/440".to_string()),
+            (
+                LaunchTarget::steam_shortcut(3_000_000_000),
+                shortcut_uri(3_000_000_000),
+            ),
+        ] {
+            assert_eq!(launch_plan(&target), LaunchPlan::Uri { uri: uri.clone() });
+            // A Flatpak Steam root routes the URI through `flatpak run`.
+            match launch_plan(&target.through_flatpak(true)) {
+                LaunchPlan::Command { program, args, .. } => {
+                    assert_eq!(program, "flatpak");
+                    assert_eq!(args, flatpak_steam_args(&uri));
+                }
+                plan => panic!("expected a flatpak command, got {:?}", plan),
+            }
+        }
+    }
+
+    #[test]
+    fn epic_plan_opens_the_uri_or_runs_legendary() {
+        let uri = "com.epicgames.launcher://apps/ns%3Aid%3AFortnite?action=launch&silent=true";
+        match launch_plan(&LaunchTarget::epic_game(uri)) {
+            LaunchPlan::Uri { uri: planned } => assert_eq!(planned, uri),
+            LaunchPlan::Command { program, args, .. } => {
+                assert!(program.ends_with("legendary"), "{}", program);
+                assert_eq!(args, ["launch", "Fortnite"]);
+            }
+        }
+    }
+
+    #[test]
+    fn executable_plan_has_program_args_cwd_and_env() {
+        let options = SpawnOptions {
+            args: vec!["--windowed".to_string()],
+            working_dir: Some(PathBuf::from("/games/hollow_knight")),
+            env: vec![("DXVK_HUD".to_string(), "fps".to_string())],
+            ..Default::default()
+        };
+        let plan = launch_plan(&LaunchTarget::executable_with(
+            "/games/hollow_knight/hk",
+            options,
+        ));
+        assert_eq!(
+            plan,
+            LaunchPlan::Command {
+                program: "/games/hollow_knight/hk".to_string(),
+                args: vec!["--windowed".to_string()],
+                working_dir: Some(PathBuf::from("/games/hollow_knight")),
+                env: vec![("DXVK_HUD".to_string(), "fps".to_string())],
+            }
+        );
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn windows_exe_plan_goes_through_runner() {
+        let options = SpawnOptions {
+            args: vec!["-skipintro".to_string()],
+            runner: Some("wine".to_string()),
+            ..Default::default()
+        };
+        let plan = launch_plan(&LaunchTarget::executable_with(
+            "/games/witcher/witcher.exe",
+            options,
+        ));
+        let LaunchPlan::Command { program, args, .. } = plan else {
+            panic!("expected a command plan, got {:?}", plan);
+        };
+        assert_eq!(program, "wine");
+        assert_eq!(args, ["/games/witcher/witcher.exe", "-skipintro"]);
+    }
+
+    // --- Pre-launch and post-exit commands ---
+
+    #[cfg(unix)]
+    #[test]
+    fn failing_pre_launch_aborts_the_launch() {
+        let dir = std::env::temp_dir().join(format!("hooks_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let marker = dir.join("game-ran");
+        let options = SpawnOptions {
+            args: vec!["-c".to_string(), format!("touch {}", marker.display())],
+            pre_launch: Some("exit 3".to_string()),
+            ..Default::default()
+        };
+
+        let result = spawn_executable_with("/bin/sh", &options);
+        assert!(matches!(result, Err(LaunchError::PreLaunchFailed(3))));
+        std::thread::sleep(Duration::from_millis(100));
+        assert!(!marker.exists(), "the game must not start");
+
+        std::fs::remove_dir_all(dir).ok();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn hooks_run_before_and_after_the_game() {
+        let dir = std::env::temp_dir().join(format!("hooks_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let options = SpawnOptions {
+            args: vec!["-c".to_string(), "echo game >> order.log".to_string()],
+            working_dir: Some(dir.clone()),
+            pre_launch: Some("echo pre >> order.log".to_string()),
+            post_exit: Some("echo post >> order.log".to_string()),
+            ..Default::default()
+        };
+        let (tx, rx) = std::sync::mpsc::channel();
+        let target = LaunchTarget::executable_with("/bin/sh", options);
+        launch_and_track(&target, move |_| tx.send(()).unwrap()).unwrap();
+
+        rx.recv_timeout(Duration::from_secs(10))
+            .expect("on_exit should fire");
+        let log = std::fs::read_to_string(dir.join("order.log")).unwrap();
+        assert_eq!(log.lines().collect::<Vec<_>>(), ["pre", "game", "post"]);
+
+        std::fs::remove_dir_all(dir).ok();
+    }
+
+    // --- Wine/Proton runners ---
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn windows_exe_runs_through_wine() {
+        let options = SpawnOptions {
+            args: vec!["-skipintro".to_string()],
+            runner: Some("wine".to_string()),
+            env: vec![("WINEPREFIX".to_string(), "/pfx/witcher".to_string())],
+            ..Default::default()
+        };
+        let cmd = build_command("/games/witcher/witcher.exe", &options);
+        assert_eq!(cmd.get_program(), "wine");
+        let args: Vec<_> = cmd.get_args().collect();
+        assert_eq!(args, ["/games/witcher/witcher.exe", "-skipintro"]);
+        let prefix = cmd
+            .get_envs()
+            .find(|(k, _)| *k == "WINEPREFIX")
+            .and_then(|(_, v)| v);
+        assert_eq!(prefix, Some(OsStr::new("/pfx/witcher")));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn windows_exe_runs_through_proton() {
+        let options = SpawnOptions {
+            runner: Some("/opt/proton/GE-Proton9/proton".to_string()),
+            env: vec![("WINEPREFIX".to_string(), "/compat/witcher/pfx".to_string())],
+            ..Default::default()
+        };
+        let cmd = build_command("/games/witcher/WITCHER.EXE", &options);
+        assert_eq!(cmd.get_program(), "/opt/proton/GE-Proton9/proton");
+        let args: Vec<_> = cmd.get_args().collect();
+        assert_eq!(args, ["run", "/games/witcher/WITCHER.EXE"]);
+        let compat = cmd
+            .get_envs()
+            .find(|(k, _)| *k == "STEAM_COMPAT_DATA_PATH")
+            .and_then(|(_, v)| v);
+        assert_eq!(compat, Some(OsStr::new("/compat/witcher")));
+    }
+
+    #[test]
+    fn runner_ignored_for_native_executables() {
+        let options = SpawnOptions {
+            runner: Some("wine".to_string()),
+            ..Default::default()
+        };
+        let cmd = build_command("/games/quake/quake", &options);
+        assert_eq!(cmd.get_program(), "/games/quake/quake");
+        assert_eq!(cmd.get_args().count(), 0);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn windows_exe_without_runner_is_rejected() {
+        let exe = std::env::temp_dir().join(format!("runner_test_{}.exe", uuid::Uuid::new_v4()));
+        std::fs::write(&exe, b"MZ").unwrap();
+
+        let err =
+            spawn_executable_with(&exe.to_string_lossy(), &SpawnOptions::default()).unwrap_err();
+        assert!(matches!(err, LaunchError::NoRunner(_)), "got {:?}", err);
+
+        std::fs::remove_file(exe).ok();
+    }
+
+    /// Reads the process group id of `pid` from `/proc/<pid>/stat` (field 5).
+    #[cfg(target_os = "linux")]
+    fn pgid_of(pid: u32) -> u32 {
+        let stat = std::fs::read_to_string(format!("/proc/{pid}/stat")).unwrap();
+        // The command name (field 2) may contain spaces, so split after its closing paren.
+        let rest = &stat[stat.rfind(')').unwrap() + 2..];
+        rest.split(' ').nth(2).unwrap().parse().unwrap()
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn detached_child_leads_its_own_process_group() {
+        let options = SpawnOptions {
+            detached: true,
+            ..Default::default()
+        };
+        let mut child = build_command("/bin/sleep", &options)
+            .arg("5")
+            .spawn()
+            .unwrap();
+        assert_eq!(pgid_of(child.id()), child.id());
+        child.kill().ok();
+        child.wait().ok();
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn detached_launch_gets_no_output_pipes() {
+        let out = std::env::temp_dir().join(format!("stdio_test_{}", uuid::Uuid::new_v4()));
+        let script = write_script(&format!(
+            "fds=$(readlink /proc/$$/fd/1 /proc/$$/fd/2)\necho \"$fds\" > {}",
+            out.display()
+        ));
+        let options = SpawnOptions {
+            detached: true,
+            ..Default::default()
+        };
+        let target = LaunchTarget::executable_with(script.to_string_lossy(), options);
+        let (output_tx, output_rx) = std::sync::mpsc::channel();
+        let (exit_tx, exit_rx) = std::sync::mpsc::channel();
+        launch_and_track_captured(
+            &target,
+            move |output| output_tx.send(output).unwrap(),
+            move |_| exit_tx.send(()).unwrap(),
+        )
+        .unwrap();
+        exit_rx.recv_timeout(Duration::from_secs(10)).unwrap();
+
+        let fds = std::fs::read_to_string(&out).unwrap();
+        assert_eq!(fds, "/dev/null\n/dev/null\n");
+        assert!(output_rx.try_recv().is_err(), "output was captured");
+
+        std::fs::remove_file(script).ok();
+        std::fs::remove_file(out).ok();
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn attached_child_shares_our_process_group() {
+        let mut child = build_command("/bin/sleep", &SpawnOptions::default())
+            .arg("5")
+            .spawn()
+            .unwrap();
+        assert_eq!(pgid_of(child.id()), pgid_of(std::process::id()));
+        child.kill().ok();
+        child.wait().ok();
+    }
+
+    // --- launch_and_track ---
+
+    #[cfg(unix)]
+    fn write_script(body: &str) -> std::path::PathBuf {
+        use std::os::unix::fs::PermissionsExt;
+        let path = std::env::temp_dir().join(format!("launch_test_{}.sh", uuid::Uuid::new_v4()));
+        std::fs::write(&path, format!("#!/bin/sh\n{body}\n")).unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+        path
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn tracked_launch_reports_elapsed_time() {
+        let script = write_script("/bin/sleep 1");
+        let (tx, rx) = std::sync::mpsc::channel();
+
+        let target = LaunchTarget::executable(script.to_string_lossy());
+        launch_and_track(&target, move |elapsed| tx.send(elapsed).unwrap()).unwrap();
+
+        let elapsed = rx
+            .recv_timeout(Duration::from_secs(10))
+            .expect("on_exit should fire after the child exits");
+        assert!(
+            elapsed >= Duration::from_secs(1),
+            "elapsed was {:?}",
+            elapsed
+        );
+
+        std::fs::remove_file(script).ok();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn tracked_playtime_accumulates_in_library() {
+        let lib_path =
+            std::env::temp_dir().join(format!("track_lib_{}.json", uuid::Uuid::new_v4()));
+        let mut lib = crate::library::Library::load(&lib_path).unwrap();
+        let script = write_script("/bin/sleep 1");
+        let game = lib
+            .add(crate::library::CustomGame::new(
+                "Sleepy",
+                &script,
+                None,
+                vec![],
+                None,
+            ))
+            .unwrap()
+            .clone();
+        let lib = std::sync::Arc::new(std::sync::Mutex::new(lib));
+
+        for _ in 0..2 {
+            let (tx, rx) = std::sync::mpsc::channel();
+            let (lib, id) = (lib.clone(), game.id.clone());
+            let target = LaunchTarget::executable(script.to_string_lossy());
+            launch_and_track(&target, move |elapsed| {
+                lib.lock()
+                    .unwrap()
+                    .add_playtime(&id, elapsed.as_secs())
+                    .unwrap();
+                tx.send(()).unwrap();
+            })
+            .unwrap();
+            rx.recv_timeout(Duration::from_secs(10)).unwrap();
+        }
+
+        let reloaded = crate::library::Library::load(&lib_path).unwrap();
+        assert!(reloaded.get(&game.id).unwrap().playtime_secs >= 2);
+
+        std::fs::remove_file(script).ok();
+        std::fs::remove_file(lib_path).ok();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn captured_spawn_collects_both_streams() {
+        let script = write_script("echo to-stdout\necho to-stderr >&2");
+        let (tx, rx) = std::sync::mpsc::channel();
+
+        let mut child = spawn_executable_captured(
+            &script.to_string_lossy(),
+            &SpawnOptions::default(),
+            move |output| tx.send(output).unwrap(),
+        )
+        .unwrap();
+        child.wait().unwrap();
+
+        let output = rx.recv_timeout(Duration::from_secs(10)).unwrap();
+        assert_eq!(output.stdout, "to-stdout\n");
+        assert_eq!(output.stderr, "to-stderr\n");
+
+        std::fs::remove_file(script).ok();
+    }
+
+    #[test]
+    fn read_capped_truncates_but_drains() {
+        let input = vec![b'x'; CAPTURE_LIMIT * 3];
+        let kept = read_capped(std::io::Cursor::new(input)).join().unwrap();
+        assert_eq!(kept.len(), CAPTURE_LIMIT);
+    }
+
+    // --- watch_startup ---
+
+    #[cfg(unix)]
+    fn launch_and_watch(target: &LaunchTarget, window: Duration) -> Result<(), LaunchError> {
+        let child = launch_and_track(target, |_| {})?.expect("executable has a child");
+        watch_startup(&child, window)
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn startup_watch_reports_early_failure() {
+        let target = LaunchTarget::executable("/bin/false");
+        let err = launch_and_watch(&target, Duration::from_secs(5)).unwrap_err();
+        assert!(matches!(err, LaunchError::EarlyExit(1)), "got {:?}", err);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn startup_watch_accepts_long_running_game() {
+        let target = LaunchTarget::executable_with(
+            "/bin/sleep",
+            SpawnOptions {
+                args: vec!["2".to_string()],
+                ..Default::default()
+            },
+        );
+        let started = Instant::now();
+        launch_and_watch(&target, Duration::from_millis(300)).unwrap();
+        assert!(started.elapsed() < Duration::from_secs(2));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn startup_watch_accepts_quick_clean_exit() {
+        let target = LaunchTarget::executable("/bin/true");
+        launch_and_watch(&target, Duration::from_secs(5)).unwrap();
+    }
+
+    // --- ProcessRegistry ---
+
+    #[cfg(unix)]
+    fn sleeper(secs: &str) -> SharedChild {
+        let child = Command::new("/bin/sleep").arg(secs).spawn().unwrap();
+        Arc::new(Mutex::new(child))
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn registry_stops_running_game() {
+        let mut registry = ProcessRegistry::default();
+        let child = sleeper("60");
+        registry.insert("custom-1", child.clone());
+        assert_eq!(registry.running(), ["custom-1"]);
+
+        registry.stop("custom-1").unwrap();
+        assert!(registry.running().is_empty());
+        let status = child.lock().unwrap().try_wait().unwrap();
+        assert!(
+            status.is_some_and(|s| !s.success()),
+            "child should be killed"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn registry_prunes_exited_children() {
+        let mut registry = ProcessRegistry::default();
+        let done = sleeper("0");
+        done.lock().unwrap().wait().unwrap();
+        let alive = sleeper("60");
+        registry.insert("custom-done", done);
+        registry.insert("custom-alive", alive);
+
+        assert_eq!(registry.running(), ["custom-alive"]);
+        assert!(matches!(
+            registry.stop("custom-done"),
+            Err(LaunchError::NotRunning(_))
+        ));
+        registry.stop("custom-alive").unwrap();
+    }
+
+    #[test]
+    fn registry_stop_unknown_key_errors() {
+        let mut registry = ProcessRegistry::default();
+        assert!(matches!(
+            registry.stop("custom-missing"),
+            Err(LaunchError::NotRunning(_))
+        ));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn stopping_tracked_game_fires_on_exit() {
+        let target = LaunchTarget::executable_with(
+            "/bin/sleep",
+            SpawnOptions {
+                args: vec!["60".to_string()],
+                ..Default::default()
+            },
+        );
+        let (tx, rx) = std::sync::mpsc::channel();
+        let child = launch_and_track(&target, move |_| tx.send(()).unwrap())
+            .unwrap()
+            .expect("direct spawn should return a handle");
+
+        let mut registry = ProcessRegistry::default();
+        registry.insert("custom-sleep", child);
+        registry.stop("custom-sleep").unwrap();
+        rx.recv_timeout(Duration::from_secs(10))
+            .expect("tracker should notice the kill");
+    }
+
+    #[test]
+    fn tracked_launch_of_missing_executable_errors() {
+        let target = LaunchTarget::executable("/absolutely/does/not/exist.exe");
+        let err = launch_and_track(&target, |_| panic!("must not be called")).unwrap_err();
+        assert!(matches!(err, LaunchError::ExecutableNotFound(_)));
+    }
+
     // --- resolve_process_name ---
 
     #[test]
@@ -281,6 +1872,43 @@ mod tests {
         );
     }
 
+    #[cfg(target_os = "macos")]
+    #[test]
+    fn process_name_prefers_declared_bundle_executable() {
+        let bundle = std::env::temp_dir().join(format!(
+            "launcher_test_{}/Fake Game.app",
+            std::process::id()
+        ));
+        let macos = bundle.join("Contents/MacOS");
+        std::fs::create_dir_all(&macos).unwrap();
+        // Sorts (and often lists) before the real binary.
+        std::fs::write(macos.join("Aaa Crash Reporter"), "").unwrap();
+        std::fs::write(macos.join("FakeGame"), "").unwrap();
+        std::fs::write(macos.join("libsteam_api.dylib"), "").unwrap();
+        std::fs::write(
+            bundle.join("Contents/Info.plist"),
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+    <key>CFBundleIdentifier</key>
+    <string>com.example.fakegame</string>
+    <key>CFBundleExecutable</key>
+    <string>FakeGame</string>
+</dict>
+</plist>"#,
+        )
+        .unwrap();
+
+        assert_eq!(resolve_process_name(&bundle.to_string_lossy()), "FakeGame");
+
+        // A plist naming a binary that isn't there falls back to the directory scan.
+        std::fs::remove_file(macos.join("FakeGame")).unwrap();
+        assert_ne!(resolve_process_name(&bundle.to_string_lossy()), "FakeGame");
+
+        std::fs::remove_dir_all(bundle.parent().unwrap()).ok();
+    }
+
     #[cfg(target_os = "macos")]
     #[test]
     fn process_name_from_real_app_bundle_if_present() {
@@ -291,4 +1919,213 @@ mod tests {
             assert!(!name.is_empty(), "should return a non-empty process name");
         }
     }
+
+    // --- Steam process detection ---
+
+    #[test]
+    fn steam_detected_by_platform_process_name() {
+        assert!(steam_in(&["systemd", "steam", "bash"]));
+        assert!(steam_in(&["explorer.exe", "Steam.exe"]));
+        assert!(steam_in(&["Finder", "steam_osx"]));
+    }
+
+    #[test]
+    fn steam_helpers_and_lookalikes_are_not_steam() {
+        assert!(!steam_in(&[
+            "steamwebhelper",
+            "steamerrorreporter",
+            "steam-runtime"
+        ]));
+        assert!(!steam_in::<&str>(&[]));
+    }
+
+    // --- process_in ---
+
+    #[test]
+    fn process_match_ignores_case_and_exe_suffix() {
+        let snapshot = ["explorer.exe", "Celeste.exe", "hollow_knight"];
+        assert!(process_in(&snapshot, "celeste.exe"));
+        assert!(process_in(&snapshot, "Celeste"));
+        assert!(process_in(&snapshot, "Hollow_Knight.EXE"));
+        assert!(!process_in(&snapshot, "celeste2"));
+        assert!(!process_in(&snapshot, ".exe"));
+        assert!(!process_in(&snapshot, ""));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn process_match_handles_truncated_linux_names() {
+        let snapshot = ["Hollow_Knight.x"];
+        assert!(process_in(&snapshot, "Hollow_Knight.x86_64"));
+        assert!(!process_in(&["Hollow_Knight"], "Hollow_Knight.x86_64"));
+    }
+
+    // --- open_uri_with ---
+
+    const LINUX_OPENERS: &[(&str, &[&str])] = &[("xdg-open", &[]), ("gio", &["open"])];
+
+    #[test]
+    fn falls_back_to_gio_when_xdg_open_is_missing() {
+        let mut calls = Vec::new();
+        let result = open_uri_with("steam://run/440", LINUX_OPENERS, |program, args| {
+            calls.push(format!("{program} {}", args.join(" ")));
+            if program == "xdg-open" {
+                Err(std::io::ErrorKind::NotFound.into())
+            } else {
+                Ok(0)
+            }
+        });
+        assert!(result.is_ok());
+        assert_eq!(
+            calls,
+            ["xdg-open steam://run/440", "gio open steam://run/440"]
+        );
+    }
+
+    #[test]
+    fn missing_openers_are_reported_by_name() {
+        let result = open_uri_with("steam://run/440", LINUX_OPENERS, |_, _| {
+            Err(std::io::ErrorKind::NotFound.into())
+        });
+        match result {
+            Err(LaunchError::NoUriHandler(tried)) => assert_eq!(tried, "xdg-open, gio"),
+            other => panic!("expected NoUriHandler, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn other_opener_failures_are_not_masked() {
+        let mut calls = 0;
+        let result = open_uri_with("steam://run/440", LINUX_OPENERS, |_, _| {
+            calls += 1;
+            Err(std::io::ErrorKind::PermissionDenied.into())
+        });
+        assert!(matches!(result, Err(LaunchError::SpawnFailed(_))));
+        assert_eq!(calls, 1, "a present but failing opener isn't skipped");
+    }
+
+    #[test]
+    fn opener_exit_codes_are_errors() {
+        let result = open_uri_with("steam://run/440", LINUX_OPENERS, |_, _| Ok(4));
+        match result {
+            Err(LaunchError::UriOpenFailed(program, 4)) => assert_eq!(program, "xdg-open"),
+            other => panic!("expected UriOpenFailed, got {other:?}"),
+        }
+    }
+
+    // --- retry ---
+
+    fn spawn_error() -> LaunchError {
+        LaunchError::SpawnFailed(std::io::Error::other("handler not ready"))
+    }
+
+    #[test]
+    fn retry_succeeds_after_transient_failures() {
+        let mut calls = 0;
+        let result = retry(5, Duration::ZERO, || {
+            calls += 1;
+            if calls <= 2 {
+                Err(spawn_error())
+            } else {
+                Ok(calls)
+            }
+        });
+        assert_eq!(result.unwrap(), 3);
+        assert_eq!(calls, 3);
+    }
+
+    #[test]
+    fn retry_returns_last_error_when_attempts_run_out() {
+        let mut calls = 0;
+        let result: Result<(), _> = retry(3, Duration::ZERO, || {
+            calls += 1;
+            Err(spawn_error())
+        });
+        assert!(matches!(result, Err(LaunchError::SpawnFailed(_))));
+        assert_eq!(calls, 3);
+    }
+
+    #[test]
+    fn retry_never_repeats_missing_executable() {
+        let mut calls = 0;
+        let result: Result<(), _> = retry(3, Duration::ZERO, || {
+            calls += 1;
+            Err(LaunchError::ExecutableNotFound("/gone".into()))
+        });
+        assert!(matches!(result, Err(LaunchError::ExecutableNotFound(_))));
+        assert_eq!(calls, 1);
+    }
+
+    #[test]
+    fn retry_makes_at_least_one_attempt() {
+        let mut calls = 0;
+        let _: Result<(), _> = retry(0, Duration::ZERO, || {
+            calls += 1;
+            Err(spawn_error())
+        });
+        assert_eq!(calls, 1);
+    }
+
+    #[test]
+    fn retry_reopens_until_the_handler_is_registered() {
+        let mut calls = 0;
+        let result = retry(5, Duration::ZERO, || {
+            open_uri_with("steam://run/440", LINUX_OPENERS, |_, _| {
+                calls += 1;
+                Ok(if calls <= 2 { 4 } else { 0 })
+            })
+        });
+        assert!(result.is_ok());
+        assert_eq!(calls, 3);
+    }
+
+    // --- Revealing paths ---
+
+    /// The command line [`reveal_in_file_manager`] would run for `path`.
+    fn reveal_argv(path: &Path) -> Vec<String> {
+        let mut argv = Vec::new();
+        open_uri_with(&reveal_target(path), REVEAL_OPENERS, |program, args| {
+            argv.push(program.to_string());
+            argv.extend(args.iter().map(|a| a.to_string()));
+            Ok(0)
+        })
+        .unwrap();
+        argv
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn reveal_opens_containing_folder_on_linux() {
+        assert_eq!(
+            reveal_argv(Path::new("/games/celeste/Celeste")),
+            ["xdg-open", "/games/celeste"]
+        );
+    }
+
+    #[cfg(target_os = "macos")]
+    #[test]
+    fn reveal_selects_in_finder() {
+        assert_eq!(
+            reveal_argv(Path::new("/Applications/Celeste.app")),
+            ["open", "-R", "/Applications/Celeste.app"]
+        );
+    }
+
+    #[cfg(target_os = "windows")]
+    #[test]
+    fn reveal_selects_in_explorer() {
+        assert_eq!(
+            reveal_argv(Path::new(r"C:\Games\Celeste\Celeste.exe")),
+            ["explorer", r"/select,C:\Games\Celeste\Celeste.exe"]
+        );
+    }
+
+    #[test]
+    fn reveal_rejects_missing_path() {
+        let missing = std::env::temp_dir().join(format!("reveal_{}", uuid::Uuid::new_v4()));
+        assert!(matches!(
+            reveal_in_file_manager(&missing),
+            Err(LaunchError::PathNotFound(_))
+        ));
+    }
 }
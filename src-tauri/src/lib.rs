@@ -1,23 +1,50 @@
+mod emulators;
 mod epic;
+mod error;
 mod fs_explorer;
+mod game;
+mod gog;
+mod itch;
 mod launcher;
 mod library;
+mod linux_apps;
+mod lutris;
+mod playnite;
+mod settings;
+#[cfg(windows)]
+mod shell_link;
 mod steam;
+mod thumbnail;
+mod watcher;
 
 use epic::EpicGame;
+use error::CommandError;
+use game::Game;
+use gog::GogGame;
+use itch::ItchGame;
 use launcher::LaunchTarget;
-use library::{CustomGame, Library};
-use std::path::PathBuf;
-use std::sync::Mutex;
+use library::{Collection, CustomGame, Library, LibraryError, LibraryIssue, SortOrder};
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, RwLock};
 use steam::SteamGame;
-use tauri::{AppHandle, Manager, State};
+use tauri::{AppHandle, Emitter, Manager, State};
 
 // ---------------------------------------------------------------------------
 // Shared state
 // ---------------------------------------------------------------------------
 
 struct AppState {
-    library: Mutex<Library>,
+    /// Read-mostly, so listing and lookups share the lock; edits take it exclusively.
+    /// Store discovery never touches it.
+    library: RwLock<Library>,
+    steam_cache: Mutex<steam::DiscoveryCache>,
+    running: Mutex<launcher::ProcessRegistry>,
+    bookmarks: Mutex<fs_explorer::BookmarkStore>,
+    settings: Mutex<settings::SettingsStore>,
+    /// Kept alive for the app's lifetime; dropping it stops change notifications.
+    /// Replaced when the Steam root changes.
+    watcher: Mutex<Option<watcher::LibraryWatcher>>,
 }
 
 fn library_path(app: &AppHandle) -> PathBuf {
@@ -27,26 +54,205 @@ fn library_path(app: &AppHandle) -> PathBuf {
         .join("custom_games.json")
 }
 
+fn settings_path(app: &AppHandle) -> PathBuf {
+    app.path()
+        .app_data_dir()
+        .expect("could not resolve app data dir")
+        .join("settings.json")
+}
+
+fn bookmarks_path(app: &AppHandle) -> PathBuf {
+    app.path()
+        .app_data_dir()
+        .expect("could not resolve app data dir")
+        .join("bookmarks.json")
+}
+
 // ---------------------------------------------------------------------------
 // Tauri commands
 // ---------------------------------------------------------------------------
 
+/// Returns cached Steam games unless a manifest changed since the last scan;
+/// pass `force: true` to always re-walk the libraries.
 #[tauri::command]
-fn get_steam_games() -> Result<Vec<SteamGame>, String> {
-    match steam::discover_games() {
-        Ok(games) => {
-            log::info!("Steam discovery: found {} games", games.len());
-            Ok(games)
+fn get_steam_games(
+    state: State<AppState>,
+    force: Option<bool>,
+) -> Result<Vec<SteamGame>, CommandError> {
+    load_steam_games(&state, force.unwrap_or(false)).map_err(|e| {
+        log::warn!("Steam discovery failed: {}", e);
+        CommandError::from(e)
+    })
+}
+
+/// The Steam root from the `steam_root_override` setting, or the platform default.
+fn steam_root(state: &AppState) -> Result<PathBuf, steam::SteamError> {
+    let settings = state.settings.lock().unwrap();
+    steam::resolve_steam_root(settings.settings().steam_root_override.as_deref())
+}
+
+/// Discovery options from the Steam settings, and the app ids the user hid.
+fn steam_discovery(state: &AppState) -> (steam::DiscoveryOptions, Vec<u32>) {
+    let store = state.settings.lock().unwrap();
+    let settings = store.settings();
+    let options = steam::DiscoveryOptions {
+        hide_tools: settings.hide_steam_tools,
+        ..Default::default()
+    };
+    (options, settings.hidden_steam_apps.clone())
+}
+
+/// Steam games plus non-duplicate shortcuts, served from the discovery cache when fresh.
+/// Tools (when hidden) and the user's hidden apps are left out.
+fn load_steam_games(state: &AppState, force: bool) -> Result<Vec<SteamGame>, steam::SteamError> {
+    let steam_root = steam_root(state)?;
+    let (options, hidden) = steam_discovery(state);
+    let mut cache = state.steam_cache.lock().unwrap();
+    if !force {
+        if let Some(games) = cache.get(&steam_root) {
+            log::info!("Steam discovery: returning {} cached games", games.len());
+            return Ok(games);
         }
+    }
+    let mut games = steam::discover_games_at(&steam_root, options)?;
+    let shortcuts = steam::discover_shortcut_games(&steam_root, false);
+    log::info!(
+        "Steam discovery: found {} games and {} shortcut(s)",
+        games.len(),
+        shortcuts.len()
+    );
+    let shortcuts = dedup_shortcuts(&games, shortcuts);
+    games.extend(shortcuts);
+    let games = steam::without_hidden_apps(games, &hidden);
+    cache.insert(&steam_root, games.clone());
+    Ok(games)
+}
+
+/// Returns Steam, Epic and custom games in one list, each tagged with its `source`.
+///
+/// A failing Steam or Epic scan contributes no games instead of failing the call.
+#[tauri::command]
+fn get_all_games(state: State<AppState>) -> Vec<Game> {
+    let games = collect_all_games(&state);
+    log::info!("get_all_games: returning {} games", games.len());
+    games
+}
+
+/// Searches every source for games whose title (or custom tags/notes) contains `query`,
+/// best matches first; see [`game::search`].
+#[tauri::command]
+fn search_games(state: State<AppState>, query: String) -> Vec<Game> {
+    let hits = game::search(collect_all_games(&state), &query);
+    log::info!("search_games: {:?} matched {} games", query, hits.len());
+    hits
+}
+
+/// The `limit` (default 10) most recently played games across all sources; games never
+/// played (or from sources without a record, like Epic) come last.
+#[tauri::command]
+fn recently_played(state: State<AppState>, limit: Option<usize>) -> Vec<Game> {
+    let games = collect_all_games(&state)
+        .into_iter()
+        .map(|g| {
+            let played = g.last_played();
+            (g, played)
+        })
+        .collect();
+    game::recently_played(games, limit.unwrap_or(10))
+}
+
+/// Disk space used by installed games per source: Steam's and Epic's recorded sizes,
+/// plus, when `include_custom` is set, the size of each custom game's folder. Measuring
+/// those folders walks every file in them, so it's off by default.
+#[tauri::command]
+fn library_storage(state: State<AppState>, include_custom: Option<bool>) -> game::StorageUsage {
+    let include_custom = include_custom.unwrap_or(false);
+    let mut measured = HashSet::new();
+    let games: Vec<(Game, Option<u64>)> = collect_all_games(&state)
+        .into_iter()
+        .map(|g| {
+            let size = match &g {
+                Game::Custom(c) if include_custom => custom_game_size(c, &mut measured),
+                _ => g.store_size(),
+            };
+            (g, size)
+        })
+        .collect();
+    let usage = game::storage_usage(&games);
+    log::info!("library_storage: {:?}", usage);
+    usage
+}
+
+/// Size of the folder a custom game lives in, or `None` when it's not worth measuring:
+/// games imported from a store are already counted there. A folder shared by several
+/// games counts once, for the first of them.
+fn custom_game_size(game: &CustomGame, measured: &mut HashSet<PathBuf>) -> Option<u64> {
+    if game.imported_from.is_some() {
+        return None;
+    }
+    let dir = if game.executable.is_dir() {
+        game.executable.as_path()
+    } else {
+        game.executable.parent()?
+    };
+    if !dir.is_dir() {
+        return None;
+    }
+    if !measured.insert(dir.to_path_buf()) {
+        return Some(0);
+    }
+    Some(fs_explorer::dir_size(dir))
+}
+
+/// Writes every game (Steam, Epic, custom) to `dest` as `"json"` or `"csv"`.
+#[tauri::command]
+fn export_library(
+    state: State<AppState>,
+    format: String,
+    dest: String,
+) -> Result<(), CommandError> {
+    let games = collect_all_games(&state);
+    let contents = match format.to_lowercase().as_str() {
+        "json" => game::to_json(&games)?,
+        "csv" => game::to_csv(&games),
+        other => {
+            let message = format!("Unsupported export format: {other}");
+            return Err(CommandError::new("invalid_argument", message));
+        }
+    };
+    std::fs::write(&dest, contents).map_err(|e| {
+        log::error!("Failed to export library to {:?}: {}", dest, e);
+        CommandError::from(e)
+    })?;
+    log::info!("Exported {} games as {} to {:?}", games.len(), format, dest);
+    Ok(())
+}
+
+fn collect_all_games(state: &AppState) -> Vec<Game> {
+    let mut games: Vec<Game> = match load_steam_games(state, false) {
+        Ok(steam) => steam.into_iter().map(Game::Steam).collect(),
         Err(e) => {
-            log::warn!("Steam discovery failed: {}", e);
-            Err(e.to_string())
+            log::warn!("Skipping Steam games: {}", e);
+            Vec::new()
         }
+    };
+    match epic::discover_games() {
+        Ok(epic) => games.extend(epic.into_iter().map(Game::Epic)),
+        Err(e) => log::warn!("Skipping Epic games: {}", e),
     }
+    games.extend(custom_games(state));
+    games
+}
+
+/// The custom games as [`Game`]s, read under a shared library lock so listing doesn't
+/// wait on other readers.
+fn custom_games(state: &AppState) -> Vec<Game> {
+    let library = state.library.read().unwrap();
+    library.games().iter().cloned().map(Game::Custom).collect()
 }
 
 #[tauri::command]
-fn get_epic_games() -> Result<Vec<EpicGame>, String> {
+fn get_epic_games() -> Result<Vec<EpicGame>, CommandError> {
     match epic::discover_games() {
         Ok(games) => {
             log::info!("Epic discovery: found {} games", games.len());
@@ -54,51 +260,709 @@ fn get_epic_games() -> Result<Vec<EpicGame>, String> {
         }
         Err(e) => {
             log::warn!("Epic discovery failed: {}", e);
-            Err(e.to_string())
+            Err(e.into())
+        }
+    }
+}
+
+/// The Steam library folders that discovery scans, each flagged when it's missing, so
+/// users can see why games on another drive don't show up.
+#[tauri::command]
+fn get_steam_libraries(state: State<AppState>) -> Result<Vec<steam::SteamLibrary>, CommandError> {
+    steam_root(&state)
+        .and_then(|root| steam::library_folders(&root))
+        .map_err(|e| {
+            log::warn!("Listing Steam libraries failed: {}", e);
+            CommandError::from(e)
+        })
+}
+
+/// Re-reads one Steam library's manifests (a `steamapps` folder from
+/// [`get_steam_libraries`]) after it changed, instead of re-walking every library. The
+/// result is merged into the discovery cache, and the frontend merges it into its list
+/// by app id. Folders that aren't one of Steam's libraries are rejected.
+#[tauri::command]
+fn rescan_steam_library(
+    state: State<AppState>,
+    steamapps_dir: String,
+) -> Result<Vec<SteamGame>, CommandError> {
+    let root = steam_root(&state)?;
+    let dir = Path::new(&steamapps_dir);
+    let libraries = steam::library_folders(&root)?;
+    if !libraries.iter().any(|library| library.path == dir) {
+        log::warn!("Refusing to rescan {:?}: not a Steam library", dir);
+        return Err(CommandError::new(
+            "invalid_argument",
+            format!("Not a Steam library: {steamapps_dir}"),
+        ));
+    }
+    if !dir.is_dir() {
+        log::warn!("Cannot rescan missing Steam library {:?}", dir);
+        return Err(CommandError::new(
+            "not_found",
+            format!("Steam library not found: {steamapps_dir}"),
+        ));
+    }
+    let (options, hidden) = steam_discovery(&state);
+    let games = steam::rescan_library(&root, dir, options);
+    let games = steam::without_hidden_apps(games, &hidden);
+    state
+        .steam_cache
+        .lock()
+        .unwrap()
+        .merge_library(&root, dir, games.clone());
+    Ok(games)
+}
+
+/// Result of [`steam_status`].
+#[derive(Clone, serde::Serialize)]
+struct SteamStatus {
+    installed: bool,
+    running: bool,
+}
+
+/// Whether Steam is installed (its root was found) and whether the client is running,
+/// so the UI can warn before a `steam://` launch cold-starts it.
+#[tauri::command]
+fn steam_status(state: State<AppState>) -> SteamStatus {
+    let installed = steam_root(&state).is_ok_and(|root| root.is_dir());
+    let running = launcher::is_steam_running();
+    log::info!("Steam status: installed={} running={}", installed, running);
+    SteamStatus { installed, running }
+}
+
+// ---------------------------------------------------------------------------
+// Streaming discovery commands
+// ---------------------------------------------------------------------------
+
+/// Payload of the `discovery-complete` event sent after a streaming scan finishes.
+#[derive(Clone, serde::Serialize)]
+struct DiscoveryComplete {
+    source: &'static str,
+    count: usize,
+}
+
+/// Converts shortcuts to [`SteamGame`]s, dropping any that duplicate a real Steam game.
+///
+/// A shortcut is collapsed into a real game only when their names match ignoring case
+/// and surrounding whitespace, or the shortcut's executable lives inside the game's
+/// install directory. Anything else is kept, so distinct games are never hidden.
+fn dedup_shortcuts(games: &[SteamGame], shortcuts: Vec<steam::ShortcutGame>) -> Vec<SteamGame> {
+    shortcuts
+        .into_iter()
+        .filter(|shortcut| {
+            let name = shortcut.app_name.trim();
+            let exe = Path::new(&shortcut.exe);
+            let duplicate = games.iter().find(|g| {
+                g.name.trim().eq_ignore_ascii_case(name)
+                    || (exe.is_absolute() && exe.starts_with(&g.install_dir))
+            });
+            if let Some(game) = duplicate {
+                log::info!(
+                    "Merging shortcut {:?} into Steam game {:?} (app_id={})",
+                    shortcut.app_name,
+                    game.name,
+                    game.app_id
+                );
+            }
+            duplicate.is_none()
+        })
+        .map(SteamGame::from)
+        .collect()
+}
+
+/// Passes each game whose app id hasn't been reported yet to `emit`; returns how many were new.
+fn forward_new_steam_games(
+    games: Vec<SteamGame>,
+    seen: &mut HashSet<u32>,
+    mut emit: impl FnMut(&SteamGame),
+) -> usize {
+    let mut count = 0;
+    for game in games.iter().filter(|g| seen.insert(g.app_id)) {
+        emit(game);
+        count += 1;
+    }
+    count
+}
+
+/// Streaming variant of [`get_steam_games`]: emits `steam-game-found` for each game as
+/// each library folder is read, then `discovery-complete` with the total.
+#[tauri::command]
+async fn stream_steam_games(app: AppHandle) -> Result<usize, CommandError> {
+    let steam_root = steam_root(&app.state::<AppState>()).map_err(|e| {
+        log::warn!("Steam discovery failed: {}", e);
+        CommandError::from(e)
+    })?;
+    let library_paths = steam::find_library_paths(&steam_root).map_err(|e| {
+        log::warn!("Steam discovery failed: {}", e);
+        CommandError::from(e)
+    })?;
+
+    let emit = |game: &SteamGame| {
+        if let Err(e) = app.emit("steam-game-found", game) {
+            log::warn!("Failed to emit steam-game-found: {}", e);
+        }
+    };
+    let (options, hidden) = steam_discovery(&app.state::<AppState>());
+    let mut seen = HashSet::new();
+    let mut count = 0;
+    let mut found = Vec::new();
+    for dir in &library_paths {
+        let games = steam::read_games_from_library(dir, options);
+        let games = steam::without_hidden_apps(games, &hidden);
+        count += forward_new_steam_games(games.clone(), &mut seen, emit);
+        found.extend(games);
+    }
+    let shortcuts = dedup_shortcuts(&found, steam::discover_shortcut_games(&steam_root, false));
+    let shortcuts = steam::without_hidden_apps(shortcuts, &hidden);
+    count += forward_new_steam_games(shortcuts, &mut HashSet::new(), emit);
+
+    log::info!("Streaming Steam discovery: emitted {} games", count);
+    let _ = app.emit(
+        "discovery-complete",
+        DiscoveryComplete {
+            source: "steam",
+            count,
+        },
+    );
+    Ok(count)
+}
+
+/// Streaming variant of [`get_epic_games`]: emits `epic-game-found` per game,
+/// then `discovery-complete` with the total.
+#[tauri::command]
+async fn stream_epic_games(app: AppHandle) -> Result<usize, CommandError> {
+    let games = epic::discover_games().map_err(|e| {
+        log::warn!("Epic discovery failed: {}", e);
+        CommandError::from(e)
+    })?;
+    for game in &games {
+        if let Err(e) = app.emit("epic-game-found", game) {
+            log::warn!("Failed to emit epic-game-found: {}", e);
+        }
+    }
+    let count = games.len();
+    log::info!("Streaming Epic discovery: emitted {} games", count);
+    let _ = app.emit(
+        "discovery-complete",
+        DiscoveryComplete {
+            source: "epic",
+            count,
+        },
+    );
+    Ok(count)
+}
+
+#[tauri::command]
+fn get_gog_games() -> Result<Vec<GogGame>, CommandError> {
+    match gog::discover_games() {
+        Ok(games) => {
+            log::info!("GOG discovery: found {} games", games.len());
+            Ok(games)
+        }
+        Err(e) => {
+            log::warn!("GOG discovery failed: {}", e);
+            Err(e.into())
+        }
+    }
+}
+
+#[tauri::command]
+fn get_itch_games() -> Result<Vec<ItchGame>, CommandError> {
+    match itch::discover_games() {
+        Ok(games) => {
+            log::info!("itch.io discovery: found {} games", games.len());
+            Ok(games)
+        }
+        Err(e) => {
+            log::warn!("itch.io discovery failed: {}", e);
+            Err(e.into())
         }
     }
 }
 
+/// Games with a `.desktop` launcher in the `Game` category (Linux only; empty elsewhere).
+#[tauri::command]
+fn discover_desktop_games() -> Vec<linux_apps::DesktopGame> {
+    let games = linux_apps::discover_games();
+    log::info!("Desktop entry discovery: found {} games", games.len());
+    games
+}
+
+/// Games configured in Lutris (Linux only; empty elsewhere).
+#[tauri::command]
+fn discover_lutris_games() -> Vec<lutris::LutrisGame> {
+    let games = lutris::discover_games();
+    log::info!("Lutris discovery: found {} games", games.len());
+    games
+}
+
 #[tauri::command]
 fn get_custom_games(state: State<AppState>) -> Vec<CustomGame> {
-    state.library.lock().unwrap().games().to_vec()
+    state.library.read().unwrap().games().to_vec()
+}
+
+/// The custom game with `id`, for the edit form; `None` if it no longer exists.
+#[tauri::command]
+fn get_game(state: State<AppState>, id: String) -> Option<CustomGame> {
+    state.library.read().unwrap().get(&id).cloned()
+}
+
+/// Custom games whose executable or cover has disappeared, for the cleanup screen.
+#[tauri::command]
+fn validate_library(state: State<AppState>) -> Vec<LibraryIssue> {
+    let issues = state.library.read().unwrap().issues();
+    log::info!("Library check: {} broken games", issues.len());
+    issues
+}
+
+/// Flips the favorite flag for a game and returns the new state. `custom-<id>` keys
+/// update the custom game itself; external keys (`steam:<appid>`, `epic:<app_name>`,
+/// see [`Game::key`]) are kept in the library's external favorites set.
+#[tauri::command]
+fn toggle_favorite(state: State<AppState>, key: String) -> Result<bool, CommandError> {
+    let mut library = state.library.write().unwrap();
+    let result = match key.strip_prefix("custom-") {
+        Some(id) => match library.get(id).map(|g| !g.favorite) {
+            Some(favorite) => library.set_favorite(id, favorite).map(|_| favorite),
+            None => Err(LibraryError::NotFound(id.to_string())),
+        },
+        None => {
+            let favorite = !library.is_external_favorite(&key);
+            library
+                .set_external_favorite(&key, favorite)
+                .map(|_| favorite)
+        }
+    };
+    result.map_err(|e| {
+        log::error!("Failed to toggle favorite for {:?}: {}", key, e);
+        CommandError::from(e)
+    })
+}
+
+/// Returns the external keys (see [`Game::key`]) the user marked as favorites.
+#[tauri::command]
+fn get_external_favorites(state: State<AppState>) -> Vec<String> {
+    let library = state.library.read().unwrap();
+    library.external_favorites().iter().cloned().collect()
+}
+
+#[tauri::command]
+fn get_collections(state: State<AppState>) -> Vec<Collection> {
+    state.library.read().unwrap().collections().to_vec()
+}
+
+#[tauri::command]
+fn create_collection(state: State<AppState>, name: String) -> Result<Collection, CommandError> {
+    let mut library = state.library.write().unwrap();
+    library.create_collection(name).cloned().map_err(|e| {
+        log::error!("Failed to create collection: {}", e);
+        CommandError::from(e)
+    })
+}
+
+/// Adds a game to a collection. `key` is `custom-<id>`, `steam:<appid>` or `epic:<app_name>`.
+#[tauri::command]
+fn add_to_collection(
+    state: State<AppState>,
+    collection_id: String,
+    key: String,
+) -> Result<Collection, CommandError> {
+    let mut library = state.library.write().unwrap();
+    library
+        .add_to_collection(&collection_id, &key)
+        .cloned()
+        .map_err(|e| {
+            log::error!("Failed to add {:?} to collection: {}", key, e);
+            CommandError::from(e)
+        })
+}
+
+#[tauri::command]
+fn remove_from_collection(
+    state: State<AppState>,
+    collection_id: String,
+    key: String,
+) -> Result<Collection, CommandError> {
+    let mut library = state.library.write().unwrap();
+    library
+        .remove_from_collection(&collection_id, &key)
+        .cloned()
+        .map_err(|e| {
+            log::error!("Failed to remove {:?} from collection: {}", key, e);
+            CommandError::from(e)
+        })
+}
+
+/// Returns every tag used by custom games, deduplicated ignoring case.
+#[tauri::command]
+fn list_tags(state: State<AppState>) -> Vec<String> {
+    state.library.read().unwrap().all_tags()
+}
+
+/// Returns the custom games carrying `tag` (case-insensitive).
+#[tauri::command]
+fn get_games_by_tag(state: State<AppState>, tag: String) -> Vec<CustomGame> {
+    let library = state.library.read().unwrap();
+    library.games_with_tag(&tag).into_iter().cloned().collect()
+}
+
+/// Result of [`add_game`]: the stored game plus non-fatal notes for the user, such as
+/// another game already having the same title.
+#[derive(serde::Serialize)]
+struct AddGameResult {
+    game: CustomGame,
+    warnings: Vec<String>,
+}
+
+/// Warns when the library already has a game titled `title` (ignoring case).
+fn duplicate_title_warnings(library: &Library, title: &str) -> Vec<String> {
+    match library.titles_matching(title).first() {
+        Some(existing) => {
+            log::info!("Adding {:?} alongside a game with the same title", title);
+            vec![format!(
+                "You already have a game named \"{}\"",
+                existing.title
+            )]
+        }
+        None => Vec::new(),
+    }
 }
 
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 fn add_game(
     state: State<AppState>,
     title: String,
     executable: String,
     cover_image: Option<String>,
+    hero_image: Option<String>,
+    screenshots: Option<Vec<String>>,
     tags: Vec<String>,
     notes: Option<String>,
-) -> Result<CustomGame, String> {
+    args: Option<Vec<String>>,
+    working_dir: Option<String>,
+    env: Option<BTreeMap<String, String>>,
+    wrappers: Option<Vec<String>>,
+    runner: Option<String>,
+    pre_launch: Option<String>,
+    post_exit: Option<String>,
+    allow_duplicate: Option<bool>,
+) -> Result<AddGameResult, CommandError> {
     log::info!("Adding custom game: title={:?} executable={:?}", title, executable);
-    let game = CustomGame::new(
+    let mut game = CustomGame::new(
         title,
         executable,
         cover_image.map(PathBuf::from),
         tags,
         notes,
     );
-    state
-        .library
-        .lock()
-        .unwrap()
-        .add(game)
-        .map(|g| g.clone())
+    game.hero_image = hero_image.map(PathBuf::from);
+    game.screenshots = screenshots
+        .unwrap_or_default()
+        .into_iter()
+        .map(PathBuf::from)
+        .collect();
+    game.args = args.unwrap_or_default();
+    game.working_dir = working_dir.map(PathBuf::from);
+    game.env = env.map(|e| e.into_iter().collect()).unwrap_or_default();
+    game.wrappers = wrappers.unwrap_or_default();
+    game.runner = runner;
+    game.pre_launch = pre_launch.filter(|c| !c.trim().is_empty());
+    game.post_exit = post_exit.filter(|c| !c.trim().is_empty());
+    let mut library = state.library.write().unwrap();
+    let warnings = duplicate_title_warnings(&library, &game.title);
+    library
+        .add_validated(game, allow_duplicate.unwrap_or(false))
+        .map(|g| AddGameResult {
+            game: g.clone(),
+            warnings,
+        })
         .map_err(|e| {
             log::error!("Failed to add game: {}", e);
-            e.to_string()
+            CommandError::from(e)
+        })
+}
+
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+fn update_game(
+    state: State<AppState>,
+    id: String,
+    title: String,
+    executable: String,
+    cover_image: Option<String>,
+    hero_image: Option<String>,
+    screenshots: Option<Vec<String>>,
+    tags: Vec<String>,
+    notes: Option<String>,
+    args: Option<Vec<String>>,
+    working_dir: Option<String>,
+    env: Option<BTreeMap<String, String>>,
+    wrappers: Option<Vec<String>>,
+    runner: Option<String>,
+    pre_launch: Option<String>,
+    post_exit: Option<String>,
+) -> Result<CustomGame, CommandError> {
+    log::info!("Updating custom game: id={} title={:?}", id, title);
+    let mut library = state.library.write().unwrap();
+    // Start from the stored entry so fields the edit form doesn't send (playtime) survive.
+    let Some(existing) = library.get(&id).cloned() else {
+        log::error!("Failed to update game: unknown id={}", id);
+        return Err(LibraryError::NotFound(id).into());
+    };
+    let game = CustomGame {
+        title,
+        executable: PathBuf::from(executable),
+        cover_image: cover_image.map(PathBuf::from),
+        tags,
+        notes,
+        // The hero, screenshots and launch options are optional in the payload; omitting
+        // them keeps the stored values and sending an empty one clears it.
+        hero_image: edited_field(hero_image, existing.hero_image),
+        screenshots: screenshots.map_or(existing.screenshots, |shots| {
+            shots.into_iter().map(PathBuf::from).collect()
+        }),
+        args: args.unwrap_or(existing.args),
+        working_dir: edited_field(working_dir, existing.working_dir),
+        env: env.map_or(existing.env, |e| e.into_iter().collect()),
+        wrappers: wrappers.unwrap_or(existing.wrappers),
+        runner: edited_field(runner, existing.runner),
+        pre_launch: edited_field(pre_launch, existing.pre_launch),
+        post_exit: edited_field(post_exit, existing.post_exit),
+        ..existing
+    };
+    library
+        .update_validated(game)
+        .map(|g| g.clone())
+        .map_err(|e| {
+            log::error!("Failed to update game: {}", e);
+            CommandError::from(e)
+        })
+}
+
+/// Applies an optional text field from [`update_game`]: `None` keeps the `stored` value and
+/// an empty (or blank) string clears it.
+fn edited_field<T: From<String>>(sent: Option<String>, stored: Option<T>) -> Option<T> {
+    match sent {
+        Some(value) if value.trim().is_empty() => None,
+        Some(value) => Some(T::from(value)),
+        None => stored,
+    }
+}
+
+/// Adds every executable found under `dir` (default depth 4) that isn't already in the
+/// library, e.g. a folder of DRM-free games.
+#[tauri::command]
+fn scan_and_import(
+    state: State<AppState>,
+    dir: String,
+    max_depth: Option<usize>,
+) -> Result<library::ImportSummary, CommandError> {
+    log::info!("Scanning {:?} for games to import", dir);
+    let mut library = state.library.write().unwrap();
+    library
+        .scan_and_import(Path::new(&dir), max_depth.unwrap_or(4))
+        .map_err(|e| {
+            log::error!("Failed to import games from {:?}: {}", dir, e);
+            CommandError::from(e)
+        })
+}
+
+/// Adds the installed games from a Playnite JSON library export, skipping executables
+/// already in the library. Playnite categories, tags and sources become tags.
+#[tauri::command]
+fn import_playnite(
+    state: State<AppState>,
+    export_path: String,
+) -> Result<library::ImportSummary, CommandError> {
+    log::info!("Importing Playnite export: {:?}", export_path);
+    let contents = std::fs::read_to_string(&export_path)?;
+    let games = playnite::parse_export(&contents).map_err(|e| {
+        log::error!("Failed to parse Playnite export {:?}: {}", export_path, e);
+        CommandError::from(e)
+    })?;
+    let mut library = state.library.write().unwrap();
+    let summary = library.import_games(games).map_err(|e| {
+        log::error!("Failed to import Playnite export: {}", e);
+        CommandError::from(e)
+    })?;
+    log::info!(
+        "Imported {} game(s) from Playnite; skipped {} already in the library",
+        summary.added.len(),
+        summary.skipped_duplicates
+    );
+    Ok(summary)
+}
+
+/// Sets covers on custom games that have none from the images in `dir` whose file
+/// names match the game titles, e.g. a folder of downloaded box art.
+#[tauri::command]
+fn auto_assign_covers(
+    state: State<AppState>,
+    dir: String,
+) -> Result<Vec<CustomGame>, CommandError> {
+    log::info!("Matching covers in {:?} to custom games", dir);
+    let mut library = state.library.write().unwrap();
+    library.auto_assign_covers(Path::new(&dir)).map_err(|e| {
+        log::error!("Failed to assign covers from {:?}: {}", dir, e);
+        CommandError::from(e)
+    })
+}
+
+/// Custom games in `order`, or in the saved sort order when `order` is omitted.
+#[tauri::command]
+fn get_games_sorted(state: State<AppState>, order: Option<SortOrder>) -> Vec<CustomGame> {
+    let order = order.unwrap_or_else(|| state.settings.lock().unwrap().settings().sort_order);
+    let library = state.library.read().unwrap();
+    library.sorted(order).into_iter().cloned().collect()
+}
+
+/// Saves the user's chosen sort order for later [`get_games_sorted`] calls.
+#[tauri::command]
+fn set_sort_order(state: State<AppState>, order: SortOrder) -> Result<(), CommandError> {
+    let mut store = state.settings.lock().unwrap();
+    store.update_with(|s| s.sort_order = order).map_err(|e| {
+        log::error!("Failed to save sort order: {}", e);
+        CommandError::from(e)
+    })?;
+    Ok(())
+}
+
+/// Whether picked covers, heroes and screenshots are copied into the app's own `covers`
+/// directory.
+#[tauri::command]
+fn get_manage_covers(state: State<AppState>) -> bool {
+    state.settings.lock().unwrap().settings().manage_covers
+}
+
+/// Turns managed covers on or off. Only images set after the change are affected.
+#[tauri::command]
+fn set_manage_covers(state: State<AppState>, enabled: bool) -> Result<(), CommandError> {
+    let mut store = state.settings.lock().unwrap();
+    store
+        .update_with(|s| s.manage_covers = enabled)
+        .map_err(|e| {
+            log::error!("Failed to save managed covers setting: {}", e);
+            CommandError::from(e)
+        })?;
+    drop(store);
+    state.library.write().unwrap().set_manage_covers(enabled);
+    Ok(())
+}
+
+/// Stores a discovered Steam game as a custom entry so it can carry notes, tags and a
+/// cover of its own; it keeps launching through Steam.
+#[tauri::command]
+fn import_steam_game(state: State<AppState>, app_id: u32) -> Result<CustomGame, CommandError> {
+    log::info!("Importing Steam game: app_id={}", app_id);
+    let games = load_steam_games(&state, false)?;
+    let Some(steam_game) = games.iter().find(|g| g.app_id == app_id) else {
+        log::error!("Failed to import Steam game: app_id={} not found", app_id);
+        return Err(LibraryError::NotFound(format!("steam:{app_id}")).into());
+    };
+    let mut library = state.library.write().unwrap();
+    library
+        .add(CustomGame::from_steam(steam_game))
+        .cloned()
+        .map_err(|e| {
+            log::error!("Failed to import Steam game: {}", e);
+            CommandError::from(e)
         })
 }
 
+/// Stores a discovered Epic game as a custom entry; it keeps launching through Epic.
+#[tauri::command]
+fn import_epic_game(state: State<AppState>, app_name: String) -> Result<CustomGame, CommandError> {
+    log::info!("Importing Epic game: app_name={:?}", app_name);
+    let games = epic::discover_games()?;
+    let Some(epic_game) = games.iter().find(|g| g.app_name == app_name) else {
+        log::error!("Failed to import Epic game: {:?} not found", app_name);
+        return Err(LibraryError::NotFound(format!("epic:{app_name}")).into());
+    };
+    let mut library = state.library.write().unwrap();
+    library
+        .add(CustomGame::from_epic(epic_game))
+        .cloned()
+        .map_err(|e| {
+            log::error!("Failed to import Epic game: {}", e);
+            CommandError::from(e)
+        })
+}
+
+/// ROM files under `rom_dir` with one of `extensions` (e.g. `["nes", "sfc"]`), each
+/// launched as `emulator [emulator_args...] <rom>`.
+#[tauri::command]
+fn scan_roms(
+    emulator: String,
+    emulator_args: Option<Vec<String>>,
+    rom_dir: String,
+    extensions: Vec<String>,
+) -> Vec<emulators::RomGame> {
+    emulators::scan_roms(
+        &emulator,
+        &emulator_args.unwrap_or_default(),
+        Path::new(&rom_dir),
+        &extensions,
+    )
+}
+
+/// Starts a ROM found by [`scan_roms`] in its emulator.
 #[tauri::command]
-fn remove_game(state: State<AppState>, id: String) -> Result<(), String> {
+fn launch_rom(rom: emulators::RomGame) -> Result<(), CommandError> {
+    log::info!("Launching ROM {:?} with {}", rom.rom, rom.emulator);
+    launcher::launch(&rom.launch_target()).map_err(|e| {
+        log::error!("Failed to launch ROM {:?}: {}", rom.rom, e);
+        CommandError::from(e)
+    })
+}
+
+/// Starts a Lutris game by slug, through the `lutris` CLI when it is installed.
+#[tauri::command]
+fn launch_lutris_game(slug: String) -> Result<(), CommandError> {
+    log::info!("Launching Lutris game: {}", slug);
+    let game = lutris::discover_games()
+        .into_iter()
+        .find(|g| g.slug == slug);
+    let Some(game) = game else {
+        let message = format!("No Lutris game with slug {slug}");
+        return Err(CommandError::new("not_found", message));
+    };
+    let Some(target) = game.launch_target() else {
+        log::warn!("{} needs the Lutris CLI to launch", game.name);
+        return Err(launcher::LaunchError::NoRunner(game.name).into());
+    };
+    launcher::launch(&target).map_err(|e| {
+        log::error!("Failed to launch Lutris game {}: {}", slug, e);
+        CommandError::from(e)
+    })
+}
+
+/// Adds the game behind a `.desktop` launcher, running the same command line.
+#[tauri::command]
+fn import_desktop_game(
+    state: State<AppState>,
+    desktop_file: String,
+) -> Result<CustomGame, CommandError> {
+    log::info!("Importing desktop entry: {:?}", desktop_file);
+    let contents = std::fs::read_to_string(&desktop_file)?;
+    let Some(entry) = linux_apps::parse_desktop_entry(&contents, Path::new(&desktop_file)) else {
+        let message = format!("Not a launchable desktop entry: {desktop_file}");
+        return Err(CommandError::new("invalid_argument", message));
+    };
+    let mut library = state.library.write().unwrap();
+    library.add(entry.to_custom_game()).cloned().map_err(|e| {
+        log::error!("Failed to import desktop entry: {}", e);
+        CommandError::from(e)
+    })
+}
+
+#[tauri::command]
+fn remove_game(state: State<AppState>, id: String) -> Result<(), CommandError> {
     log::info!("Removing custom game: id={}", id);
     state
         .library
-        .lock()
+        .write()
         .unwrap()
         .remove(&id)
         .map(|removed| {
@@ -106,52 +970,550 @@ fn remove_game(state: State<AppState>, id: String) -> Result<(), String> {
         })
         .map_err(|e| {
             log::error!("Failed to remove game id={}: {}", id, e);
-            e.to_string()
+            CommandError::from(e)
         })
 }
 
+/// Removes several custom games at once, e.g. from the cleanup screen. Unknown ids are
+/// skipped; the removed games are returned.
+#[tauri::command]
+fn remove_games(state: State<AppState>, ids: Vec<String>) -> Result<Vec<CustomGame>, CommandError> {
+    log::info!("Removing {} custom game(s)", ids.len());
+    let mut library = state.library.write().unwrap();
+    library.remove_many(&ids).map_err(|e| {
+        log::error!("Failed to remove games: {}", e);
+        CommandError::from(e)
+    })
+}
+
+/// Payload of the `game-exited` event emitted when a tracked game closes.
+#[derive(Clone, serde::Serialize)]
+struct GameExited {
+    key: String,
+    elapsed_secs: u64,
+}
+
+/// Payload of the `game-output` event carrying a launched game's leading stdout/stderr.
+#[derive(Clone, serde::Serialize)]
+struct GameOutput {
+    key: String,
+    #[serde(flatten)]
+    output: launcher::CapturedOutput,
+}
+
+/// Directly spawned games are watched for [`launcher::EARLY_EXIT_WINDOW`], so one that
+/// crashes on start fails the call instead of looking launched.
+///
+/// Async so a game's pre-launch command runs on a worker thread instead of freezing
+/// the UI until it finishes.
 #[tauri::command]
-fn launch_game(
-    _state: State<AppState>,
-    _key: String,
+#[allow(clippy::too_many_arguments)]
+async fn launch_game(
+    app: AppHandle,
+    key: String,
     app_id: Option<u32>,
+    is_shortcut: Option<bool>,
     executable: Option<String>,
     epic_launch_uri: Option<String>,
-) -> Result<(), String> {
+    detached: Option<bool>,
+    force_via_steam: Option<bool>,
+    bypass_steam: Option<bool>,
+) -> Result<(), CommandError> {
     log::info!(
-        "launch_game: key={:?} app_id={:?} executable={:?} epic={:?}",
-        _key,
+        "launch_game: key={:?} app_id={:?} shortcut={:?} executable={:?} epic={:?}",
+        key,
         app_id,
+        is_shortcut,
         executable,
         epic_launch_uri,
     );
+    let state = app.state::<AppState>();
+    let mut target = launch_target(
+        &state,
+        &key,
+        app_id,
+        is_shortcut,
+        executable,
+        epic_launch_uri,
+        ShortcutLaunch {
+            force_via_steam: force_via_steam.unwrap_or(false),
+            bypass_steam: bypass_steam.unwrap_or(false),
+        },
+    )?;
+    // Detached games survive the launcher closing; tracking still works while we're open.
+    if let LaunchTarget::Executable { options, .. } = &mut target {
+        options.detached = detached.unwrap_or(false);
+    }
+    let output_app = app.clone();
+    let output_key = key.clone();
+    let on_output = move |output| {
+        let payload = GameOutput {
+            key: output_key,
+            output,
+        };
+        if let Err(e) = output_app.emit("game-output", payload) {
+            log::warn!("Failed to emit game-output: {}", e);
+        }
+    };
+    let exit_app = app.clone();
+    let exit_key = key.clone();
+    let child = launcher::launch_and_track_captured(&target, on_output, move |elapsed| {
+        let elapsed_secs = elapsed.as_secs();
+        // Custom game keys are `custom-<uuid>`; only those have playtime in the library.
+        if let Some(id) = exit_key.strip_prefix("custom-") {
+            let state = exit_app.state::<AppState>();
+            let mut library = state.library.write().unwrap();
+            if let Err(e) = library.add_playtime(id, elapsed_secs) {
+                log::warn!("Failed to record playtime for {:?}: {}", exit_key, e);
+            }
+        }
+        let payload = GameExited {
+            key: exit_key,
+            elapsed_secs,
+        };
+        if let Err(e) = exit_app.emit("game-exited", payload) {
+            log::warn!("Failed to emit game-exited: {}", e);
+        }
+    })
+    .map_err(|e| {
+        log::error!("Launch failed for {:?}: {}", key, e);
+        CommandError::from(e)
+    })?;
+    if let Some(child) = child {
+        state
+            .running
+            .lock()
+            .unwrap()
+            .insert(key.clone(), child.clone());
+        launcher::watch_startup(&child, launcher::EARLY_EXIT_WINDOW).map_err(|e| {
+            log::error!("{:?} failed to start: {}", key, e);
+            CommandError::from(e)
+        })?;
+    }
+    Ok(())
+}
+
+/// Shows what `launch_game` would run for the same arguments, without launching anything.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+fn preview_launch(
+    state: State<AppState>,
+    key: String,
+    app_id: Option<u32>,
+    is_shortcut: Option<bool>,
+    executable: Option<String>,
+    epic_launch_uri: Option<String>,
+    force_via_steam: Option<bool>,
+    bypass_steam: Option<bool>,
+) -> Result<launcher::LaunchPlan, CommandError> {
+    let target = launch_target(
+        &state,
+        &key,
+        app_id,
+        is_shortcut,
+        executable,
+        epic_launch_uri,
+        ShortcutLaunch {
+            force_via_steam: force_via_steam.unwrap_or(false),
+            bypass_steam: bypass_steam.unwrap_or(false),
+        },
+    )?;
+    Ok(launcher::launch_plan(&target))
+}
+
+/// The URI `launch_game` would open for a Steam or Epic game (or the executable path
+/// for a custom one), so users can copy it when a launch misbehaves.
+#[tauri::command]
+fn get_launch_uri(
+    state: State<AppState>,
+    app_id: Option<u32>,
+    is_shortcut: Option<bool>,
+    epic_launch_uri: Option<String>,
+    executable: Option<String>,
+) -> Result<String, CommandError> {
+    let target = launch_target(
+        &state,
+        "",
+        app_id,
+        is_shortcut,
+        executable,
+        epic_launch_uri,
+        ShortcutLaunch::default(),
+    )?;
+    Ok(target.uri_or_path())
+}
+
+/// Opens the store page of a Steam game (by `app_id`) or an installed Epic game (by
+/// `epic_app_name`) in the default browser. Non-Steam shortcuts have no store page.
+#[tauri::command]
+fn open_store_page(
+    app_id: Option<u32>,
+    is_shortcut: Option<bool>,
+    epic_app_name: Option<String>,
+) -> Result<(), CommandError> {
+    let epic_game = match &epic_app_name {
+        Some(app_name) if app_id.is_none() => {
+            let games = epic::discover_games()?;
+            let Some(game) = games.into_iter().find(|g| &g.app_name == app_name) else {
+                log::error!("Cannot open store page: Epic game {:?} not found", app_name);
+                return Err(LibraryError::NotFound(format!("epic:{app_name}")).into());
+            };
+            Some(game)
+        }
+        _ => None,
+    };
+    let url = store_page_url(app_id, is_shortcut.unwrap_or(false), epic_game.as_ref())?;
+    log::info!("Opening store page {}", url);
+    launcher::open_uri(&url).map_err(|e| {
+        log::error!("Failed to open store page {}: {}", url, e);
+        CommandError::from(e)
+    })
+}
+
+/// The store URL behind [`open_store_page`].
+fn store_page_url(
+    app_id: Option<u32>,
+    is_shortcut: bool,
+    epic_game: Option<&epic::EpicGame>,
+) -> Result<String, CommandError> {
+    match (app_id, epic_game) {
+        (Some(id), _) if is_shortcut => Err(CommandError::new(
+            "no_store_page",
+            format!("Non-Steam shortcut {id} has no store page"),
+        )),
+        (Some(id), _) => Ok(steam::store_url(id)),
+        (None, Some(game)) => Ok(game.store_url()),
+        (None, None) => Err(CommandError::new(
+            "invalid_argument",
+            "No Steam app id or Epic game specified",
+        )),
+    }
+}
+
+/// The `launch_game` toggles for non-Steam shortcuts; see [`steam::ShortcutGame::launch_target`].
+#[derive(Debug, Clone, Copy, Default)]
+struct ShortcutLaunch {
+    force_via_steam: bool,
+    bypass_steam: bool,
+}
+
+/// Picks the launch target for a game from the frontend's view of it. Stored custom
+/// games carry their own args and working directory, and a shortcut bypassing Steam
+/// is looked up in `shortcuts.vdf` for its executable. Steam targets go through the
+/// Flatpak client when the configured Steam root is a Flatpak install.
+fn launch_target(
+    state: &AppState,
+    key: &str,
+    app_id: Option<u32>,
+    is_shortcut: Option<bool>,
+    executable: Option<String>,
+    epic_launch_uri: Option<String>,
+    shortcut: ShortcutLaunch,
+) -> Result<LaunchTarget, CommandError> {
     let target = match (app_id, epic_launch_uri, executable) {
+        (Some(id), _, _) if is_shortcut.unwrap_or(false) && shortcut.bypass_steam => {
+            let root = steam_root(state)?;
+            steam::discover_shortcut_games(&root, true)
+                .into_iter()
+                .find(|s| s.app_id == id)
+                .ok_or_else(|| {
+                    CommandError::new("not_found", format!("Steam shortcut not found: {}", id))
+                })?
+                .launch_target(shortcut.force_via_steam, shortcut.bypass_steam)
+        }
+        (Some(id), _, _) if is_shortcut.unwrap_or(false) => LaunchTarget::steam_shortcut(id),
         (Some(id), _, _) => LaunchTarget::steam(id),
         (_, Some(uri), _) => LaunchTarget::epic_game(uri),
-        (_, _, Some(path)) => LaunchTarget::executable(path),
+        (_, _, Some(path)) => match key.strip_prefix("custom-").and_then(|id| {
+            let library = state.library.read().unwrap();
+            library.get(id).map(CustomGame::launch_target)
+        }) {
+            Some(target) => target,
+            None => LaunchTarget::executable(path),
+        },
         (None, None, None) => {
-            log::warn!("launch_game called with no launch target");
-            return Err("No launch target specified".to_string());
+            log::warn!("No launch target specified for {:?}", key);
+            return Err(CommandError::new(
+                "invalid_argument",
+                "No launch target specified",
+            ));
         }
     };
-    launcher::launch(&target).map_err(|e| {
-        log::error!("Launch failed for {:?}: {}", _key, e);
-        e.to_string()
+    if target.steam_uri().is_none() {
+        return Ok(target);
+    }
+    let flatpak = steam_root(state).is_ok_and(|root| steam::is_flatpak_root(&root));
+    Ok(target.through_flatpak(flatpak))
+}
+
+/// Kills a game previously started directly (not through Steam/Epic) by `launch_game`.
+#[tauri::command]
+fn stop_game(state: State<AppState>, key: String) -> Result<(), CommandError> {
+    log::info!("stop_game: key={:?}", key);
+    state.running.lock().unwrap().stop(&key).map_err(|e| {
+        log::error!("Failed to stop {:?}: {}", key, e);
+        CommandError::from(e)
+    })
+}
+
+/// Returns the launch keys of directly started games that are still running.
+#[tauri::command]
+fn get_running_games(state: State<AppState>) -> Vec<String> {
+    state.running.lock().unwrap().running()
+}
+
+/// Whether the game under `key` is running: either we spawned it and it hasn't exited,
+/// or a process with its executable's name is live (e.g. it was started some other way).
+/// Steam games don't expose an executable, so they only count when we spawned them.
+#[tauri::command]
+fn game_running(state: State<AppState>, key: String) -> bool {
+    if state.running.lock().unwrap().running().contains(&key) {
+        return true;
+    }
+    let executable = match process_lookup(&key) {
+        Some(ProcessLookup::Custom(id)) => {
+            let library = state.library.read().unwrap();
+            library
+                .get(id)
+                .filter(|g| g.imported_from.is_none())
+                .map(|g| g.executable.clone())
+        }
+        Some(ProcessLookup::Epic(app_name)) => epic::discover_games()
+            .unwrap_or_default()
+            .into_iter()
+            .find(|g| g.app_name == app_name)
+            .and_then(|g| g.launch_executable),
+        None => None,
+    };
+    executable.is_some_and(|exe| {
+        launcher::is_process_running(&launcher::resolve_process_name(&exe.to_string_lossy()))
     })
 }
 
+/// Where [`game_running`] finds the executable of the game a [`Game::key`] names.
+#[derive(Debug, PartialEq)]
+enum ProcessLookup<'a> {
+    /// A custom game's id.
+    Custom(&'a str),
+    /// An Epic game's app name.
+    Epic(&'a str),
+}
+
+/// Parses a [`Game::key`] into a [`ProcessLookup`]; Steam keys have none.
+fn process_lookup(key: &str) -> Option<ProcessLookup<'_>> {
+    match key.strip_prefix("custom-") {
+        Some(id) => Some(ProcessLookup::Custom(id)),
+        None => key.strip_prefix("epic:").map(ProcessLookup::Epic),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Settings commands
+// ---------------------------------------------------------------------------
+
+#[tauri::command]
+fn get_settings(state: State<AppState>) -> settings::Settings {
+    state.settings.lock().unwrap().settings().clone()
+}
+
+/// Saves the whole settings object and returns it as stored. A changed Steam root also
+/// restarts the file watcher on the new root's libraries.
+#[tauri::command]
+fn update_settings(
+    app: AppHandle,
+    state: State<AppState>,
+    settings: settings::Settings,
+) -> Result<settings::Settings, CommandError> {
+    let mut store = state.settings.lock().unwrap();
+    let previous_root = store.settings().steam_root_override.clone();
+    let saved = store.update(settings).cloned().map_err(|e| {
+        log::error!("Failed to save settings: {}", e);
+        CommandError::from(e)
+    })?;
+    drop(store);
+    // Hidden Steam apps may have changed, so cached discovery results are stale.
+    state.steam_cache.lock().unwrap().clear();
+    state
+        .library
+        .write()
+        .unwrap()
+        .set_manage_covers(saved.manage_covers);
+    if saved.steam_root_override != previous_root {
+        // The running watcher follows the old root's libraries.
+        log::info!("Steam root changed; restarting the file watcher");
+        let watcher = start_watcher(
+            &app,
+            &library_path(&app),
+            saved.steam_root_override.as_deref(),
+        );
+        *state.watcher.lock().unwrap() = watcher;
+    }
+    Ok(saved)
+}
+
 // ---------------------------------------------------------------------------
 // File-explorer commands
 // ---------------------------------------------------------------------------
 
+/// Lists `path` for the file explorer; hidden entries are skipped unless `show_hidden`
+/// (default: the `show_hidden` setting).
+/// `filter` is `executables` or `extensions:[...]`; see [`fs_explorer::EntryFilter`].
 #[tauri::command]
-fn list_directory(path: String) -> Result<Vec<fs_explorer::DirEntry>, String> {
-    fs_explorer::read_dir(&path)
+fn list_directory(
+    state: State<AppState>,
+    path: String,
+    show_hidden: Option<bool>,
+    filter: Option<String>,
+) -> Result<Vec<fs_explorer::DirEntry>, CommandError> {
+    let filter = filter
+        .as_deref()
+        .map(fs_explorer::EntryFilter::parse)
+        .transpose()
+        .map_err(|e| CommandError::new("invalid_argument", e))?;
+    let show_hidden =
+        show_hidden.unwrap_or_else(|| state.settings.lock().unwrap().settings().show_hidden);
+    let mut entries = fs_explorer::read_dir_opts(&path, show_hidden)?;
+    if let Some(filter) = filter {
+        entries.retain(|e| filter.keep(e));
+    }
+    Ok(entries)
 }
 
+/// Like [`list_directory`], but returns only `limit` entries from `offset` along with
+/// the total, for folders with tens of thousands of entries.
 #[tauri::command]
-fn get_file_explorer_bookmarks() -> Vec<fs_explorer::Bookmark> {
-    fs_explorer::get_bookmarks()
+fn list_directory_paged(
+    state: State<AppState>,
+    path: String,
+    offset: usize,
+    limit: usize,
+    show_hidden: Option<bool>,
+    filter: Option<String>,
+) -> Result<fs_explorer::DirPage, CommandError> {
+    let entries = list_directory(state, path, show_hidden, filter)?;
+    Ok(fs_explorer::page(entries, offset, limit))
+}
+
+/// Finds launchable files under `root` (default depth 6) for the "add game" flow.
+#[tauri::command]
+fn search_executables(root: String, max_depth: Option<usize>) -> Vec<fs_explorer::DirEntry> {
+    let found = fs_explorer::find_executables(Path::new(&root), max_depth.unwrap_or(6));
+    log::info!(
+        "search_executables: {} result(s) under {:?}",
+        found.len(),
+        root
+    );
+    found
+}
+
+/// Opens the system file manager at `path`, selecting it where the platform allows.
+#[tauri::command]
+fn reveal_in_file_manager(path: String) -> Result<(), CommandError> {
+    launcher::reveal_in_file_manager(Path::new(&path)).map_err(|e| {
+        log::error!("Failed to reveal {:?}: {}", path, e);
+        CommandError::from(e)
+    })
+}
+
+/// Returns the parent of `path`, or `None` when it's already a filesystem root.
+#[tauri::command]
+fn parent_directory(path: String) -> Option<String> {
+    fs_explorer::parent_directory(&path)
+}
+
+#[tauri::command]
+fn normalize_path(path: String) -> String {
+    fs_explorer::normalize_path(&path)
+}
+
+/// Returns mounted drives/volumes so users can browse games stored off the system drive.
+#[tauri::command]
+fn list_drives() -> Vec<fs_explorer::Bookmark> {
+    fs_explorer::list_drives()
+}
+
+/// Returns the built-in OS locations followed by the user's own bookmarks.
+#[tauri::command]
+fn get_file_explorer_bookmarks(state: State<AppState>) -> Vec<fs_explorer::Bookmark> {
+    let mut bookmarks = fs_explorer::get_bookmarks();
+    bookmarks.extend_from_slice(state.bookmarks.lock().unwrap().bookmarks());
+    bookmarks
+}
+
+#[tauri::command]
+fn add_bookmark(
+    state: State<AppState>,
+    label: String,
+    path: String,
+) -> Result<fs_explorer::Bookmark, CommandError> {
+    let mut bookmarks = state.bookmarks.lock().unwrap();
+    bookmarks.add(&label, &path).cloned().map_err(|e| {
+        log::error!("Failed to add bookmark {:?}: {}", path, e);
+        CommandError::from(e)
+    })
+}
+
+#[tauri::command]
+fn remove_bookmark(state: State<AppState>, path: String) -> Result<(), CommandError> {
+    let mut bookmarks = state.bookmarks.lock().unwrap();
+    bookmarks.remove(&path).map(|_| ()).map_err(|e| {
+        log::error!("Failed to remove bookmark {:?}: {}", path, e);
+        CommandError::from(e)
+    })
+}
+
+/// Returns a cached thumbnail of the cover at `path`, at most `max_width` pixels wide
+/// (default 300), or `None` if the image can't be decoded so the UI uses the original.
+#[tauri::command]
+fn get_cover_thumbnail(app: AppHandle, path: String, max_width: Option<u32>) -> Option<String> {
+    let cache_dir = app
+        .path()
+        .app_cache_dir()
+        .map_err(|e| log::warn!("Could not resolve app cache dir: {}", e))
+        .ok()?
+        .join("thumbnails");
+    thumbnail::thumbnail(&cache_dir, Path::new(&path), max_width.unwrap_or(300))
+        .map(|p| p.to_string_lossy().to_string())
+}
+
+/// Watches the library file and Steam libraries; on change, reloads the library from disk
+/// and emits `library-changed` so the frontend refreshes. Returns `None` if watching fails.
+fn start_watcher(
+    app: &AppHandle,
+    library_file: &Path,
+    steam_root_override: Option<&Path>,
+) -> Option<watcher::LibraryWatcher> {
+    let steamapps = steam::resolve_steam_root(steam_root_override)
+        .ok()
+        .and_then(|root| steam::find_library_paths(&root).ok())
+        .unwrap_or_default();
+    let app = app.clone();
+    let on_change = move |changes: watcher::Changes| {
+        let mut reloaded = false;
+        if changes.library {
+            if let Some(state) = app.try_state::<AppState>() {
+                match state.library.write().unwrap().reload_if_changed() {
+                    Ok(changed) => reloaded = changed,
+                    Err(e) => log::warn!("Failed to reload library: {}", e),
+                }
+            }
+        }
+        // The library's own saves also land here; they read back unchanged and are skipped.
+        if !reloaded && !changes.steam {
+            return;
+        }
+        log::info!("Library files changed on disk: {:?}", changes);
+        if let Err(e) = app.emit("library-changed", ()) {
+            log::warn!("Failed to emit library-changed: {}", e);
+        }
+    };
+    match watcher::LibraryWatcher::start(library_file, &steamapps, on_change) {
+        Ok(watcher) => Some(watcher),
+        Err(e) => {
+            log::warn!("File watching disabled: {}", e);
+            None
+        }
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -175,23 +1537,411 @@ pub fn run() {
         .setup(|app| {
             let path = library_path(app.handle());
             log::info!("Loading custom game library from {:?}", path);
-            let library = Library::load(path).expect("failed to load game library");
+            let mut library = Library::load(&path).expect("failed to load game library");
             log::info!("Library ready: {} custom game(s)", library.games().len());
+            let bookmarks = fs_explorer::BookmarkStore::load(bookmarks_path(app.handle()));
+            let settings = settings::SettingsStore::load(settings_path(app.handle()));
+            library.set_manage_covers(settings.settings().manage_covers);
+            let watcher = start_watcher(
+                app.handle(),
+                &path,
+                settings.settings().steam_root_override.as_deref(),
+            );
             app.manage(AppState {
-                library: Mutex::new(library),
+                library: RwLock::new(library),
+                steam_cache: Mutex::new(steam::DiscoveryCache::default()),
+                running: Mutex::new(launcher::ProcessRegistry::default()),
+                bookmarks: Mutex::new(bookmarks),
+                settings: Mutex::new(settings),
+                watcher: Mutex::new(watcher),
             });
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
             get_steam_games,
+            get_all_games,
+            search_games,
+            recently_played,
+            library_storage,
+            export_library,
             get_epic_games,
+            stream_steam_games,
+            stream_epic_games,
+            get_gog_games,
+            get_itch_games,
+            discover_desktop_games,
+            import_desktop_game,
+            scan_roms,
+            launch_rom,
+            discover_lutris_games,
+            launch_lutris_game,
             get_custom_games,
+            get_game,
+            validate_library,
+            list_tags,
+            toggle_favorite,
+            get_external_favorites,
+            get_collections,
+            create_collection,
+            add_to_collection,
+            remove_from_collection,
+            get_games_by_tag,
             add_game,
+            update_game,
             remove_game,
+            remove_games,
+            import_steam_game,
+            scan_and_import,
+            import_playnite,
+            auto_assign_covers,
+            get_games_sorted,
+            steam_status,
+            get_steam_libraries,
+            rescan_steam_library,
+            game_running,
+            set_sort_order,
+            get_manage_covers,
+            set_manage_covers,
+            get_cover_thumbnail,
+            get_settings,
+            update_settings,
+            import_epic_game,
             launch_game,
+            preview_launch,
+            get_launch_uri,
+            open_store_page,
+            stop_game,
+            get_running_games,
             list_directory,
+            list_directory_paged,
+            search_executables,
+            reveal_in_file_manager,
+            parent_directory,
+            normalize_path,
+            list_drives,
             get_file_explorer_bookmarks,
+            add_bookmark,
+            remove_bookmark,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
+
+// ============================================================
+// Tests
+// ============================================================
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn steam_game(app_id: u32, name: &str) -> SteamGame {
+        SteamGame {
+            app_id,
+            name: name.to_string(),
+            install_dir: PathBuf::from("/fake"),
+            is_shortcut: false,
+            size_on_disk: None,
+            last_played: None,
+            cover_image: None,
+            owner: None,
+        }
+    }
+
+    fn shortcut(app_id: u32, name: &str, exe: &str) -> steam::ShortcutGame {
+        steam::ShortcutGame {
+            app_id,
+            app_name: name.to_string(),
+            exe: exe.to_string(),
+            start_dir: String::new(),
+            launch_options: String::new(),
+            is_hidden: false,
+            last_played: None,
+            cover_image: None,
+            tags: vec![],
+            owner: None,
+        }
+    }
+
+    // --- dedup_shortcuts ---
+
+    #[test]
+    fn shortcut_with_matching_name_is_merged() {
+        let games = vec![steam_game(620, "Portal 2")];
+        let kept = dedup_shortcuts(
+            &games,
+            vec![
+                shortcut(1, "  portal 2 ", "/opt/portal2/portal2.sh"),
+                shortcut(2, "RetroArch", "/usr/bin/retroarch"),
+            ],
+        );
+        assert_eq!(kept.len(), 1);
+        assert_eq!(kept[0].name, "RetroArch");
+        assert!(kept[0].is_shortcut);
+    }
+
+    #[test]
+    fn shortcut_inside_install_dir_is_merged() {
+        let mut game = steam_game(1091500, "Cyberpunk 2077");
+        game.install_dir = PathBuf::from("/steam/steamapps/common/Cyberpunk 2077");
+        let kept = dedup_shortcuts(
+            &[game],
+            vec![shortcut(
+                1,
+                "CP2077 (modded)",
+                "/steam/steamapps/common/Cyberpunk 2077/bin/x64/Cyberpunk2077.exe",
+            )],
+        );
+        assert!(kept.is_empty());
+    }
+
+    #[test]
+    fn distinct_shortcuts_are_kept() {
+        let mut game = steam_game(620, "Portal 2");
+        game.install_dir = PathBuf::from("/steam/steamapps/common/Portal 2");
+        let kept = dedup_shortcuts(
+            &[game],
+            vec![
+                // Similar name and a sibling directory sharing a prefix are not duplicates.
+                shortcut(
+                    1,
+                    "Portal 2 Mod",
+                    "/steam/steamapps/common/Portal 2 Mod/p2.sh",
+                ),
+                shortcut(2, "Emulator", ""),
+            ],
+        );
+        let names: Vec<_> = kept.iter().map(|g| g.name.as_str()).collect();
+        assert_eq!(names, ["Portal 2 Mod", "Emulator"]);
+    }
+
+    // --- forward_new_steam_games ---
+
+    #[test]
+    fn forwards_each_game_once_across_batches() {
+        let mut seen = HashSet::new();
+        let mut emitted = Vec::new();
+
+        let first = forward_new_steam_games(
+            vec![steam_game(1, "A"), steam_game(2, "B")],
+            &mut seen,
+            |g| emitted.push(g.name.clone()),
+        );
+        let second = forward_new_steam_games(
+            vec![steam_game(2, "B again"), steam_game(3, "C")],
+            &mut seen,
+            |g| emitted.push(g.name.clone()),
+        );
+
+        assert_eq!(first, 2);
+        assert_eq!(second, 1);
+        assert_eq!(emitted, ["A", "B", "C"]);
+    }
+
+    #[test]
+    fn forwards_nothing_for_empty_batch() {
+        let mut calls = 0;
+        let count = forward_new_steam_games(vec![], &mut HashSet::new(), |_| calls += 1);
+        assert_eq!(count, 0);
+        assert_eq!(calls, 0);
+    }
+
+    // --- duplicate_title_warnings ---
+
+    #[test]
+    fn duplicate_title_warns_case_insensitively() {
+        let path = std::env::temp_dir().join(format!("lib_test_{}.json", uuid::Uuid::new_v4()));
+        let mut library = Library::load(&path).unwrap();
+        library
+            .add(CustomGame::new(
+                "Resident Evil 4",
+                "/games/re4",
+                None,
+                vec![],
+                None,
+            ))
+            .unwrap();
+
+        assert_eq!(
+            duplicate_title_warnings(&library, "resident evil 4"),
+            ["You already have a game named \"Resident Evil 4\""]
+        );
+        assert!(duplicate_title_warnings(&library, "Resident Evil 4 Remake").is_empty());
+
+        std::fs::remove_file(path).ok();
+    }
+
+    // --- edited_field ---
+
+    #[test]
+    fn edited_field_keeps_omitted_and_clears_empty() {
+        let stored = Some(PathBuf::from("/games/quake"));
+        assert_eq!(edited_field(None, stored.clone()), stored);
+        assert_eq!(edited_field(Some("  ".to_string()), stored.clone()), None);
+        assert_eq!(
+            edited_field(Some("/games/doom".to_string()), stored),
+            Some(PathBuf::from("/games/doom"))
+        );
+        assert_eq!(edited_field::<String>(Some(String::new()), None), None);
+    }
+
+    // --- store_page_url ---
+
+    #[test]
+    fn store_page_urls_for_steam_and_epic() {
+        assert_eq!(
+            store_page_url(Some(620), false, None).unwrap(),
+            "https://store.steampowered.com/app/620"
+        );
+
+        let epic_game = epic::EpicGame {
+            app_name: "Min".to_string(),
+            display_name: "Hades".to_string(),
+            install_location: PathBuf::from("/games/hades"),
+            catalog_namespace: "min".to_string(),
+            catalog_item_id: "fb39bac8278a4126989f0fe12e7353af".to_string(),
+            cover_image: None,
+            launch_executable: None,
+            install_size: None,
+            app_version: None,
+        };
+        assert_eq!(
+            store_page_url(None, false, Some(&epic_game)).unwrap(),
+            "https://store.epicgames.com/browse?q=Hades&sortBy=relevancy"
+        );
+    }
+
+    #[test]
+    fn shortcuts_have_no_store_page() {
+        let err = store_page_url(Some(3_000_000_000), true, None).unwrap_err();
+        assert_eq!(err.code, "no_store_page");
+        assert_eq!(
+            store_page_url(None, false, None).unwrap_err().code,
+            "invalid_argument"
+        );
+    }
+
+    // --- process_lookup ---
+
+    #[test]
+    fn process_lookup_parses_game_keys() {
+        let epic = Game::Epic(epic::EpicGame {
+            app_name: "Quail".to_string(),
+            display_name: "Quail".to_string(),
+            install_location: PathBuf::from("/games/quail"),
+            catalog_namespace: "ns".to_string(),
+            catalog_item_id: "item".to_string(),
+            cover_image: None,
+            launch_executable: None,
+            install_size: None,
+            app_version: None,
+        });
+        assert_eq!(
+            process_lookup(&epic.key()),
+            Some(ProcessLookup::Epic("Quail"))
+        );
+
+        let custom = CustomGame::new("Doom", "/games/doom", None, vec![], None);
+        let id = custom.id.clone();
+        assert_eq!(
+            process_lookup(&Game::Custom(custom).key()),
+            Some(ProcessLookup::Custom(id.as_str()))
+        );
+
+        assert_eq!(
+            process_lookup(&Game::Steam(steam_game(620, "Portal 2")).key()),
+            None
+        );
+    }
+
+    // --- shared library lock ---
+
+    fn test_state(dir: &Path) -> AppState {
+        AppState {
+            library: RwLock::new(Library::load(dir.join("custom_games.json")).unwrap()),
+            steam_cache: Mutex::new(steam::DiscoveryCache::default()),
+            running: Mutex::new(launcher::ProcessRegistry::default()),
+            bookmarks: Mutex::new(fs_explorer::BookmarkStore::load(dir.join("bookmarks.json"))),
+            settings: Mutex::new(settings::SettingsStore::load(dir.join("settings.json"))),
+            watcher: Mutex::new(None),
+        }
+    }
+
+    fn has_custom(games: &[Game], title: &str) -> bool {
+        games
+            .iter()
+            .any(|g| matches!(g, Game::Custom(c) if c.title == title))
+    }
+
+    #[test]
+    fn listing_runs_while_another_reader_holds_the_library() {
+        use std::sync::mpsc;
+        use std::time::Duration;
+
+        let dir = std::env::temp_dir().join(format!("state_test_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let state = test_state(&dir);
+        state
+            .library
+            .write()
+            .unwrap()
+            .add(CustomGame::new("Doom", "/games/doom", None, vec![], None))
+            .unwrap();
+
+        let (held_tx, held_rx) = mpsc::channel();
+        let (done_tx, done_rx) = mpsc::channel::<()>();
+        std::thread::scope(|s| {
+            let state = &state;
+            s.spawn(move || {
+                let _reader = state.library.read().unwrap();
+                held_tx.send(()).unwrap();
+                // Gives up after a while so a listing that waits for this reader fails
+                // the test instead of hanging it.
+                assert!(
+                    done_rx.recv_timeout(Duration::from_secs(10)).is_ok(),
+                    "listing waited for another reader"
+                );
+            });
+            held_rx.recv().unwrap();
+            let games = custom_games(state);
+            done_tx.send(()).unwrap();
+            assert!(has_custom(&games, "Doom"));
+        });
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn listing_waits_for_an_edit_and_sees_it() {
+        use std::sync::mpsc;
+        use std::time::Duration;
+
+        let dir = std::env::temp_dir().join(format!("state_test_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let state = test_state(&dir);
+
+        std::thread::scope(|s| {
+            let mut writer = state.library.write().unwrap();
+            let (tx, rx) = mpsc::channel();
+            let state = &state;
+            s.spawn(move || tx.send(custom_games(state)).unwrap());
+            assert!(
+                rx.recv_timeout(Duration::from_millis(200)).is_err(),
+                "listing read the library mid-edit"
+            );
+            writer
+                .add(CustomGame::new(
+                    "Celeste",
+                    "/games/celeste",
+                    None,
+                    vec![],
+                    None,
+                ))
+                .unwrap();
+            drop(writer);
+            let games = rx.recv_timeout(Duration::from_secs(10)).unwrap();
+            assert!(has_custom(&games, "Celeste"));
+        });
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -1,5 +1,9 @@
+use crate::epic::EpicGame;
+use crate::launcher::{LaunchTarget, SpawnOptions};
+use crate::steam::SteamGame;
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
 use thiserror::Error;
 use uuid::Uuid;
 
@@ -11,6 +15,16 @@ pub enum LibraryError {
     Io(#[from] std::io::Error),
     #[error("Serialization error: {0}")]
     Json(#[from] serde_json::Error),
+    #[error("Executable not found: {}", .0.display())]
+    ExecutableMissing(PathBuf),
+    #[error("Collection not found: {0}")]
+    CollectionNotFound(String),
+    #[error("A game with this executable is already in the library: {}", .0.display())]
+    Duplicate(PathBuf),
+    #[error("Image is missing or not a png, jpg, webp or avif file: {}", .0.display())]
+    InvalidCover(PathBuf),
+    #[error("Library file was saved by a newer version of the app (format {0}) and is read-only")]
+    NewerVersion(u32),
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -19,8 +33,81 @@ pub struct CustomGame {
     pub title: String,
     pub executable: PathBuf,
     pub cover_image: Option<PathBuf>,
+    /// Wide background image for the game's detail view; validated like `cover_image`.
+    #[serde(default)]
+    pub hero_image: Option<PathBuf>,
+    /// Screenshot images, in display order; each validated like `cover_image`.
+    #[serde(default)]
+    pub screenshots: Vec<PathBuf>,
     pub tags: Vec<String>,
     pub notes: Option<String>,
+    /// How the frontend should render `notes`.
+    #[serde(default)]
+    pub notes_format: NotesFormat,
+    /// Total seconds spent in the game across tracked launches.
+    #[serde(default)]
+    pub playtime_secs: u64,
+    /// Unix timestamp of when the last tracked session ended, or `None` if never played.
+    #[serde(default)]
+    pub last_played: Option<u64>,
+    /// Unix timestamp of when the game was added; `0` for entries saved before it existed.
+    #[serde(default)]
+    pub added_at: u64,
+    /// Command-line arguments passed to the executable on launch.
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// Directory the executable is started in; inherits the app's cwd when `None`.
+    #[serde(default)]
+    pub working_dir: Option<PathBuf>,
+    /// Environment variables added to (or overriding) the inherited environment on launch.
+    /// Stored as a JSON object, e.g. `{"MANGOHUD": "1"}`.
+    #[serde(default, with = "env_map")]
+    pub env: Vec<(String, String)>,
+    /// Programs the executable is run through on Linux, e.g. `gamemoderun`, `mangohud`.
+    #[serde(default)]
+    pub wrappers: Vec<String>,
+    /// Wine/Proton used for Windows `.exe` games on Linux (`"wine"` or a Proton path).
+    #[serde(default)]
+    pub runner: Option<String>,
+    /// Shell command run before launching, e.g. to mount a drive; launch aborts if it fails.
+    #[serde(default)]
+    pub pre_launch: Option<String>,
+    /// Shell command run after the game exits, e.g. to restore the screen resolution.
+    #[serde(default)]
+    pub post_exit: Option<String>,
+    /// Pinned by the user; favorites sort ahead of other games.
+    #[serde(default)]
+    pub favorite: bool,
+    /// Store the game was imported from; such games launch through that store instead
+    /// of running `executable` directly. Not called `source`, which already tags
+    /// [`crate::game::Game`] variants in JSON.
+    #[serde(default)]
+    pub imported_from: Option<GameSource>,
+}
+
+/// Rendering hint for [`CustomGame::notes`].
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum NotesFormat {
+    /// Shown as literal text.
+    #[default]
+    Plain,
+    /// Rendered as Markdown.
+    Markdown,
+}
+
+/// Store a custom game was imported from, with what's needed to launch it there.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "store", rename_all = "lowercase")]
+pub enum GameSource {
+    Steam {
+        app_id: u32,
+        is_shortcut: bool,
+    },
+    Epic {
+        app_name: String,
+        launch_uri: String,
+    },
 }
 
 impl CustomGame {
@@ -36,172 +123,2464 @@ impl CustomGame {
             title: title.into(),
             executable: executable.into(),
             cover_image,
+            hero_image: None,
+            screenshots: Vec::new(),
             tags,
             notes,
+            notes_format: NotesFormat::Plain,
+            playtime_secs: 0,
+            last_played: None,
+            added_at: unix_now(),
+            args: Vec::new(),
+            working_dir: None,
+            env: Vec::new(),
+            wrappers: Vec::new(),
+            runner: None,
+            pre_launch: None,
+            post_exit: None,
+            favorite: false,
+            imported_from: None,
+        }
+    }
+
+    /// Creates a custom entry for a discovered Steam game that still launches via Steam.
+    /// The install directory stands in for the executable.
+    pub fn from_steam(game: &SteamGame) -> Self {
+        Self {
+            imported_from: Some(GameSource::Steam {
+                app_id: game.app_id,
+                is_shortcut: game.is_shortcut,
+            }),
+            last_played: game.last_played,
+            ..Self::new(
+                &game.name,
+                &game.install_dir,
+                game.cover_image.clone(),
+                vec![],
+                None,
+            )
+        }
+    }
+
+    /// Creates a custom entry for a discovered Epic game that still launches via Epic.
+    /// The install location stands in for the executable.
+    pub fn from_epic(game: &EpicGame) -> Self {
+        Self {
+            imported_from: Some(GameSource::Epic {
+                app_name: game.app_name.clone(),
+                launch_uri: game.launch_uri(),
+            }),
+            ..Self::new(
+                &game.display_name,
+                &game.install_location,
+                game.cover_image.clone(),
+                vec![],
+                None,
+            )
+        }
+    }
+
+    /// Builds the launcher target for this game, carrying its per-game launch settings.
+    /// Imported games route through their store's launcher instead.
+    pub fn launch_target(&self) -> LaunchTarget {
+        match &self.imported_from {
+            Some(GameSource::Steam {
+                app_id,
+                is_shortcut: true,
+            }) => return LaunchTarget::steam_shortcut(*app_id),
+            Some(GameSource::Steam { app_id, .. }) => return LaunchTarget::steam(*app_id),
+            Some(GameSource::Epic { launch_uri, .. }) => {
+                return LaunchTarget::epic_game(launch_uri.clone())
+            }
+            None => {}
+        }
+        LaunchTarget::executable_with(
+            self.executable.to_string_lossy(),
+            SpawnOptions {
+                args: self.args.clone(),
+                working_dir: self.working_dir.clone(),
+                env: self.env.clone(),
+                wrappers: self.wrappers.clone(),
+                runner: self.runner.clone(),
+                pre_launch: self.pre_launch.clone(),
+                post_exit: self.post_exit.clone(),
+                ..Default::default()
+            },
+        )
+    }
+}
+
+/// A user-defined group of games, e.g. "To Play" or "Co-op".
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Collection {
+    pub id: String,
+    pub name: String,
+    /// Game keys in insertion order: `custom-<id>`, `steam:<appid>`,
+    /// `steam-shortcut:<id>` or `epic:<app_name>`.
+    pub member_keys: Vec<String>,
+}
+
+/// How [`Library::sorted`] orders games. Ties are broken by title, A to Z.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SortOrder {
+    #[default]
+    TitleAsc,
+    TitleDesc,
+    /// Most recently played first; never-played games last.
+    RecentlyPlayed,
+    /// Newest additions first.
+    RecentlyAdded,
+}
+
+/// Result of [`Library::scan_and_import`].
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct ImportSummary {
+    /// Games that were added, in path order.
+    pub added: Vec<CustomGame>,
+    /// Executables skipped because the library already had them.
+    pub skipped_duplicates: usize,
+    /// The scan stopped at [`crate::fs_explorer::MAX_EXECUTABLE_RESULTS`] executables,
+    /// so games further into the folder weren't looked at.
+    pub truncated: bool,
+}
+
+/// A custom game whose files have gone missing, as reported by [`Library::issues`].
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct LibraryIssue {
+    pub id: String,
+    pub title: String,
+    /// The executable (or, for imported games, the install directory) is gone.
+    pub executable_missing: bool,
+    /// A cover is set but the file no longer exists.
+    pub cover_missing: bool,
+    /// A hero image is set but the file no longer exists.
+    pub hero_missing: bool,
+    /// How many of the game's screenshots no longer exist.
+    pub screenshots_missing: usize,
+}
+
+/// Checks one game's paths with `exists`, returning an issue only if something is missing.
+pub fn check_game(game: &CustomGame, exists: impl Fn(&Path) -> bool) -> Option<LibraryIssue> {
+    let executable_missing = !exists(&game.executable);
+    let cover_missing = game.cover_image.as_deref().is_some_and(|c| !exists(c));
+    let hero_missing = game.hero_image.as_deref().is_some_and(|h| !exists(h));
+    let screenshots_missing = game.screenshots.iter().filter(|s| !exists(s)).count();
+    let broken = executable_missing || cover_missing || hero_missing || screenshots_missing > 0;
+    broken.then(|| LibraryIssue {
+        id: game.id.clone(),
+        title: game.title.clone(),
+        executable_missing,
+        cover_missing,
+        hero_missing,
+        screenshots_missing,
+    })
+}
+
+/// Picks the image in `candidates` whose file name best matches `title`. Both are
+/// compared lowercased with punctuation dropped, and the file name may leave out a
+/// subtitle after `:` or ` - `, so `The Witcher 3.jpg` matches "The Witcher 3: Wild
+/// Hunt". Names more than a few typos away from the title don't match.
+pub fn match_cover_for(title: &str, candidates: &[PathBuf]) -> Option<PathBuf> {
+    let full = normalize_title(title);
+    let main = title
+        .split_once(':')
+        .or_else(|| title.split_once(" - "))
+        .map(|(main, _)| normalize_title(main))
+        .filter(|main| !main.is_empty());
+    let wanted: Vec<&str> = std::iter::once(full.as_str())
+        .chain(main.as_deref())
+        .filter(|t| !t.is_empty())
+        .collect();
+
+    candidates
+        .iter()
+        .filter(|p| has_cover_extension(p))
+        .filter_map(|p| {
+            let name = normalize_title(&p.file_stem()?.to_string_lossy());
+            let distance = wanted
+                .iter()
+                .map(|t| (edit_distance(&name, t), t.chars().count()))
+                .filter(|&(distance, len)| distance <= len / COVER_MATCH_TOLERANCE)
+                .map(|(distance, _)| distance)
+                .min()?;
+            Some((distance, p))
+        })
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, p)| p.clone())
+}
+
+/// (De)serializes ordered `(key, value)` pairs as a JSON object, keeping file order.
+mod env_map {
+    use serde::de::{MapAccess, Visitor};
+    use serde::{Deserializer, Serializer};
+    use std::fmt;
+
+    pub fn serialize<S: Serializer>(env: &[(String, String)], s: S) -> Result<S::Ok, S::Error> {
+        s.collect_map(env.iter().map(|(k, v)| (k, v)))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<Vec<(String, String)>, D::Error> {
+        struct EnvVisitor;
+
+        impl<'de> Visitor<'de> for EnvVisitor {
+            type Value = Vec<(String, String)>;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a map of environment variable names to values")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut env = Vec::new();
+                while let Some(pair) = map.next_entry()? {
+                    env.push(pair);
+                }
+                Ok(env)
+            }
         }
+
+        d.deserialize_map(EnvVisitor)
     }
 }
 
+/// Schema version written by [`Library`]. Files without a version are the legacy bare array.
+const LIBRARY_VERSION: u32 = 1;
+
+/// On-disk envelope: `{ "version": 1, "games": [...] }`.
+#[derive(Serialize, Deserialize)]
+struct LibraryFile<G> {
+    version: u32,
+    games: G,
+}
+
 /// Manages the collection of custom (non-Steam) games, persisted to a JSON file.
 pub struct Library {
     path: PathBuf,
     games: Vec<CustomGame>,
+    /// Position of each game in `games` by id; rebuilt whenever positions shift.
+    index: HashMap<String, usize>,
+    /// Favorite markers for Steam/Epic games, keyed `steam:<appid>` or `epic:<app_name>`.
+    /// Persisted next to the library file since those games aren't stored in it.
+    external_favorites: BTreeSet<String>,
+    /// User collections, persisted in their own file next to the library.
+    collections: Vec<Collection>,
+    /// Copy picked covers into [`Library::covers_dir`]. Not persisted here: the app sets
+    /// it from [`crate::settings::Settings::manage_covers`].
+    manage_covers: bool,
+    /// Nesting depth of open batches; while non-zero, library writes are deferred.
+    batch_depth: usize,
+    /// Whether `games` has changes that haven't been written yet.
+    dirty: bool,
+    /// Format version of a library file written by a newer app. Such a file is kept
+    /// read-only so saving can't drop fields this version doesn't know about.
+    newer_version: Option<u32>,
+    /// Number of library file writes, so tests can check batching.
+    #[cfg(test)]
+    writes: usize,
 }
 
 impl Library {
     /// Loads the library from `path`, creating an empty one if the file doesn't exist.
     pub fn load(path: impl Into<PathBuf>) -> Result<Self, LibraryError> {
         let path = path.into();
-        let games = if path.exists() {
+        let (games, version) = if path.exists() {
             let contents = std::fs::read_to_string(&path)?;
-            let games: Vec<CustomGame> = serde_json::from_str(&contents)?;
-            log::info!("Library loaded: {} game(s) from {:?}", games.len(), path);
-            games
+            match parse_library(&contents) {
+                Ok((games, version)) => {
+                    log::info!("Library loaded: {} game(s) from {:?}", games.len(), path);
+                    (games, version)
+                }
+                Err(e) => Self::recover(&path).ok_or(e)?,
+            }
         } else {
             log::info!("No library file found at {:?}, starting empty", path);
-            Vec::new()
+            (Vec::new(), LIBRARY_VERSION)
         };
-        Ok(Self { path, games })
+        let external_favorites = load_external_favorites(&favorites_path_for(&path));
+        let collections = load_collections(&collections_path_for(&path));
+        Ok(Self {
+            index: index_games(&games),
+            path,
+            games,
+            external_favorites,
+            collections,
+            manage_covers: false,
+            batch_depth: 0,
+            dirty: false,
+            newer_version: (version > LIBRARY_VERSION).then_some(version),
+            #[cfg(test)]
+            writes: 0,
+        })
+    }
+
+    /// Re-reads the library and its sidecar files from disk, e.g. after an external edit.
+    pub fn reload(&mut self) -> Result<(), LibraryError> {
+        let manage_covers = self.manage_covers;
+        *self = Self::load(self.path.clone())?;
+        self.manage_covers = manage_covers;
+        Ok(())
+    }
+
+    /// Like [`Library::reload`], but only when the files on disk hold something other
+    /// than what's in memory; returns whether they did. The library's own saves read
+    /// back unchanged, so they never count as external edits.
+    pub fn reload_if_changed(&mut self) -> Result<bool, LibraryError> {
+        let mut on_disk = Self::load(self.path.clone())?;
+        let unchanged = on_disk.games == self.games
+            && on_disk.external_favorites == self.external_favorites
+            && on_disk.collections == self.collections;
+        if unchanged {
+            return Ok(false);
+        }
+        on_disk.manage_covers = self.manage_covers;
+        *self = on_disk;
+        Ok(true)
     }
 
     pub fn games(&self) -> &[CustomGame] {
         &self.games
     }
 
+    /// Returns the games in `order`, breaking ties by title.
+    pub fn sorted(&self, order: SortOrder) -> Vec<&CustomGame> {
+        let mut games: Vec<&CustomGame> = self.games.iter().collect();
+        let by_title = |a: &CustomGame, b: &CustomGame| {
+            a.title
+                .to_lowercase()
+                .cmp(&b.title.to_lowercase())
+                .then_with(|| a.title.cmp(&b.title))
+        };
+        games.sort_by(|a, b| match order {
+            SortOrder::TitleAsc => by_title(a, b),
+            SortOrder::TitleDesc => by_title(b, a),
+            SortOrder::RecentlyPlayed => b
+                .last_played
+                .cmp(&a.last_played)
+                .then_with(|| by_title(a, b)),
+            SortOrder::RecentlyAdded => b.added_at.cmp(&a.added_at).then_with(|| by_title(a, b)),
+        });
+        games
+    }
+
+    /// Sets whether covers set through the validated add/update paths are copied into
+    /// [`Library::covers_dir`].
+    pub fn set_manage_covers(&mut self, enabled: bool) {
+        if self.manage_covers != enabled {
+            log::info!(
+                "Managed covers {}",
+                if enabled { "enabled" } else { "disabled" }
+            );
+        }
+        self.manage_covers = enabled;
+    }
+
+    /// Directory holding managed cover, hero and screenshot copies: `covers` next to the
+    /// library file.
+    pub fn covers_dir(&self) -> PathBuf {
+        self.path
+            .parent()
+            .map_or_else(|| PathBuf::from("covers"), |dir| dir.join("covers"))
+    }
+
+    /// Adds a game, rejecting it with [`LibraryError::Duplicate`] when another game already
+    /// points at the same executable.
     pub fn add(&mut self, game: CustomGame) -> Result<&CustomGame, LibraryError> {
+        if let Some(existing) = self.find_by_executable(&game.executable) {
+            log::warn!(
+                "Rejecting game {:?}: executable {:?} already used by {:?}",
+                game.title,
+                game.executable,
+                existing.title
+            );
+            return Err(LibraryError::Duplicate(game.executable));
+        }
+        self.add_allowing_duplicate(game)
+    }
+
+    /// Like [`Library::add`], for when the user really wants two entries for one executable
+    /// (e.g. different launch arguments).
+    pub fn add_allowing_duplicate(
+        &mut self,
+        game: CustomGame,
+    ) -> Result<&CustomGame, LibraryError> {
         log::info!("Adding game to library: {:?} (id={})", game.title, game.id);
+        self.index.insert(game.id.clone(), self.games.len());
         self.games.push(game);
         self.persist()?;
         Ok(self.games.last().unwrap())
     }
 
+    /// Like [`Library::add`], but first checks that the game's executable exists on disk
+    /// (a regular file, or on macOS a `.app` bundle directory). `allow_duplicate` skips
+    /// the duplicate-executable check.
+    pub fn add_validated(
+        &mut self,
+        game: CustomGame,
+        allow_duplicate: bool,
+    ) -> Result<&CustomGame, LibraryError> {
+        #[cfg(windows)]
+        let game = resolve_shortcut(game);
+        if !executable_exists(&game.executable) {
+            log::warn!(
+                "Rejecting game {:?}: executable {:?} missing",
+                game.title,
+                game.executable
+            );
+            return Err(LibraryError::ExecutableMissing(game.executable));
+        }
+        let game = CustomGame {
+            cover_image: validate_cover(game.cover_image)?,
+            hero_image: validate_cover(game.hero_image)?,
+            screenshots: validate_images(game.screenshots)?,
+            ..game
+        };
+        let picked: Vec<PathBuf> = image_paths(&game).map(Path::to_path_buf).collect();
+        let game = self.adopt_images(game)?;
+        let copied = new_images(&game, &picked);
+        let result = if allow_duplicate {
+            self.add_allowing_duplicate(game)
+        } else {
+            self.add(game)
+        };
+        if result.is_err() {
+            for copied in copied {
+                std::fs::remove_file(copied).ok();
+            }
+        }
+        result
+    }
+
     pub fn remove(&mut self, id: &str) -> Result<CustomGame, LibraryError> {
-        let index = self
-            .games
-            .iter()
-            .position(|g| g.id == id)
-            .ok_or_else(|| LibraryError::NotFound(id.to_string()))?;
+        let index = self.index_of(id)?;
         let removed = self.games.remove(index);
+        self.index = index_games(&self.games);
         log::info!("Removed game from library: {:?} (id={})", removed.title, removed.id);
         self.persist()?;
+        self.forget_removed(std::slice::from_ref(&removed))?;
+        Ok(removed)
+    }
+
+    /// Removes every game whose id is in `ids`, writing the library once. Unknown ids are
+    /// ignored; the returned games show which ids matched.
+    pub fn remove_many(&mut self, ids: &[String]) -> Result<Vec<CustomGame>, LibraryError> {
+        let wanted: BTreeSet<&str> = ids.iter().map(String::as_str).collect();
+        let (removed, kept): (Vec<CustomGame>, Vec<CustomGame>) = std::mem::take(&mut self.games)
+            .into_iter()
+            .partition(|g| wanted.contains(g.id.as_str()));
+        self.games = kept;
+        self.index = index_games(&self.games);
+        if removed.is_empty() {
+            log::debug!("remove_many: none of {} id(s) matched", ids.len());
+            return Ok(removed);
+        }
+        log::info!(
+            "Removed {} game(s) from library ({} requested)",
+            removed.len(),
+            wanted.len()
+        );
+        self.persist()?;
+        self.forget_removed(&removed)?;
         Ok(removed)
     }
 
     pub fn update(&mut self, updated: CustomGame) -> Result<&CustomGame, LibraryError> {
-        let index = self
+        let index = self.index_of(&updated.id)?;
+        self.games[index] = updated;
+        self.persist()?;
+        Ok(&self.games[index])
+    }
+
+    /// Like [`Library::update`], but first checks the cover, hero and screenshot images
+    /// the same way [`Library::add_validated`] does. A `None` cover or hero clears it.
+    /// Images the game already had are kept without being checked again, so one that
+    /// has gone missing doesn't block unrelated edits.
+    pub fn update_validated(&mut self, updated: CustomGame) -> Result<&CustomGame, LibraryError> {
+        let index = self.index_of(&updated.id)?;
+        let existing = &self.games[index];
+        let updated = CustomGame {
+            cover_image: validate_changed_cover(updated.cover_image, &existing.cover_image)?,
+            hero_image: validate_changed_cover(updated.hero_image, &existing.hero_image)?,
+            screenshots: validate_changed_images(updated.screenshots, &existing.screenshots)?,
+            ..updated
+        };
+        let mut known: Vec<PathBuf> = image_paths(&updated).map(Path::to_path_buf).collect();
+        let updated = self.adopt_images(updated)?;
+        let dropped: Vec<PathBuf> = image_paths(&self.games[index])
+            .filter(|p| !image_paths(&updated).any(|kept| kept == *p))
+            .map(Path::to_path_buf)
+            .collect();
+        known.extend(image_paths(&self.games[index]).map(Path::to_path_buf));
+        let copied = new_images(&updated, &known);
+        if let Err(e) = self.update(updated) {
+            // Nothing saved refers to the copies made for this edit.
+            for copied in copied {
+                std::fs::remove_file(copied).ok();
+            }
+            return Err(e);
+        }
+        // Replaced or cleared managed images are no longer referenced by anything.
+        for image in dropped {
+            self.delete_managed_image(&image);
+        }
+        Ok(&self.games[index])
+    }
+
+    /// Adds a finished session's length to the game's accumulated playtime and marks it
+    /// as played now.
+    pub fn add_playtime(&mut self, id: &str, secs: u64) -> Result<&CustomGame, LibraryError> {
+        let index = self.index_of(id)?;
+        let game = &mut self.games[index];
+        game.playtime_secs = game.playtime_secs.saturating_add(secs);
+        game.last_played = Some(unix_now());
+        log::info!(
+            "Recorded {}s of playtime for {:?} (total {}s)",
+            secs,
+            game.title,
+            game.playtime_secs
+        );
+        self.persist()?;
+        Ok(&self.games[index])
+    }
+
+    pub fn get(&self, id: &str) -> Option<&CustomGame> {
+        self.index.get(id).map(|&i| &self.games[i])
+    }
+
+    /// Walks `dir` up to `max_depth` levels for executables (and `.app` bundles on macOS)
+    /// and adds each one not already in the library, titled after its file name.
+    /// Files that are clearly not games, such as shared libraries, uninstallers, crash
+    /// handlers and redistributable installers, are left out (see [`is_likely_game`]).
+    pub fn scan_and_import(
+        &mut self,
+        dir: &Path,
+        max_depth: usize,
+    ) -> Result<ImportSummary, LibraryError> {
+        let (found, truncated) =
+            crate::fs_explorer::find_executables_matching(dir, max_depth, |entry| {
+                let path = Path::new(&entry.path);
+                is_likely_game(path.strip_prefix(dir).unwrap_or(path))
+            });
+        let games = found
+            .into_iter()
+            .map(|entry| {
+                let path = PathBuf::from(&entry.path);
+                CustomGame::new(title_from_path(&path), path, None, vec![], None)
+            })
+            .collect();
+        let mut summary = self.import_games(games)?;
+        summary.truncated = truncated;
+        log::info!(
+            "Imported {} game(s) from {:?}; skipped {} already in the library",
+            summary.added.len(),
+            dir,
+            summary.skipped_duplicates
+        );
+        if truncated {
+            log::warn!(
+                "Scan of {:?} hit the executable limit; some games may be missing",
+                dir
+            );
+        }
+        Ok(summary)
+    }
+
+    /// Adds each of `games` whose executable isn't already in the library (including
+    /// earlier entries of `games`), writing the library once.
+    pub fn import_games(&mut self, games: Vec<CustomGame>) -> Result<ImportSummary, LibraryError> {
+        let mut summary = ImportSummary {
+            added: Vec::new(),
+            skipped_duplicates: 0,
+            truncated: false,
+        };
+        self.with_batch(|lib| {
+            for game in games {
+                if lib.find_by_executable(&game.executable).is_some() {
+                    summary.skipped_duplicates += 1;
+                    continue;
+                }
+                summary.added.push(lib.add(game)?.clone());
+            }
+            Ok(())
+        })?;
+        Ok(summary)
+    }
+
+    /// Gives each game without a cover the image in `dir` whose file name matches its
+    /// title (see [`match_cover_for`]). Returns the games that got a cover.
+    pub fn auto_assign_covers(&mut self, dir: &Path) -> Result<Vec<CustomGame>, LibraryError> {
+        let mut images: Vec<PathBuf> = std::fs::read_dir(dir)?
+            .flatten()
+            .map(|e| e.path())
+            .filter(|p| p.is_file() && has_cover_extension(p))
+            .collect();
+        images.sort();
+        let uncovered: Vec<CustomGame> = self
             .games
             .iter()
-            .position(|g| g.id == updated.id)
-            .ok_or_else(|| LibraryError::NotFound(updated.id.clone()))?;
-        self.games[index] = updated;
+            .filter(|g| g.cover_image.is_none())
+            .cloned()
+            .collect();
+        let assigned = self.with_batch(|lib| {
+            let mut assigned = Vec::new();
+            for game in uncovered {
+                let Some(cover) = match_cover_for(&game.title, &images) else {
+                    continue;
+                };
+                let game = CustomGame {
+                    cover_image: Some(cover),
+                    ..game
+                };
+                assigned.push(lib.update_validated(game)?.clone());
+            }
+            Ok(assigned)
+        })?;
+        log::info!("Assigned {} cover(s) from {:?}", assigned.len(), dir);
+        Ok(assigned)
+    }
+
+    /// Starts a batch: until the matching [`Library::end_batch`], changes to the games
+    /// stay in memory instead of being written one by one. Batches may nest.
+    pub fn begin_batch(&mut self) {
+        self.batch_depth += 1;
+    }
+
+    /// Ends a batch, writing the library once if the outermost batch changed anything.
+    pub fn end_batch(&mut self) -> Result<(), LibraryError> {
+        self.batch_depth = self.batch_depth.saturating_sub(1);
+        if self.batch_depth > 0 {
+            return Ok(());
+        }
+        self.persist_if_dirty()
+    }
+
+    /// Runs `f` inside a batch. Changes made before an error are still written, and
+    /// `f`'s error takes precedence over a failed write.
+    pub fn with_batch<T>(
+        &mut self,
+        f: impl FnOnce(&mut Self) -> Result<T, LibraryError>,
+    ) -> Result<T, LibraryError> {
+        self.begin_batch();
+        let result = f(self);
+        let written = self.end_batch();
+        let value = result?;
+        written?;
+        Ok(value)
+    }
+
+    /// Writes the library to a sibling `.tmp` file and renames it over the real path,
+    /// so a crash mid-write never leaves a truncated library behind. Does nothing when
+    /// there are no unwritten changes.
+    ///
+    /// A file from a newer app version is never overwritten: the unsaved changes are
+    /// discarded by reloading it and [`LibraryError::NewerVersion`] is returned.
+    pub fn persist_if_dirty(&mut self) -> Result<(), LibraryError> {
+        if !self.dirty {
+            return Ok(());
+        }
+        if let Some(version) = self.newer_version {
+            log::warn!(
+                "Not saving library {:?}: format {} is newer than supported {}",
+                self.path,
+                version,
+                LIBRARY_VERSION
+            );
+            self.reload()?;
+            return Err(LibraryError::NewerVersion(version));
+        }
+        let file = LibraryFile {
+            version: LIBRARY_VERSION,
+            games: &self.games,
+        };
+        let json = serde_json::to_string_pretty(&file)?;
+        write_atomic(&self.path, &json)?;
+        self.dirty = false;
+        #[cfg(test)]
+        {
+            self.writes += 1;
+        }
+        Ok(())
+    }
+
+    /// Returns the game whose executable is the same file as `path`, after normalization.
+    pub fn find_by_executable(&self, path: &Path) -> Option<&CustomGame> {
+        let wanted = executable_key(path);
+        self.games
+            .iter()
+            .find(|g| executable_key(&g.executable) == wanted)
+    }
+
+    /// Returns games whose title equals `title`, ignoring case and surrounding whitespace.
+    /// Unlike executables, shared titles are allowed (e.g. a game and its remaster).
+    pub fn titles_matching(&self, title: &str) -> Vec<&CustomGame> {
+        let wanted = title.trim().to_lowercase();
+        self.games
+            .iter()
+            .filter(|g| g.title.trim().to_lowercase() == wanted)
+            .collect()
+    }
+
+    /// Returns the games whose executable, cover, hero or screenshots no longer exist on
+    /// disk.
+    pub fn issues(&self) -> Vec<LibraryIssue> {
+        self.games
+            .iter()
+            .filter_map(|g| check_game(g, Path::exists))
+            .collect()
+    }
+
+    /// Marks or unmarks a custom game as a favorite.
+    pub fn set_favorite(&mut self, id: &str, favorite: bool) -> Result<&CustomGame, LibraryError> {
+        let index = self.index_of(id)?;
+        self.games[index].favorite = favorite;
+        log::info!(
+            "Set favorite={} for {:?}",
+            favorite,
+            self.games[index].title
+        );
         self.persist()?;
         Ok(&self.games[index])
     }
 
-    pub fn get(&self, id: &str) -> Option<&CustomGame> {
-        self.games.iter().find(|g| g.id == id)
+    /// Returns every custom game with favorites pinned first, otherwise in library order.
+    pub fn favorites(&self) -> Vec<&CustomGame> {
+        let mut games: Vec<&CustomGame> = self.games.iter().collect();
+        games.sort_by_key(|g| !g.favorite);
+        games
+    }
+
+    /// Marks or unmarks a Steam/Epic game (by `steam:<appid>` / `epic:<app_name>` key).
+    pub fn set_external_favorite(&mut self, key: &str, favorite: bool) -> Result<(), LibraryError> {
+        let changed = if favorite {
+            self.external_favorites.insert(key.to_string())
+        } else {
+            self.external_favorites.remove(key)
+        };
+        if changed {
+            log::info!("Set favorite={} for external game {:?}", favorite, key);
+            let json = serde_json::to_string_pretty(&self.external_favorites)?;
+            write_atomic(&favorites_path_for(&self.path), &json)?;
+        }
+        Ok(())
+    }
+
+    pub fn is_external_favorite(&self, key: &str) -> bool {
+        self.external_favorites.contains(key)
+    }
+
+    pub fn external_favorites(&self) -> &BTreeSet<String> {
+        &self.external_favorites
+    }
+
+    /// Returns the games carrying `tag`, compared case-insensitively.
+    pub fn games_with_tag(&self, tag: &str) -> Vec<&CustomGame> {
+        let tag = tag.trim().to_lowercase();
+        self.games
+            .iter()
+            .filter(|g| g.tags.iter().any(|t| t.trim().to_lowercase() == tag))
+            .collect()
+    }
+
+    /// Returns every tag used in the library, deduplicated case-insensitively (the first
+    /// spelling seen wins) and sorted alphabetically ignoring case.
+    pub fn all_tags(&self) -> Vec<String> {
+        let mut seen = std::collections::HashSet::new();
+        let mut tags: Vec<String> = self
+            .games
+            .iter()
+            .flat_map(|g| &g.tags)
+            .map(|t| t.trim())
+            .filter(|t| !t.is_empty() && seen.insert(t.to_lowercase()))
+            .map(str::to_string)
+            .collect();
+        tags.sort_by_key(|t| t.to_lowercase());
+        tags
+    }
+
+    pub fn collections(&self) -> &[Collection] {
+        &self.collections
+    }
+
+    /// Creates an empty collection named `name`.
+    pub fn create_collection(
+        &mut self,
+        name: impl Into<String>,
+    ) -> Result<&Collection, LibraryError> {
+        let collection = Collection {
+            id: Uuid::new_v4().to_string(),
+            name: name.into(),
+            member_keys: Vec::new(),
+        };
+        log::info!(
+            "Creating collection {:?} (id={})",
+            collection.name,
+            collection.id
+        );
+        self.collections.push(collection);
+        self.persist_collections()?;
+        Ok(self.collections.last().unwrap())
+    }
+
+    /// Adds a game key to a collection; adding a key that's already a member is a no-op.
+    pub fn add_to_collection(
+        &mut self,
+        collection_id: &str,
+        key: &str,
+    ) -> Result<&Collection, LibraryError> {
+        let index = self.collection_index(collection_id)?;
+        if !self.collections[index].member_keys.iter().any(|k| k == key) {
+            self.collections[index].member_keys.push(key.to_string());
+            self.persist_collections()?;
+        }
+        Ok(&self.collections[index])
+    }
+
+    /// Removes a game key from a collection; removing a non-member is a no-op.
+    pub fn remove_from_collection(
+        &mut self,
+        collection_id: &str,
+        key: &str,
+    ) -> Result<&Collection, LibraryError> {
+        let index = self.collection_index(collection_id)?;
+        let members = &mut self.collections[index].member_keys;
+        let before = members.len();
+        members.retain(|k| k != key);
+        if members.len() != before {
+            self.persist_collections()?;
+        }
+        Ok(&self.collections[index])
+    }
+
+    fn collection_index(&self, id: &str) -> Result<usize, LibraryError> {
+        self.collections
+            .iter()
+            .position(|c| c.id == id)
+            .ok_or_else(|| LibraryError::CollectionNotFound(id.to_string()))
+    }
+
+    fn persist_collections(&self) -> Result<(), LibraryError> {
+        let json = serde_json::to_string_pretty(&self.collections)?;
+        write_atomic(&collections_path_for(&self.path), &json)?;
+        Ok(())
+    }
+
+    /// Cleans up after removed games: deletes their managed covers and drops them from
+    /// every collection they belonged to.
+    fn forget_removed(&mut self, removed: &[CustomGame]) -> Result<(), LibraryError> {
+        for image in removed.iter().flat_map(image_paths) {
+            self.delete_managed_image(image);
+        }
+        let keys: BTreeSet<String> = removed.iter().map(|g| format!("custom-{}", g.id)).collect();
+        let mut changed = false;
+        for collection in &mut self.collections {
+            let before = collection.member_keys.len();
+            collection.member_keys.retain(|k| !keys.contains(k));
+            changed |= collection.member_keys.len() != before;
+        }
+        if changed {
+            self.persist_collections()?;
+        }
+        Ok(())
+    }
+
+    /// Position of the game with `id` in `games`.
+    fn index_of(&self, id: &str) -> Result<usize, LibraryError> {
+        self.index
+            .get(id)
+            .copied()
+            .ok_or_else(|| LibraryError::NotFound(id.to_string()))
+    }
+
+    /// With managed covers on, copies the game's cover, hero and screenshots into
+    /// [`Library::covers_dir`] and points the game at the copies, so deleting the
+    /// originals doesn't break its art. The copies are named `<id>.<ext>`,
+    /// `<id>-hero.<ext>` and `<id>-<uuid>.<ext>`. A kept image that has since gone
+    /// missing is left for [`Library::issues`] to report.
+    fn adopt_images(&self, mut game: CustomGame) -> Result<CustomGame, LibraryError> {
+        if !self.manage_covers {
+            return Ok(game);
+        }
+        if let Some(cover) = game.cover_image.take() {
+            game.cover_image = Some(self.adopt_image(cover, &game.id)?);
+        }
+        if let Some(hero) = game.hero_image.take() {
+            game.hero_image = Some(self.adopt_image(hero, &format!("{}-hero", game.id))?);
+        }
+        game.screenshots = std::mem::take(&mut game.screenshots)
+            .into_iter()
+            .map(|shot| self.adopt_image(shot, &format!("{}-{}", game.id, Uuid::new_v4())))
+            .collect::<Result<_, _>>()?;
+        Ok(game)
+    }
+
+    /// Copies `source` to `<covers_dir>/<name>.<ext>` and returns the copy. An image that
+    /// is already managed, or isn't a file, is returned as is.
+    fn adopt_image(&self, source: PathBuf, name: &str) -> Result<PathBuf, LibraryError> {
+        if self.is_managed_image(&source) || !source.is_file() {
+            return Ok(source);
+        }
+        let ext = source
+            .extension()
+            .map(|e| e.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        let dir = self.covers_dir();
+        std::fs::create_dir_all(&dir)?;
+        let target = std::fs::canonicalize(&dir)?.join(format!("{}.{}", name, ext));
+        std::fs::copy(&source, &target)?;
+        log::info!("Copied image {:?} to {:?}", source, target);
+        Ok(target)
+    }
+
+    /// Whether `image` is a file inside [`Library::covers_dir`].
+    fn is_managed_image(&self, image: &Path) -> bool {
+        std::fs::canonicalize(self.covers_dir())
+            .is_ok_and(|dir| image.parent() == Some(dir.as_path()))
+    }
+
+    /// Deletes `image` if it is a managed copy; images elsewhere belong to the user.
+    fn delete_managed_image(&self, image: &Path) {
+        if !self.is_managed_image(image) {
+            return;
+        }
+        match std::fs::remove_file(image) {
+            Ok(()) => log::info!("Deleted managed image {:?}", image),
+            Err(e) => log::warn!("Failed to delete managed image {:?}: {}", image, e),
+        }
+    }
+
+    /// Marks the games as changed and writes them, unless a batch is open; the batch
+    /// then writes once when it ends.
+    fn persist(&mut self) -> Result<(), LibraryError> {
+        self.dirty = true;
+        if self.batch_depth > 0 {
+            return Ok(());
+        }
+        self.persist_if_dirty()
+    }
+
+    /// Attempts to read the games from a leftover `.tmp` file when the main file is corrupt.
+    fn recover(path: &Path) -> Option<(Vec<CustomGame>, u32)> {
+        let tmp = temp_path_for(path);
+        let contents = std::fs::read_to_string(&tmp).ok()?;
+        let (games, version) = parse_library(&contents).ok()?;
+        log::warn!(
+            "Library file {:?} is corrupt; recovered {} game(s) from {:?}",
+            path,
+            games.len(),
+            tmp
+        );
+        Some((games, version))
+    }
+}
+
+/// The game's cover, hero and screenshot paths, in that order.
+fn image_paths(game: &CustomGame) -> impl Iterator<Item = &Path> {
+    game.cover_image
+        .as_deref()
+        .into_iter()
+        .chain(game.hero_image.as_deref())
+        .chain(game.screenshots.iter().map(PathBuf::as_path))
+}
+
+/// The images of `game` that aren't in `known`: after [`Library::adopt_images`], with
+/// `known` holding the paths it was given, the copies it just made.
+fn new_images(game: &CustomGame, known: &[PathBuf]) -> Vec<PathBuf> {
+    image_paths(game)
+        .filter(|p| !known.iter().any(|k| k == p))
+        .map(Path::to_path_buf)
+        .collect()
+}
+
+/// Maps each game's id to its position in `games`.
+fn index_games(games: &[CustomGame]) -> HashMap<String, usize> {
+    games
+        .iter()
+        .enumerate()
+        .map(|(i, g)| (g.id.clone(), i))
+        .collect()
+}
+
+/// Parses a library file in either the versioned envelope or the legacy bare-array
+/// format, returning the games and the file's version. Legacy files are upgraded in
+/// memory and rewritten as versioned on next save.
+fn parse_library(contents: &str) -> Result<(Vec<CustomGame>, u32), serde_json::Error> {
+    let value: serde_json::Value = serde_json::from_str(contents)?;
+    if value.is_array() {
+        log::info!(
+            "Migrating legacy library file to version {}",
+            LIBRARY_VERSION
+        );
+        return Ok((serde_json::from_value(value)?, LIBRARY_VERSION));
+    }
+    let file: LibraryFile<Vec<CustomGame>> = serde_json::from_value(value)?;
+    if file.version > LIBRARY_VERSION {
+        log::warn!(
+            "Library file version {} is newer than supported version {}; opening it read-only",
+            file.version,
+            LIBRARY_VERSION
+        );
+    }
+    Ok((file.games, file.version))
+}
+
+/// Returns whether `path` is something we can launch: a file, or a macOS `.app` bundle.
+fn executable_exists(path: &Path) -> bool {
+    if path.is_file() {
+        return true;
+    }
+    cfg!(target_os = "macos")
+        && path.is_dir()
+        && path.extension().and_then(|e| e.to_str()) == Some("app")
+}
+
+/// Image types accepted as covers, both custom ones and those discovered for store
+/// games; matched case-insensitively.
+pub(crate) const COVER_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "webp", "avif"];
+
+/// [`match_cover_for`] allows one edit per this many characters of the title.
+const COVER_MATCH_TOLERANCE: usize = 5;
+
+pub(crate) fn has_cover_extension(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|ext| COVER_EXTENSIONS.iter().any(|c| ext.eq_ignore_ascii_case(c)))
+}
+
+/// Lowercases `title` and turns runs of punctuation and whitespace into single spaces.
+fn normalize_title(title: &str) -> String {
+    title
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Levenshtein distance between `a` and `b`, counted in characters.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, &cb) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(ca != cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+    row[b.len()]
+}
+
+/// Checks that a cover is an existing [`COVER_EXTENSIONS`] file and returns its canonical,
+/// absolute path, so a relative path can't break once the working directory changes.
+fn validate_cover(cover: Option<PathBuf>) -> Result<Option<PathBuf>, LibraryError> {
+    cover.map(validate_image).transpose()
+}
+
+/// Validates each of `images` like [`validate_cover`], failing on the first bad one.
+fn validate_images(images: Vec<PathBuf>) -> Result<Vec<PathBuf>, LibraryError> {
+    images.into_iter().map(validate_image).collect()
+}
+
+/// Like [`validate_cover`], but a cover equal to `stored` is kept unchecked.
+fn validate_changed_cover(
+    cover: Option<PathBuf>,
+    stored: &Option<PathBuf>,
+) -> Result<Option<PathBuf>, LibraryError> {
+    if cover == *stored {
+        return Ok(cover);
+    }
+    validate_cover(cover)
+}
+
+/// Like [`validate_images`], but images already in `stored` are kept unchecked.
+fn validate_changed_images(
+    images: Vec<PathBuf>,
+    stored: &[PathBuf],
+) -> Result<Vec<PathBuf>, LibraryError> {
+    images
+        .into_iter()
+        .map(|image| {
+            if stored.contains(&image) {
+                Ok(image)
+            } else {
+                validate_image(image)
+            }
+        })
+        .collect()
+}
+
+fn validate_image(image: PathBuf) -> Result<PathBuf, LibraryError> {
+    if !has_cover_extension(&image) || !image.is_file() {
+        log::warn!("Rejecting image {:?}", image);
+        return Err(LibraryError::InvalidCover(image));
+    }
+    let canonical = std::fs::canonicalize(&image)?;
+    // Keep plain drive paths free of the `\\?\` prefix so the webview can load them.
+    #[cfg(windows)]
+    if let Some(plain) = canonical
+        .to_str()
+        .and_then(|p| p.strip_prefix(r"\\?\"))
+        .filter(|p| !p.starts_with("UNC\\"))
+    {
+        return Ok(PathBuf::from(plain));
+    }
+    Ok(canonical)
+}
+
+/// Points a game added through a `.lnk` shortcut at the shortcut's target, taking over
+/// its arguments and working directory unless the game sets its own.
+#[cfg(windows)]
+fn resolve_shortcut(mut game: CustomGame) -> CustomGame {
+    if !crate::shell_link::is_shell_link(&game.executable) {
+        return game;
+    }
+    match crate::shell_link::resolve(&game.executable) {
+        Ok(link) => {
+            game.executable = link.target;
+            if game.args.is_empty() {
+                game.args = link.args;
+            }
+            if game.working_dir.is_none() {
+                game.working_dir = link.working_dir;
+            }
+        }
+        Err(e) => log::warn!("Could not resolve shortcut {:?}: {}", game.executable, e),
+    }
+    game
+}
+
+/// Name fragments of executables that ship alongside games but aren't games themselves.
+const NON_GAME_NAMES: &[&str] = &[
+    "uninstall",
+    "unins0",
+    "crashhandler",
+    "crash_handler",
+    "crashreport",
+    "crashpad",
+    "vcredist",
+    "vc_redist",
+    "dxsetup",
+    "dxwebsetup",
+    "dotnetfx",
+    "prereqsetup",
+];
+
+/// Whether a scanned executable, given relative to the scanned folder, looks like a
+/// game: not a shared library, not a known helper (see [`NON_GAME_NAMES`]), and not
+/// inside a redistributables or installer folder such as `_CommonRedist`.
+fn is_likely_game(relative: &Path) -> bool {
+    let name = relative
+        .file_name()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .to_lowercase();
+    let is_library = name.contains(".so.")
+        || [".so", ".dll", ".dylib"]
+            .iter()
+            .any(|ext| name.ends_with(ext));
+    let in_helper_dir = relative.parent().is_some_and(|parent| {
+        parent.components().any(|c| {
+            let dir = c.as_os_str().to_string_lossy().to_lowercase();
+            dir.contains("redist") || dir == "__installer" || dir == "installers"
+        })
+    });
+    !is_library && !in_helper_dir && !NON_GAME_NAMES.iter().any(|n| name.contains(n))
+}
+
+/// Derives a display title from an executable or bundle path: the file stem with
+/// underscores and dashes turned into spaces, e.g. `Hollow_Knight.exe` → `Hollow Knight`.
+fn title_from_path(path: &Path) -> String {
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let title = stem
+        .split(['_', '-'])
+        .filter(|w| !w.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ");
+    if title.is_empty() {
+        stem.to_string()
+    } else {
+        title
+    }
+}
+
+/// Comparison key for executable paths: canonical when the file exists, otherwise
+/// lexically normalized; case-insensitive on Windows.
+fn executable_key(path: &Path) -> String {
+    let key = match std::fs::canonicalize(path) {
+        Ok(canonical) => canonical.to_string_lossy().to_string(),
+        Err(_) => crate::fs_explorer::normalize_path(&path.to_string_lossy()),
+    };
+    if cfg!(windows) {
+        key.to_lowercase()
+    } else {
+        key
+    }
+}
+
+/// Writes `contents` to a sibling `.tmp` file, then renames it over `path`, so a crash
+/// mid-write never leaves a truncated file behind.
+pub(crate) fn write_atomic(path: &Path, contents: &str) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let tmp = temp_path_for(path);
+    std::fs::write(&tmp, contents)?;
+    std::fs::rename(&tmp, path)?;
+    Ok(())
+}
+
+/// Returns the external favorites file next to the library, e.g. `custom_games.favorites.json`.
+fn favorites_path_for(path: &Path) -> PathBuf {
+    path.with_extension("favorites.json")
+}
+
+/// Reads the external favorites set; a missing or unreadable file yields an empty set.
+fn load_external_favorites(path: &Path) -> BTreeSet<String> {
+    let Ok(contents) = std::fs::read_to_string(path) else {
+        return BTreeSet::new();
+    };
+    serde_json::from_str::<BTreeSet<String>>(&contents)
+        .map(|keys| keys.into_iter().map(migrate_legacy_key).collect())
+        .unwrap_or_else(|e| {
+            log::warn!("Ignoring corrupt favorites file {:?}: {}", path, e);
+            BTreeSet::new()
+        })
+}
+
+/// Returns the collections file next to the library, e.g. `custom_games.collections.json`.
+fn collections_path_for(path: &Path) -> PathBuf {
+    path.with_extension("collections.json")
+}
+
+/// Reads the saved collections; a missing or unreadable file yields no collections.
+fn load_collections(path: &Path) -> Vec<Collection> {
+    let Ok(contents) = std::fs::read_to_string(path) else {
+        return Vec::new();
+    };
+    let mut collections: Vec<Collection> = serde_json::from_str(&contents).unwrap_or_else(|e| {
+        log::warn!("Ignoring corrupt collections file {:?}: {}", path, e);
+        Vec::new()
+    });
+    for collection in &mut collections {
+        let mut members: Vec<String> = Vec::new();
+        for key in collection.member_keys.drain(..).map(migrate_legacy_key) {
+            if !members.contains(&key) {
+                members.push(key);
+            }
+        }
+        collection.member_keys = members;
+    }
+    collections
+}
+
+/// Steam shortcuts used to share the `steam:<id>` key with store games. Steam sets the
+/// top bit of every shortcut's app id, which no store app id has, so such keys are
+/// rewritten to the `steam-shortcut:<id>` form the shortcut is listed under now.
+fn migrate_legacy_key(key: String) -> String {
+    let app_id = key
+        .strip_prefix("steam:")
+        .and_then(|id| id.parse::<u32>().ok());
+    match app_id {
+        Some(app_id) if app_id & 0x8000_0000 != 0 => format!("steam-shortcut:{}", app_id),
+        _ => key,
+    }
+}
+
+/// Seconds since the Unix epoch, or `0` if the clock is before it.
+fn unix_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+/// Returns the temp file used for atomic writes, e.g. `custom_games.json.tmp`.
+fn temp_path_for(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".tmp");
+    path.with_file_name(name)
+}
+
+// ============================================================
+// Tests
+// ============================================================
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn make_game(title: &str, exe: &str) -> CustomGame {
+        CustomGame::new(title, exe, None, vec![], None)
+    }
+
+    fn temp_path() -> PathBuf {
+        let mut p = std::env::temp_dir();
+        p.push(format!("game_library_test_{}.json", Uuid::new_v4()));
+        p
+    }
+
+    // --- CustomGame construction ---
+
+    #[test]
+    fn new_game_has_unique_id() {
+        let a = make_game("Game A", "/path/a");
+        let b = make_game("Game B", "/path/b");
+        assert_ne!(a.id, b.id);
+    }
+
+    #[test]
+    fn new_game_stores_fields() {
+        let game = CustomGame::new(
+            "Hollow Knight",
+            "/games/hollow_knight",
+            Some(PathBuf::from("/images/hk.png")),
+            vec!["metroidvania".to_string()],
+            Some("great game".to_string()),
+        );
+        assert_eq!(game.title, "Hollow Knight");
+        assert_eq!(game.executable, PathBuf::from("/games/hollow_knight"));
+        assert_eq!(game.cover_image, Some(PathBuf::from("/images/hk.png")));
+        assert_eq!(game.tags, vec!["metroidvania"]);
+        assert_eq!(game.notes, Some("great game".to_string()));
+    }
+
+    // --- Library load ---
+
+    #[test]
+    fn load_nonexistent_file_returns_empty_library() {
+        let lib = Library::load("/tmp/definitely_does_not_exist_abc123.json").unwrap();
+        assert!(lib.games().is_empty());
+    }
+
+    #[test]
+    fn load_existing_file_deserializes_games() {
+        let path = temp_path();
+        let games = vec![make_game("Game A", "/a"), make_game("Game B", "/b")];
+        std::fs::write(&path, serde_json::to_string(&games).unwrap()).unwrap();
+
+        let lib = Library::load(&path).unwrap();
+        assert_eq!(lib.games().len(), 2);
+        assert_eq!(lib.games()[0].title, "Game A");
+
+        std::fs::remove_file(path).ok();
+    }
+
+    #[test]
+    fn legacy_and_versioned_files_load_the_same_games() {
+        let games = vec![make_game("Game A", "/a"), make_game("Game B", "/b")];
+        let legacy = temp_path();
+        std::fs::write(&legacy, serde_json::to_string(&games).unwrap()).unwrap();
+        let versioned = temp_path();
+        let envelope = serde_json::json!({ "version": 1, "games": games });
+        std::fs::write(&versioned, envelope.to_string()).unwrap();
+
+        let from_legacy = Library::load(&legacy).unwrap();
+        let from_versioned = Library::load(&versioned).unwrap();
+        assert_eq!(from_legacy.games(), games.as_slice());
+        assert_eq!(from_versioned.games(), games.as_slice());
+
+        std::fs::remove_file(legacy).ok();
+        std::fs::remove_file(versioned).ok();
+    }
+
+    #[test]
+    fn save_upgrades_legacy_file_to_versioned_envelope() {
+        let path = temp_path();
+        let games = vec![make_game("Old", "/old")];
+        std::fs::write(&path, serde_json::to_string(&games).unwrap()).unwrap();
+
+        let mut lib = Library::load(&path).unwrap();
+        lib.add(make_game("New", "/new")).unwrap();
+
+        let saved: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(saved["version"], LIBRARY_VERSION);
+        assert_eq!(saved["games"].as_array().unwrap().len(), 2);
+        assert_eq!(saved["games"][0]["title"], "Old");
+
+        std::fs::remove_file(path).ok();
+    }
+
+    #[test]
+    fn newer_version_file_is_never_overwritten() {
+        let path = temp_path();
+        let games = vec![make_game("Future", "/future")];
+        let envelope = serde_json::json!({ "version": 99, "games": games, "shelves": [] });
+        std::fs::write(&path, envelope.to_string()).unwrap();
+
+        let mut lib = Library::load(&path).unwrap();
+        assert_eq!(lib.games(), games.as_slice());
+        assert!(matches!(
+            lib.add(make_game("New", "/new")),
+            Err(LibraryError::NewerVersion(99))
+        ));
+
+        assert_eq!(lib.games(), games.as_slice());
+        let on_disk = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(on_disk, envelope.to_string());
+
+        std::fs::remove_file(path).ok();
+    }
+
+    #[test]
+    fn reload_picks_up_external_edits() {
+        let path = temp_path();
+        let mut lib = Library::load(&path).unwrap();
+        lib.add(make_game("Mine", "/m")).unwrap();
+
+        let mut other = Library::load(&path).unwrap();
+        other.add(make_game("Theirs", "/t")).unwrap();
+
+        lib.reload().unwrap();
+        assert_eq!(lib.games().len(), 2);
+        std::fs::remove_file(path).ok();
+    }
+
+    #[test]
+    fn own_writes_are_not_external_changes() {
+        let path = temp_path();
+        let mut lib = Library::load(&path).unwrap();
+        lib.add(make_game("Mine", "/m")).unwrap();
+        lib.set_external_favorite("steam:620", true).unwrap();
+        assert!(!lib.reload_if_changed().unwrap());
+
+        let mut other = Library::load(&path).unwrap();
+        other.add(make_game("Theirs", "/t")).unwrap();
+        assert!(lib.reload_if_changed().unwrap());
+        assert_eq!(lib.games().len(), 2);
+        assert!(!lib.reload_if_changed().unwrap());
+
+        std::fs::remove_file(path).ok();
+    }
+
+    // --- add ---
+
+    #[test]
+    fn add_game_persists_to_disk() {
+        let path = temp_path();
+        let mut lib = Library::load(&path).unwrap();
+
+        lib.add(make_game("Celeste", "/games/celeste")).unwrap();
+
+        let lib2 = Library::load(&path).unwrap();
+        assert_eq!(lib2.games().len(), 1);
+        assert_eq!(lib2.games()[0].title, "Celeste");
+
+        std::fs::remove_file(path).ok();
+    }
+
+    #[test]
+    fn adding_same_executable_twice_is_rejected() {
+        let path = temp_path();
+        let mut lib = Library::load(&path).unwrap();
+        lib.add(make_game("Celeste", "/games/celeste")).unwrap();
+
+        let result = lib.add(make_game("Celeste again", "/games/./celeste"));
+        assert!(matches!(result, Err(LibraryError::Duplicate(_))));
+        assert_eq!(Library::load(&path).unwrap().games().len(), 1);
+
+        std::fs::remove_file(path).ok();
+    }
+
+    #[test]
+    fn duplicate_detection_follows_canonical_paths() {
+        let dir = std::env::temp_dir().join(format!("dup_test_{}", Uuid::new_v4()));
+        std::fs::create_dir_all(dir.join("bin")).unwrap();
+        std::fs::write(dir.join("bin/game"), "").unwrap();
+        let path = temp_path();
+        let mut lib = Library::load(&path).unwrap();
+        lib.add(make_game("Game", dir.join("bin/game").to_str().unwrap()))
+            .unwrap();
+
+        let roundabout = dir.join("bin/../bin/game");
+        assert!(lib.find_by_executable(&roundabout).is_some());
+        assert!(matches!(
+            lib.add(make_game("Game 2", roundabout.to_str().unwrap())),
+            Err(LibraryError::Duplicate(_))
+        ));
+
+        std::fs::remove_file(path).ok();
+        std::fs::remove_dir_all(dir).ok();
+    }
+
+    #[test]
+    fn allow_duplicate_override_adds_second_entry() {
+        let path = temp_path();
+        let mut lib = Library::load(&path).unwrap();
+        lib.add(make_game("Doom", "/games/doom")).unwrap();
+        lib.add_allowing_duplicate(make_game("Doom (modded)", "/games/doom"))
+            .unwrap();
+        assert_eq!(lib.games().len(), 2);
+        std::fs::remove_file(path).ok();
+    }
+
+    // --- scan_and_import ---
+
+    #[cfg(unix)]
+    fn write_executable(path: &Path) {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::write(path, "#!/bin/sh\n").unwrap();
+        std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o755)).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn scan_and_import_adds_new_executables_only() {
+        let dir = std::env::temp_dir().join(format!("scan_test_{}", Uuid::new_v4()));
+        std::fs::create_dir_all(dir.join("Hollow Knight")).unwrap();
+        std::fs::create_dir_all(dir.join("celeste")).unwrap();
+        write_executable(&dir.join("Hollow Knight/Hollow_Knight.x86_64"));
+        write_executable(&dir.join("celeste/Celeste"));
+        write_executable(&dir.join("super-meat-boy"));
+        std::fs::write(dir.join("celeste/readme.txt"), "").unwrap();
+        std::fs::write(dir.join("celeste/Content.pak"), "").unwrap();
+
+        let path = temp_path();
+        let mut lib = Library::load(&path).unwrap();
+        lib.add(make_game(
+            "Celeste",
+            dir.join("celeste/Celeste").to_str().unwrap(),
+        ))
+        .unwrap();
+
+        let summary = lib.scan_and_import(&dir, 4).unwrap();
+        let titles: Vec<_> = summary.added.iter().map(|g| g.title.as_str()).collect();
+        assert_eq!(titles, ["Hollow Knight", "super meat boy"]);
+        assert_eq!(summary.skipped_duplicates, 1);
+        assert!(!summary.truncated);
+        assert_eq!(lib.games().len(), 3);
+
+        let again = lib.scan_and_import(&dir, 4).unwrap();
+        assert!(again.added.is_empty());
+        assert_eq!(again.skipped_duplicates, 3);
+
+        std::fs::remove_file(path).ok();
+        std::fs::remove_dir_all(dir).ok();
+    }
+
+    #[test]
+    fn import_games_skips_known_and_repeated_executables() {
+        let path = temp_path();
+        let mut lib = Library::load(&path).unwrap();
+        lib.add(make_game("Doom", "/games/doom/gzdoom")).unwrap();
+        let writes = lib.writes;
+
+        let summary = lib
+            .import_games(vec![
+                make_game("Doom (Playnite)", "/games/doom/gzdoom"),
+                make_game("Celeste", "/games/celeste/Celeste"),
+                make_game("Celeste again", "/games/celeste/Celeste"),
+            ])
+            .unwrap();
+        let titles: Vec<_> = summary.added.iter().map(|g| g.title.as_str()).collect();
+        assert_eq!(titles, ["Celeste"]);
+        assert_eq!(summary.skipped_duplicates, 2);
+        assert_eq!(lib.writes, writes + 1, "the import is written once");
+
+        std::fs::remove_file(path).ok();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn scan_and_import_skips_libraries_and_helpers() {
+        let dir = std::env::temp_dir().join(format!("scan_test_{}", Uuid::new_v4()));
+        std::fs::create_dir_all(dir.join("Game/_CommonRedist/vcredist")).unwrap();
+        write_executable(&dir.join("Game/Game.x86_64"));
+        write_executable(&dir.join("Game/UnityPlayer.so"));
+        write_executable(&dir.join("Game/libsteam_api.so.1"));
+        write_executable(&dir.join("Game/UnityCrashHandler64"));
+        write_executable(&dir.join("Game/uninstall"));
+        write_executable(&dir.join("Game/_CommonRedist/vcredist/setup"));
+
+        let path = temp_path();
+        let mut lib = Library::load(&path).unwrap();
+        let summary = lib.scan_and_import(&dir, 4).unwrap();
+        let titles: Vec<_> = summary.added.iter().map(|g| g.title.as_str()).collect();
+        assert_eq!(titles, ["Game"]);
+
+        std::fs::remove_file(path).ok();
+        std::fs::remove_dir_all(dir).ok();
+    }
+
+    #[test]
+    fn likely_games_exclude_libraries_and_helpers() {
+        assert!(is_likely_game(Path::new("Celeste/Celeste.exe")));
+        assert!(is_likely_game(Path::new("Hades/start.sh")));
+        assert!(!is_likely_game(Path::new("Game/steam_api64.dll")));
+        assert!(!is_likely_game(Path::new("Game/lib/libfmod.so.13")));
+        assert!(!is_likely_game(Path::new("Game/unins000.exe")));
+        assert!(!is_likely_game(Path::new("Game/UnityCrashHandler64.exe")));
+        assert!(!is_likely_game(Path::new("_CommonRedist/vc/setup.exe")));
+        assert!(!is_likely_game(Path::new("Game/__Installer/setup.exe")));
+    }
+
+    #[test]
+    fn title_from_path_uses_stem_and_spaces() {
+        assert_eq!(
+            title_from_path(Path::new("/g/Hollow_Knight.exe")),
+            "Hollow Knight"
+        );
+        assert_eq!(title_from_path(Path::new("/g/Portal.app")), "Portal");
+        assert_eq!(title_from_path(Path::new("/g/__")), "__");
+    }
+
+    // --- notes_format ---
+
+    #[test]
+    fn notes_format_defaults_to_plain_for_old_entries() {
+        let json = r#"{"id":"1","title":"Old","executable":"/o","cover_image":null,"tags":[],"notes":"**hi**"}"#;
+        let game: CustomGame = serde_json::from_str(json).unwrap();
+        assert_eq!(game.notes_format, NotesFormat::Plain);
+        assert_eq!(make_game("New", "/n").notes_format, NotesFormat::Plain);
+    }
+
+    #[test]
+    fn notes_format_round_trips() {
+        for format in [NotesFormat::Plain, NotesFormat::Markdown] {
+            let mut game = make_game("Notes", "/n");
+            game.notes_format = format;
+            let json = serde_json::to_string(&game).unwrap();
+            let back: CustomGame = serde_json::from_str(&json).unwrap();
+            assert_eq!(back.notes_format, format);
+        }
+        let json = serde_json::to_value(NotesFormat::Markdown).unwrap();
+        assert_eq!(json, "markdown");
+    }
+
+    // --- titles_matching ---
+
+    #[test]
+    fn titles_matching_ignores_case() {
+        let path = temp_path();
+        let mut lib = Library::load(&path).unwrap();
+        lib.add(make_game("Half-Life", "/games/hl")).unwrap();
+        lib.add(make_game("Portal", "/games/portal")).unwrap();
+
+        let hits = lib.titles_matching("  half-LIFE ");
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].executable, PathBuf::from("/games/hl"));
+        assert!(lib.titles_matching("Half-Life 2").is_empty());
+
+        lib.add(make_game("HALF-LIFE", "/games/hl-source")).unwrap();
+        assert_eq!(lib.titles_matching("Half-Life").len(), 2);
+        std::fs::remove_file(path).ok();
+    }
+
+    // --- sorting ---
+
+    fn sort_fixture() -> (PathBuf, Library) {
+        let path = temp_path();
+        let mut lib = Library::load(&path).unwrap();
+        for (title, added_at, last_played) in [
+            ("celeste", 300, Some(20)),
+            ("Braid", 100, None),
+            ("Axiom Verge", 200, Some(10)),
+            ("Dead Cells", 300, Some(20)),
+        ] {
+            let mut game = make_game(title, &format!("/games/{title}"));
+            game.added_at = added_at;
+            game.last_played = last_played;
+            lib.add(game).unwrap();
+        }
+        (path, lib)
+    }
+
+    fn titles(games: Vec<&CustomGame>) -> Vec<&str> {
+        games.into_iter().map(|g| g.title.as_str()).collect()
+    }
+
+    #[test]
+    fn sorted_by_title_ignores_case() {
+        let (path, lib) = sort_fixture();
+        assert_eq!(
+            titles(lib.sorted(SortOrder::TitleAsc)),
+            ["Axiom Verge", "Braid", "celeste", "Dead Cells"]
+        );
+        assert_eq!(
+            titles(lib.sorted(SortOrder::TitleDesc)),
+            ["Dead Cells", "celeste", "Braid", "Axiom Verge"]
+        );
+        std::fs::remove_file(path).ok();
+    }
+
+    #[test]
+    fn sorted_by_recency_breaks_ties_by_title() {
+        let (path, lib) = sort_fixture();
+        assert_eq!(
+            titles(lib.sorted(SortOrder::RecentlyPlayed)),
+            ["celeste", "Dead Cells", "Axiom Verge", "Braid"]
+        );
+        assert_eq!(
+            titles(lib.sorted(SortOrder::RecentlyAdded)),
+            ["celeste", "Dead Cells", "Axiom Verge", "Braid"]
+        );
+        std::fs::remove_file(path).ok();
+    }
+
+    #[test]
+    fn entry_without_added_at_defaults_to_zero() {
+        let path = temp_path();
+        std::fs::write(
+            &path,
+            r#"[{"id":"1","title":"Old","executable":"/o","cover_image":null,"tags":[],"notes":null}]"#,
+        )
+        .unwrap();
+        assert_eq!(Library::load(&path).unwrap().games()[0].added_at, 0);
+        assert!(make_game("New", "/n").added_at > 0);
+        std::fs::remove_file(path).ok();
+    }
+
+    #[test]
+    fn add_multiple_games() {
+        let path = temp_path();
+        let mut lib = Library::load(&path).unwrap();
+
+        lib.add(make_game("A", "/a")).unwrap();
+        lib.add(make_game("B", "/b")).unwrap();
+        lib.add(make_game("C", "/c")).unwrap();
+
+        assert_eq!(lib.games().len(), 3);
+        std::fs::remove_file(path).ok();
+    }
+
+    // --- persist ---
+
+    #[test]
+    fn persist_leaves_no_temp_file() {
+        let path = temp_path();
+        let mut lib = Library::load(&path).unwrap();
+        lib.add(make_game("Atomic", "/a")).unwrap();
+
+        assert!(path.exists());
+        assert!(!temp_path_for(&path).exists());
+        std::fs::remove_file(path).ok();
+    }
+
+    #[test]
+    fn partial_write_keeps_previous_contents() {
+        let path = temp_path();
+        let mut lib = Library::load(&path).unwrap();
+        lib.add(make_game("Survivor", "/s")).unwrap();
+
+        // Simulate a crash halfway through writing the next version.
+        let tmp = temp_path_for(&path);
+        std::fs::write(&tmp, r#"[{"id": "trunc"#).unwrap();
+
+        let lib2 = Library::load(&path).unwrap();
+        assert_eq!(lib2.games().len(), 1);
+        assert_eq!(lib2.games()[0].title, "Survivor");
+
+        std::fs::remove_file(path).ok();
+        std::fs::remove_file(tmp).ok();
+    }
+
+    #[test]
+    fn corrupt_file_recovers_from_temp() {
+        let path = temp_path();
+        let tmp = temp_path_for(&path);
+        let games = vec![make_game("Recovered", "/r")];
+        std::fs::write(&tmp, serde_json::to_string(&games).unwrap()).unwrap();
+        std::fs::write(&path, "[{\"id\": ").unwrap();
+
+        let lib = Library::load(&path).unwrap();
+        assert_eq!(lib.games().len(), 1);
+        assert_eq!(lib.games()[0].title, "Recovered");
+
+        std::fs::remove_file(path).ok();
+        std::fs::remove_file(tmp).ok();
+    }
+
+    #[test]
+    fn corrupt_file_without_temp_is_an_error() {
+        let path = temp_path();
+        std::fs::write(&path, "not json").unwrap();
+        assert!(matches!(Library::load(&path), Err(LibraryError::Json(_))));
+        std::fs::remove_file(path).ok();
+    }
+
+    // --- id index ---
+
+    fn assert_index_consistent(lib: &Library) {
+        assert_eq!(lib.index.len(), lib.games.len());
+        for (i, game) in lib.games.iter().enumerate() {
+            assert_eq!(
+                lib.index.get(&game.id),
+                Some(&i),
+                "{} misindexed",
+                game.title
+            );
+        }
+    }
+
+    #[test]
+    fn index_tracks_adds_and_removals() {
+        let path = temp_path();
+        let mut lib = Library::load(&path).unwrap();
+        let ids: Vec<String> = lib
+            .with_batch(|lib| {
+                (0..500)
+                    .map(|i| {
+                        Ok(lib
+                            .add(make_game(&format!("G{i}"), &format!("/g/{i}")))?
+                            .id
+                            .clone())
+                    })
+                    .collect()
+            })
+            .unwrap();
+        assert_index_consistent(&lib);
+        assert_eq!(lib.get(&ids[250]).unwrap().title, "G250");
+
+        // Removing early entries shifts every later position.
+        lib.remove(&ids[0]).unwrap();
+        lib.remove_many(&ids[10..20]).unwrap();
+        assert_index_consistent(&lib);
+        assert!(lib.get(&ids[0]).is_none());
+        assert!(lib.get(&ids[15]).is_none());
+        assert_eq!(lib.get(&ids[499]).unwrap().title, "G499");
+        lib.add_playtime(&ids[300], 5).unwrap();
+        assert_eq!(lib.get(&ids[300]).unwrap().playtime_secs, 5);
+        assert!(matches!(
+            lib.remove(&ids[0]),
+            Err(LibraryError::NotFound(_))
+        ));
+
+        let reloaded = Library::load(&path).unwrap();
+        assert_index_consistent(&reloaded);
+        assert_eq!(reloaded.get(&ids[300]).unwrap().title, "G300");
+
+        std::fs::remove_file(path).ok();
+    }
+
+    // --- batching ---
+
+    #[test]
+    fn batched_adds_write_once() {
+        let path = temp_path();
+        let mut lib = Library::load(&path).unwrap();
+        lib.add(make_game("Single", "/single")).unwrap();
+        assert_eq!(lib.writes, 1, "single operations still write immediately");
+
+        lib.with_batch(|lib| {
+            for i in 0..20 {
+                lib.add(make_game(&format!("Game {i}"), &format!("/games/{i}")))?;
+            }
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(lib.writes, 2, "the batch should write exactly once");
+        assert_eq!(Library::load(&path).unwrap().games().len(), 21);
+
+        // Nested batches defer to the outermost; an empty batch writes nothing.
+        lib.begin_batch();
+        lib.begin_batch();
+        lib.add(make_game("Nested", "/nested")).unwrap();
+        lib.end_batch().unwrap();
+        assert_eq!(lib.writes, 2);
+        lib.end_batch().unwrap();
+        assert_eq!(lib.writes, 3);
+        lib.with_batch(|_| Ok(())).unwrap();
+        assert_eq!(lib.writes, 3);
+
+        std::fs::remove_file(path).ok();
+    }
+
+    #[test]
+    fn failed_batch_still_writes_earlier_changes() {
+        let path = temp_path();
+        let mut lib = Library::load(&path).unwrap();
+        let result = lib.with_batch(|lib| {
+            lib.add(make_game("Kept", "/kept"))?;
+            lib.add(make_game("Duplicate", "/kept"))?;
+            Ok(())
+        });
+        assert!(matches!(result, Err(LibraryError::Duplicate(_))));
+        assert_eq!(lib.writes, 1);
+        assert_eq!(Library::load(&path).unwrap().games()[0].title, "Kept");
+
+        std::fs::remove_file(path).ok();
+    }
+
+    // --- remove_many ---
+
+    #[test]
+    fn remove_many_removes_known_and_ignores_unknown() {
+        let path = temp_path();
+        let mut lib = Library::load(&path).unwrap();
+        let a = lib.add(make_game("A", "/a")).unwrap().id.clone();
+        let b = lib.add(make_game("B", "/b")).unwrap().id.clone();
+        let c = lib.add(make_game("C", "/c")).unwrap().id.clone();
+        let collection = lib.create_collection("Mixed").unwrap().id.clone();
+        lib.add_to_collection(&collection, &format!("custom-{a}"))
+            .unwrap();
+        lib.add_to_collection(&collection, &format!("custom-{b}"))
+            .unwrap();
+
+        let removed = lib
+            .remove_many(&[c.clone(), "missing".to_string(), a.clone()])
+            .unwrap();
+        let mut titles: Vec<&str> = removed.iter().map(|g| g.title.as_str()).collect();
+        titles.sort();
+        assert_eq!(titles, ["A", "C"]);
+
+        let reloaded = Library::load(&path).unwrap();
+        assert_eq!(reloaded.games().len(), 1);
+        assert_eq!(reloaded.games()[0].id, b);
+        assert_eq!(
+            reloaded.collections()[0].member_keys,
+            [format!("custom-{b}")]
+        );
+
+        assert!(lib
+            .remove_many(&["missing".to_string()])
+            .unwrap()
+            .is_empty());
+        assert_eq!(lib.games().len(), 1);
+
+        std::fs::remove_file(&path).ok();
+        std::fs::remove_file(collections_path_for(&path)).ok();
+    }
+
+    // --- issues ---
+
+    #[test]
+    fn check_game_flags_missing_paths() {
+        let present = |p: &Path| p == Path::new("/games/ok") || p == Path::new("/covers/ok.png");
+        let mut game = make_game("Fine", "/games/ok");
+        assert_eq!(check_game(&game, present), None, "no cover is not an issue");
+        game.cover_image = Some(PathBuf::from("/covers/ok.png"));
+        assert_eq!(check_game(&game, present), None);
+
+        game.cover_image = Some(PathBuf::from("/covers/gone.png"));
+        let issue = check_game(&game, present).unwrap();
+        assert!(!issue.executable_missing);
+        assert!(issue.cover_missing);
+
+        game.executable = PathBuf::from("/games/uninstalled");
+        let issue = check_game(&game, present).unwrap();
+        assert_eq!(issue.id, game.id);
+        assert_eq!(issue.title, "Fine");
+        assert!(issue.executable_missing && issue.cover_missing);
+    }
+
+    #[test]
+    fn check_game_flags_missing_hero_and_screenshots() {
+        let present = |p: &Path| p == Path::new("/games/ok") || p == Path::new("/art/ok.png");
+        let mut game = make_game("Art", "/games/ok");
+        game.hero_image = Some(PathBuf::from("/art/ok.png"));
+        game.screenshots = vec![PathBuf::from("/art/ok.png")];
+        assert_eq!(check_game(&game, present), None);
+
+        game.hero_image = Some(PathBuf::from("/art/gone.png"));
+        game.screenshots.push(PathBuf::from("/art/gone1.png"));
+        game.screenshots.push(PathBuf::from("/art/gone2.png"));
+        let issue = check_game(&game, present).unwrap();
+        assert!(!issue.executable_missing && !issue.cover_missing);
+        assert!(issue.hero_missing);
+        assert_eq!(issue.screenshots_missing, 2);
+    }
+
+    #[test]
+    fn issues_lists_only_broken_entries() {
+        let path = temp_path();
+        let (dir, exe) = temp_file("game.exe");
+        std::fs::write(dir.join("cover.png"), "").unwrap();
+        let mut lib = Library::load(&path).unwrap();
+        let mut healthy = make_game("Healthy", exe.to_str().unwrap());
+        healthy.cover_image = Some(dir.join("cover.png"));
+        lib.add(healthy).unwrap();
+        let mut no_cover = make_game("No Cover", dir.to_str().unwrap());
+        no_cover.cover_image = Some(dir.join("deleted.png"));
+        lib.add(no_cover).unwrap();
+        let gone = lib
+            .add(make_game("Gone", "/no/such/game.exe"))
+            .unwrap()
+            .id
+            .clone();
+
+        let issues = lib.issues();
+        let summary: Vec<(&str, bool, bool)> = issues
+            .iter()
+            .map(|i| (i.title.as_str(), i.executable_missing, i.cover_missing))
+            .collect();
+        assert_eq!(summary, [("No Cover", false, true), ("Gone", true, false)]);
+        assert_eq!(issues[1].id, gone);
+
+        std::fs::remove_dir_all(dir).ok();
+        std::fs::remove_file(path).ok();
+    }
+
+    // --- add_validated ---
+
+    #[test]
+    fn add_validated_accepts_existing_file() {
+        let path = temp_path();
+        let exe = temp_path();
+        std::fs::write(&exe, "").unwrap();
+        let mut lib = Library::load(&path).unwrap();
+
+        lib.add_validated(make_game("Real", exe.to_str().unwrap()), false)
+            .expect("existing executable should be accepted");
+        assert_eq!(lib.games().len(), 1);
+
+        std::fs::remove_file(exe).ok();
+        std::fs::remove_file(path).ok();
+    }
+
+    #[test]
+    fn add_validated_rejects_missing_file() {
+        let path = temp_path();
+        let mut lib = Library::load(&path).unwrap();
+
+        let result = lib.add_validated(make_game("Typo", "/games/celestee"), false);
+        assert!(matches!(
+            result,
+            Err(LibraryError::ExecutableMissing(p)) if p == Path::new("/games/celestee")
+        ));
+        assert!(lib.games().is_empty());
+        assert!(!path.exists(), "nothing should be persisted");
+    }
+
+    #[test]
+    fn app_bundle_accepted_only_on_macos() {
+        let path = temp_path();
+        let bundle = std::env::temp_dir().join(format!("Game_{}.app", Uuid::new_v4()));
+        std::fs::create_dir_all(bundle.join("Contents/MacOS")).unwrap();
+        let mut lib = Library::load(&path).unwrap();
+
+        let result = lib.add_validated(make_game("Bundle", bundle.to_str().unwrap()), false);
+        if cfg!(target_os = "macos") {
+            assert!(result.is_ok(), ".app bundles are launchable on macOS");
+        } else {
+            assert!(matches!(result, Err(LibraryError::ExecutableMissing(_))));
+        }
+
+        std::fs::remove_dir_all(bundle).ok();
+        std::fs::remove_file(path).ok();
+    }
+
+    // --- cover validation ---
+
+    /// Creates an empty file in a fresh temp directory and returns (dir, file).
+    fn temp_file(name: &str) -> (PathBuf, PathBuf) {
+        let dir = std::env::temp_dir().join(format!("cover_test_{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let file = dir.join(name);
+        std::fs::write(&file, "").unwrap();
+        (dir, file)
+    }
+
+    #[test]
+    fn valid_cover_is_canonicalized() {
+        let path = temp_path();
+        let (dir, exe) = temp_file("game.exe");
+        std::fs::write(dir.join("Cover.JPG"), "").unwrap();
+        let mut lib = Library::load(&path).unwrap();
+
+        let mut game = make_game("Covered", exe.to_str().unwrap());
+        game.cover_image = Some(dir.join("sub/../Cover.JPG"));
+        std::fs::create_dir_all(dir.join("sub")).unwrap();
+        let added = lib.add_validated(game, false).unwrap().clone();
+        let cover = added.cover_image.expect("cover should be kept");
+        assert!(cover.is_absolute());
+        assert_eq!(cover, std::fs::canonicalize(dir.join("Cover.JPG")).unwrap());
+
+        // Clearing the cover needs no file.
+        let cleared = CustomGame {
+            cover_image: None,
+            ..added
+        };
+        assert_eq!(lib.update_validated(cleared).unwrap().cover_image, None);
+
+        std::fs::remove_dir_all(dir).ok();
+        std::fs::remove_file(path).ok();
+    }
+
+    #[test]
+    fn nonexistent_cover_rejected() {
+        let path = temp_path();
+        let (dir, exe) = temp_file("game.exe");
+        let mut lib = Library::load(&path).unwrap();
+
+        let mut game = make_game("Broken", exe.to_str().unwrap());
+        game.cover_image = Some(dir.join("missing.png"));
+        let result = lib.add_validated(game, false);
+        assert!(
+            matches!(result, Err(LibraryError::InvalidCover(p)) if p == dir.join("missing.png"))
+        );
+        assert!(lib.games().is_empty());
+
+        std::fs::remove_dir_all(dir).ok();
+        std::fs::remove_file(path).ok();
+    }
+
+    #[test]
+    fn hero_and_screenshots_default_when_absent() {
+        let json = r#"{"id":"1","title":"Old","executable":"/o","cover_image":null,"tags":[],"notes":null}"#;
+        let game: CustomGame = serde_json::from_str(json).unwrap();
+        assert_eq!(game.hero_image, None);
+        assert!(game.screenshots.is_empty());
+    }
+
+    #[test]
+    fn hero_and_screenshots_are_validated_and_round_trip() {
+        let path = temp_path();
+        let (dir, exe) = temp_file("game.exe");
+        for image in ["hero.jpg", "shot1.png", "shot2.webp", "notes.txt"] {
+            std::fs::write(dir.join(image), "").unwrap();
+        }
+        let mut lib = Library::load(&path).unwrap();
+
+        let mut game = make_game("Gallery", exe.to_str().unwrap());
+        game.hero_image = Some(dir.join("hero.jpg"));
+        game.screenshots = vec![dir.join("shot1.png"), dir.join("sub/../shot2.webp")];
+        std::fs::create_dir_all(dir.join("sub")).unwrap();
+        let added = lib.add_validated(game, false).unwrap().clone();
+        let canonical = |name: &str| std::fs::canonicalize(dir.join(name)).unwrap();
+        assert_eq!(added.hero_image, Some(canonical("hero.jpg")));
+        assert_eq!(
+            added.screenshots,
+            [canonical("shot1.png"), canonical("shot2.webp")]
+        );
+
+        let reloaded = Library::load(&path).unwrap();
+        assert_eq!(reloaded.get(&added.id), Some(&added));
+
+        let bad = CustomGame {
+            screenshots: vec![dir.join("notes.txt")],
+            ..added.clone()
+        };
+        assert!(matches!(
+            lib.update_validated(bad),
+            Err(LibraryError::InvalidCover(p)) if p == dir.join("notes.txt")
+        ));
+        assert_eq!(lib.get(&added.id), Some(&added));
+
+        std::fs::remove_dir_all(dir).ok();
+        std::fs::remove_file(path).ok();
+    }
+
+    #[test]
+    fn unchanged_missing_images_do_not_block_edits() {
+        let path = temp_path();
+        let (dir, exe) = temp_file("game.exe");
+        for image in ["cover.png", "hero.jpg", "shot.webp", "new.png"] {
+            std::fs::write(dir.join(image), "").unwrap();
+        }
+        let mut lib = Library::load(&path).unwrap();
+
+        let mut game = make_game("Moved", exe.to_str().unwrap());
+        game.cover_image = Some(dir.join("cover.png"));
+        game.hero_image = Some(dir.join("hero.jpg"));
+        game.screenshots = vec![dir.join("shot.webp")];
+        let added = lib.add_validated(game, false).unwrap().clone();
+        for image in ["cover.png", "hero.jpg", "shot.webp"] {
+            std::fs::remove_file(dir.join(image)).unwrap();
+        }
+
+        let renamed = lib
+            .update_validated(CustomGame {
+                title: "Renamed".to_string(),
+                ..added.clone()
+            })
+            .unwrap()
+            .clone();
+        assert_eq!(renamed.cover_image, added.cover_image);
+        assert_eq!(renamed.hero_image, added.hero_image);
+        assert_eq!(renamed.screenshots, added.screenshots);
+
+        // Newly added images are still checked.
+        let mut screenshots = added.screenshots.clone();
+        screenshots.push(dir.join("new.png"));
+        screenshots.push(dir.join("gone.png"));
+        assert!(matches!(
+            lib.update_validated(CustomGame {
+                screenshots,
+                ..renamed
+            }),
+            Err(LibraryError::InvalidCover(p)) if p == dir.join("gone.png")
+        ));
+
+        std::fs::remove_dir_all(dir).ok();
+        std::fs::remove_file(path).ok();
+    }
+
+    #[test]
+    fn cover_extensions_match_discovered_store_art() {
+        for name in ["a.png", "b.JPG", "c.jpeg", "d.webp", "e.AVIF"] {
+            assert!(has_cover_extension(Path::new(name)), "{name}");
+        }
+        assert!(!has_cover_extension(Path::new("f.gif")));
+    }
+
+    #[test]
+    fn unsupported_cover_extension_rejected() {
+        let path = temp_path();
+        let (dir, exe) = temp_file("game.exe");
+        std::fs::write(dir.join("cover.gif"), "").unwrap();
+        let mut lib = Library::load(&path).unwrap();
+        let game = lib
+            .add_validated(make_game("Gif", exe.to_str().unwrap()), false)
+            .unwrap()
+            .clone();
+
+        let updated = CustomGame {
+            cover_image: Some(dir.join("cover.gif")),
+            ..game
+        };
+        assert!(matches!(
+            lib.update_validated(updated),
+            Err(LibraryError::InvalidCover(_))
+        ));
+        assert_eq!(
+            lib.games()[0].cover_image,
+            None,
+            "stored entry is unchanged"
+        );
+
+        std::fs::remove_dir_all(dir).ok();
+        std::fs::remove_file(path).ok();
+    }
+
+    // --- managed covers ---
+
+    #[test]
+    fn managed_cover_is_copied_and_removed_with_game() {
+        let (dir, exe) = temp_file("game.exe");
+        let path = dir.join("custom_games.json");
+        std::fs::write(dir.join("download.PNG"), "png bytes").unwrap();
+        let mut lib = Library::load(&path).unwrap();
+        lib.set_manage_covers(true);
+
+        let mut game = make_game("Managed", exe.to_str().unwrap());
+        game.cover_image = Some(dir.join("download.PNG"));
+        let added = lib.add_validated(game, false).unwrap().clone();
+        let cover = added.cover_image.clone().unwrap();
+        let covers_dir = std::fs::canonicalize(dir.join("covers")).unwrap();
+        assert_eq!(cover, covers_dir.join(format!("{}.png", added.id)));
+        assert_eq!(std::fs::read_to_string(&cover).unwrap(), "png bytes");
+
+        // The copy survives the original being deleted, and the setting survives a reload.
+        std::fs::remove_file(dir.join("download.PNG")).unwrap();
+        assert!(cover.is_file());
+        lib.reload().unwrap();
+        assert!(lib.manage_covers);
+
+        lib.remove(&added.id).unwrap();
+        assert!(
+            !cover.exists(),
+            "managed cover should be deleted with the game"
+        );
+
+        std::fs::remove_dir_all(dir).ok();
+    }
+
+    #[test]
+    fn replacing_managed_cover_drops_old_copy_only() {
+        let (dir, exe) = temp_file("game.exe");
+        let path = dir.join("custom_games.json");
+        std::fs::write(dir.join("a.png"), "a").unwrap();
+        std::fs::write(dir.join("b.jpg"), "b").unwrap();
+        let mut lib = Library::load(&path).unwrap();
+        lib.set_manage_covers(true);
+
+        let mut game = make_game("Swap", exe.to_str().unwrap());
+        game.cover_image = Some(dir.join("a.png"));
+        let added = lib.add_validated(game, false).unwrap().clone();
+        let first = added.cover_image.clone().unwrap();
+
+        let updated = lib
+            .update_validated(CustomGame {
+                cover_image: Some(dir.join("b.jpg")),
+                ..added
+            })
+            .unwrap()
+            .clone();
+        let second = updated.cover_image.clone().unwrap();
+        assert!(second.ends_with(format!("{}.jpg", updated.id)));
+        assert!(!first.exists(), "replaced managed cover should be deleted");
+        assert!(
+            dir.join("a.png").exists(),
+            "the user's original is untouched"
+        );
+
+        // Re-saving with the managed copy keeps it in place.
+        let same = lib.update_validated(updated).unwrap();
+        assert_eq!(same.cover_image.as_ref(), Some(&second));
+        assert!(second.is_file());
+
+        std::fs::remove_dir_all(dir).ok();
     }
 
-    fn persist(&self) -> Result<(), LibraryError> {
-        if let Some(parent) = self.path.parent() {
-            std::fs::create_dir_all(parent)?;
+    #[test]
+    fn managed_hero_and_screenshots_follow_the_game() {
+        let (dir, exe) = temp_file("game.exe");
+        let path = dir.join("custom_games.json");
+        for name in ["hero.jpg", "one.png", "two.webp"] {
+            std::fs::write(dir.join(name), name).unwrap();
         }
-        let json = serde_json::to_string_pretty(&self.games)?;
-        std::fs::write(&self.path, json)?;
-        Ok(())
-    }
-}
+        let mut lib = Library::load(&path).unwrap();
+        lib.set_manage_covers(true);
 
-// ============================================================
-// Tests
-// ============================================================
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::path::PathBuf;
+        let mut game = make_game("Gallery", exe.to_str().unwrap());
+        game.hero_image = Some(dir.join("hero.jpg"));
+        game.screenshots = vec![dir.join("one.png"), dir.join("two.webp")];
+        let added = lib.add_validated(game, false).unwrap().clone();
+        let covers_dir = std::fs::canonicalize(dir.join("covers")).unwrap();
+        let hero = added.hero_image.clone().unwrap();
+        assert_eq!(hero, covers_dir.join(format!("{}-hero.jpg", added.id)));
+        assert_eq!(std::fs::read_to_string(&hero).unwrap(), "hero.jpg");
+        let shots = added.screenshots.clone();
+        assert!(shots
+            .iter()
+            .all(|s| s.parent() == Some(covers_dir.as_path())));
+        assert_eq!(std::fs::read_to_string(&shots[1]).unwrap(), "two.webp");
 
-    fn make_game(title: &str, exe: &str) -> CustomGame {
-        CustomGame::new(title, exe, None, vec![], None)
-    }
+        // Dropping a screenshot deletes its copy; the rest stay.
+        let updated = lib
+            .update_validated(CustomGame {
+                screenshots: vec![shots[1].clone()],
+                ..added
+            })
+            .unwrap()
+            .clone();
+        assert_eq!(updated.screenshots, [shots[1].clone()]);
+        assert!(
+            !shots[0].exists(),
+            "dropped managed screenshot should be deleted"
+        );
+        assert!(hero.is_file() && shots[1].is_file());
+        assert!(
+            dir.join("one.png").exists(),
+            "the user's original is untouched"
+        );
 
-    fn temp_path() -> PathBuf {
-        let mut p = std::env::temp_dir();
-        p.push(format!("game_library_test_{}.json", Uuid::new_v4()));
-        p
-    }
+        lib.remove(&updated.id).unwrap();
+        assert!(!hero.exists() && !shots[1].exists());
 
-    // --- CustomGame construction ---
+        std::fs::remove_dir_all(dir).ok();
+    }
 
     #[test]
-    fn new_game_has_unique_id() {
-        let a = make_game("Game A", "/path/a");
-        let b = make_game("Game B", "/path/b");
-        assert_ne!(a.id, b.id);
+    fn failed_edit_leaves_no_managed_copies() {
+        let (dir, exe) = temp_file("game.exe");
+        let path = dir.join("custom_games.json");
+        std::fs::write(dir.join("hero.png"), "hero").unwrap();
+        let game = make_game("Future", exe.to_str().unwrap());
+        let envelope = serde_json::json!({ "version": 99, "games": [&game] });
+        std::fs::write(&path, envelope.to_string()).unwrap();
+        let mut lib = Library::load(&path).unwrap();
+        lib.set_manage_covers(true);
+
+        let result = lib.update_validated(CustomGame {
+            hero_image: Some(dir.join("hero.png")),
+            ..game
+        });
+        assert!(matches!(result, Err(LibraryError::NewerVersion(99))));
+        let copies = std::fs::read_dir(dir.join("covers")).unwrap().count();
+        assert_eq!(copies, 0, "the hero copy should be removed again");
+
+        std::fs::remove_dir_all(dir).ok();
     }
 
     #[test]
-    fn new_game_stores_fields() {
-        let game = CustomGame::new(
-            "Hollow Knight",
-            "/games/hollow_knight",
-            Some(PathBuf::from("/images/hk.png")),
-            vec!["metroidvania".to_string()],
-            Some("great game".to_string()),
+    fn unmanaged_covers_stay_where_they_are() {
+        let (dir, exe) = temp_file("game.exe");
+        let path = dir.join("custom_games.json");
+        std::fs::write(dir.join("cover.webp"), "").unwrap();
+        let mut lib = Library::load(&path).unwrap();
+        assert!(!lib.manage_covers, "off by default");
+
+        let mut game = make_game("Linked", exe.to_str().unwrap());
+        game.cover_image = Some(dir.join("cover.webp"));
+        let added = lib.add_validated(game, false).unwrap().clone();
+        assert_eq!(
+            added.cover_image,
+            Some(std::fs::canonicalize(dir.join("cover.webp")).unwrap())
         );
-        assert_eq!(game.title, "Hollow Knight");
-        assert_eq!(game.executable, PathBuf::from("/games/hollow_knight"));
-        assert_eq!(game.cover_image, Some(PathBuf::from("/images/hk.png")));
-        assert_eq!(game.tags, vec!["metroidvania"]);
-        assert_eq!(game.notes, Some("great game".to_string()));
+        assert!(!dir.join("covers").exists());
+
+        lib.remove(&added.id).unwrap();
+        assert!(
+            dir.join("cover.webp").exists(),
+            "user files are never deleted"
+        );
+
+        std::fs::remove_dir_all(dir).ok();
     }
 
-    // --- Library load ---
+    // --- cover matching ---
 
     #[test]
-    fn load_nonexistent_file_returns_empty_library() {
-        let lib = Library::load("/tmp/definitely_does_not_exist_abc123.json").unwrap();
-        assert!(lib.games().is_empty());
+    fn cover_matches_title_without_subtitle() {
+        let candidates = [
+            PathBuf::from("/covers/Hollow Knight.png"),
+            PathBuf::from("/covers/The Witcher 3.jpg"),
+        ];
+        assert_eq!(
+            match_cover_for("The Witcher 3: Wild Hunt", &candidates),
+            Some(PathBuf::from("/covers/The Witcher 3.jpg"))
+        );
+        assert_eq!(
+            match_cover_for("THE WITCHER 3 - Wild Hunt", &candidates),
+            Some(PathBuf::from("/covers/The Witcher 3.jpg"))
+        );
+        assert_eq!(match_cover_for("Stardew Valley", &candidates), None);
+        assert_eq!(match_cover_for("The Witness", &candidates), None);
     }
 
     #[test]
-    fn load_existing_file_deserializes_games() {
-        let path = temp_path();
-        let games = vec![make_game("Game A", "/a"), make_game("Game B", "/b")];
-        std::fs::write(&path, serde_json::to_string(&games).unwrap()).unwrap();
-
-        let lib = Library::load(&path).unwrap();
-        assert_eq!(lib.games().len(), 2);
-        assert_eq!(lib.games()[0].title, "Game A");
-
-        std::fs::remove_file(path).ok();
+    fn cover_match_tolerates_typos_and_skips_non_images() {
+        let candidates = [
+            PathBuf::from("/covers/Celest.webp"),
+            PathBuf::from("/covers/Celeste.txt"),
+        ];
+        assert_eq!(
+            match_cover_for("Celeste", &candidates),
+            Some(PathBuf::from("/covers/Celest.webp"))
+        );
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(normalize_title("  Half-Life: 2! "), "half life 2");
     }
 
-    // --- add ---
-
     #[test]
-    fn add_game_persists_to_disk() {
-        let path = temp_path();
+    fn auto_assign_covers_fills_only_missing_covers() {
+        let (dir, _) = temp_file("unused");
+        let path = dir.join("custom_games.json");
+        let covers = dir.join("downloads");
+        std::fs::create_dir_all(&covers).unwrap();
+        for name in ["The Witcher 3.jpg", "Celeste.png", "Hades.png"] {
+            std::fs::write(covers.join(name), "").unwrap();
+        }
         let mut lib = Library::load(&path).unwrap();
+        let witcher = lib
+            .add(make_game("The Witcher 3: Wild Hunt", "/games/witcher3"))
+            .unwrap()
+            .clone();
+        lib.add(make_game("Stardew Valley", "/games/stardew"))
+            .unwrap();
+        let mut hades = make_game("Hades", "/games/hades");
+        hades.cover_image = Some(PathBuf::from("/art/hades.png"));
+        lib.add(hades).unwrap();
 
-        lib.add(make_game("Celeste", "/games/celeste")).unwrap();
-
-        let lib2 = Library::load(&path).unwrap();
-        assert_eq!(lib2.games().len(), 1);
-        assert_eq!(lib2.games()[0].title, "Celeste");
+        let assigned = lib.auto_assign_covers(&covers).unwrap();
+        assert_eq!(assigned.len(), 1);
+        assert_eq!(assigned[0].id, witcher.id);
+        assert_eq!(
+            lib.get(&witcher.id).unwrap().cover_image,
+            Some(std::fs::canonicalize(covers.join("The Witcher 3.jpg")).unwrap())
+        );
 
-        std::fs::remove_file(path).ok();
+        std::fs::remove_dir_all(dir).ok();
     }
 
     #[test]
-    fn add_multiple_games() {
+    fn add_skips_validation() {
         let path = temp_path();
         let mut lib = Library::load(&path).unwrap();
-
-        lib.add(make_game("A", "/a")).unwrap();
-        lib.add(make_game("B", "/b")).unwrap();
-        lib.add(make_game("C", "/c")).unwrap();
-
-        assert_eq!(lib.games().len(), 3);
+        lib.add(make_game("Unchecked", "/no/such/exe")).unwrap();
+        assert_eq!(lib.games().len(), 1);
         std::fs::remove_file(path).ok();
     }
 
@@ -256,6 +2635,26 @@ mod tests {
         std::fs::remove_file(path).ok();
     }
 
+    #[test]
+    fn update_persists_across_reload() {
+        let path = temp_path();
+        let mut lib = Library::load(&path).unwrap();
+
+        let original = lib.add(make_game("Draft", "/exe")).unwrap().clone();
+        let mut edited = original.clone();
+        edited.title = "Final".to_string();
+        edited.tags = vec!["rpg".to_string()];
+        edited.notes = Some("edited".to_string());
+        lib.update(edited).unwrap();
+
+        let lib2 = Library::load(&path).unwrap();
+        let reloaded = lib2.get(&original.id).expect("id should be stable");
+        assert_eq!(reloaded.title, "Final");
+        assert_eq!(reloaded.tags, vec!["rpg"]);
+        assert_eq!(reloaded.notes, Some("edited".to_string()));
+        std::fs::remove_file(path).ok();
+    }
+
     #[test]
     fn update_nonexistent_id_returns_error() {
         let path = temp_path();
@@ -268,6 +2667,388 @@ mod tests {
         std::fs::remove_file(path).ok();
     }
 
+    // --- playtime ---
+
+    #[test]
+    fn new_game_has_zero_playtime() {
+        assert_eq!(make_game("Fresh", "/f").playtime_secs, 0);
+    }
+
+    #[test]
+    fn add_playtime_accumulates_and_persists() {
+        let path = temp_path();
+        let mut lib = Library::load(&path).unwrap();
+        let game = lib.add(make_game("Timed", "/t")).unwrap().clone();
+
+        assert_eq!(game.last_played, None);
+        lib.add_playtime(&game.id, 90).unwrap();
+        lib.add_playtime(&game.id, 30).unwrap();
+        assert!(lib.get(&game.id).unwrap().last_played.is_some());
+
+        let lib2 = Library::load(&path).unwrap();
+        assert_eq!(lib2.get(&game.id).unwrap().playtime_secs, 120);
+        std::fs::remove_file(path).ok();
+    }
+
+    #[test]
+    fn add_playtime_unknown_id_returns_error() {
+        let path = temp_path();
+        let mut lib = Library::load(&path).unwrap();
+        assert!(matches!(
+            lib.add_playtime("missing", 10),
+            Err(LibraryError::NotFound(_))
+        ));
+        std::fs::remove_file(path).ok();
+    }
+
+    #[test]
+    fn legacy_entry_without_playtime_loads() {
+        let path = temp_path();
+        std::fs::write(
+            &path,
+            r#"[{"id":"1","title":"Old","executable":"/o","cover_image":null,"tags":[],"notes":null}]"#,
+        )
+        .unwrap();
+        let lib = Library::load(&path).unwrap();
+        assert_eq!(lib.games()[0].playtime_secs, 0);
+        std::fs::remove_file(path).ok();
+    }
+
+    // --- launch options ---
+
+    #[test]
+    fn legacy_entry_without_launch_options_loads() {
+        let path = temp_path();
+        std::fs::write(
+            &path,
+            r#"[{"id":"1","title":"Old","executable":"/o","cover_image":null,"tags":[],"notes":null,"playtime_secs":42}]"#,
+        )
+        .unwrap();
+        let lib = Library::load(&path).unwrap();
+        let game = &lib.games()[0];
+        assert!(game.args.is_empty());
+        assert_eq!(game.working_dir, None);
+        assert_eq!(game.playtime_secs, 42);
+        std::fs::remove_file(path).ok();
+    }
+
+    #[test]
+    fn launch_target_carries_args_and_working_dir() {
+        let mut game = make_game("Quake", "/games/quake/quake");
+        game.args = vec!["+map".to_string(), "e1m1".to_string()];
+        game.working_dir = Some(PathBuf::from("/games/quake"));
+
+        assert_eq!(
+            game.launch_target(),
+            LaunchTarget::Executable {
+                path: "/games/quake/quake".to_string(),
+                options: SpawnOptions {
+                    args: vec!["+map".to_string(), "e1m1".to_string()],
+                    working_dir: Some(PathBuf::from("/games/quake")),
+                    ..Default::default()
+                },
+            }
+        );
+    }
+
+    #[test]
+    fn env_round_trips_as_json_object() {
+        let mut game = make_game("Elden Ring", "/games/er/proton");
+        game.env = vec![
+            ("WINEPREFIX".to_string(), "/pfx/er".to_string()),
+            ("DXVK_HUD".to_string(), "fps".to_string()),
+        ];
+
+        let json = serde_json::to_string(&game).unwrap();
+        assert!(json.contains(r#""env":{"WINEPREFIX":"/pfx/er","DXVK_HUD":"fps"}"#));
+
+        let back: CustomGame = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.env, game.env);
+    }
+
+    #[test]
+    fn legacy_entry_without_env_loads() {
+        let json = r#"{"id":"1","title":"Old","executable":"/o","cover_image":null,"tags":[],"notes":null}"#;
+        let game: CustomGame = serde_json::from_str(json).unwrap();
+        assert!(game.env.is_empty());
+    }
+
+    // --- favorites ---
+
+    #[test]
+    fn set_favorite_pins_game_first_and_persists() {
+        let path = temp_path();
+        let mut lib = Library::load(&path).unwrap();
+        lib.add(make_game("A", "/a")).unwrap();
+        let b = lib.add(make_game("B", "/b")).unwrap().id.clone();
+        lib.add(make_game("C", "/c")).unwrap();
+
+        assert!(lib.set_favorite(&b, true).unwrap().favorite);
+        let order: Vec<_> = lib.favorites().iter().map(|g| g.title.as_str()).collect();
+        assert_eq!(order, ["B", "A", "C"]);
+
+        let reloaded = Library::load(&path).unwrap();
+        assert!(reloaded.get(&b).unwrap().favorite);
+
+        lib.set_favorite(&b, false).unwrap();
+        assert!(!Library::load(&path).unwrap().get(&b).unwrap().favorite);
+        std::fs::remove_file(path).ok();
+    }
+
+    #[test]
+    fn set_favorite_unknown_id_returns_error() {
+        let path = temp_path();
+        let mut lib = Library::load(&path).unwrap();
+        assert!(matches!(
+            lib.set_favorite("missing", true),
+            Err(LibraryError::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn external_favorites_persist_across_reload() {
+        let path = temp_path();
+        let mut lib = Library::load(&path).unwrap();
+        lib.set_external_favorite("steam:440", true).unwrap();
+        lib.set_external_favorite("epic:Quail", true).unwrap();
+        lib.set_external_favorite("epic:Quail", false).unwrap();
+
+        let reloaded = Library::load(&path).unwrap();
+        assert!(reloaded.is_external_favorite("steam:440"));
+        assert!(!reloaded.is_external_favorite("epic:Quail"));
+        assert_eq!(reloaded.external_favorites().len(), 1);
+
+        std::fs::remove_file(favorites_path_for(&path)).ok();
+    }
+
+    #[test]
+    fn legacy_shortcut_keys_are_migrated_on_load() {
+        let path = temp_path();
+        std::fs::write(
+            favorites_path_for(&path),
+            r#"["steam:440", "steam:3000000000"]"#,
+        )
+        .unwrap();
+        std::fs::write(
+            collections_path_for(&path),
+            r#"[{"id": "c1", "name": "Emulators",
+                 "member_keys": ["steam:3000000000", "steam-shortcut:3000000000", "steam:620"]}]"#,
+        )
+        .unwrap();
+
+        let lib = Library::load(&path).unwrap();
+        assert!(lib.is_external_favorite("steam:440"));
+        assert!(lib.is_external_favorite("steam-shortcut:3000000000"));
+        assert!(!lib.is_external_favorite("steam:3000000000"));
+        assert_eq!(
+            lib.collections()[0].member_keys,
+            ["steam-shortcut:3000000000", "steam:620"]
+        );
+
+        std::fs::remove_file(favorites_path_for(&path)).ok();
+        std::fs::remove_file(collections_path_for(&path)).ok();
+    }
+
+    #[test]
+    fn legacy_entry_is_not_favorite() {
+        let json = r#"{"id":"1","title":"Old","executable":"/o","cover_image":null,"tags":[],"notes":null}"#;
+        let game: CustomGame = serde_json::from_str(json).unwrap();
+        assert!(!game.favorite);
+    }
+
+    // --- collections ---
+
+    #[test]
+    fn create_collection_persists_across_reload() {
+        let path = temp_path();
+        let mut lib = Library::load(&path).unwrap();
+        let id = lib.create_collection("To Play").unwrap().id.clone();
+
+        let reloaded = Library::load(&path).unwrap();
+        assert_eq!(reloaded.collections().len(), 1);
+        assert_eq!(reloaded.collections()[0].id, id);
+        assert_eq!(reloaded.collections()[0].name, "To Play");
+        assert!(reloaded.collections()[0].member_keys.is_empty());
+
+        std::fs::remove_file(collections_path_for(&path)).ok();
+    }
+
+    #[test]
+    fn collection_membership_edits_persist() {
+        let path = temp_path();
+        let mut lib = Library::load(&path).unwrap();
+        let id = lib.create_collection("Co-op").unwrap().id.clone();
+
+        lib.add_to_collection(&id, "steam:620").unwrap();
+        lib.add_to_collection(&id, "epic:Quail").unwrap();
+        lib.add_to_collection(&id, "steam:620").unwrap();
+        let members = &lib
+            .remove_from_collection(&id, "epic:Quail")
+            .unwrap()
+            .member_keys;
+        assert_eq!(members, &["steam:620"]);
+
+        let reloaded = Library::load(&path).unwrap();
+        assert_eq!(reloaded.collections()[0].member_keys, ["steam:620"]);
+
+        std::fs::remove_file(collections_path_for(&path)).ok();
+    }
+
+    #[test]
+    fn unknown_collection_returns_error() {
+        let path = temp_path();
+        let mut lib = Library::load(&path).unwrap();
+        assert!(matches!(
+            lib.add_to_collection("missing", "steam:1"),
+            Err(LibraryError::CollectionNotFound(_))
+        ));
+        assert!(matches!(
+            lib.remove_from_collection("missing", "steam:1"),
+            Err(LibraryError::CollectionNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn removing_game_drops_it_from_every_collection() {
+        let path = temp_path();
+        let mut lib = Library::load(&path).unwrap();
+        let game_id = lib.add(make_game("Portal", "/p")).unwrap().id.clone();
+        let key = format!("custom-{game_id}");
+        let a = lib.create_collection("To Play").unwrap().id.clone();
+        let b = lib.create_collection("Puzzle").unwrap().id.clone();
+        lib.add_to_collection(&a, &key).unwrap();
+        lib.add_to_collection(&b, "steam:400").unwrap();
+        lib.add_to_collection(&b, &key).unwrap();
+
+        lib.remove(&game_id).unwrap();
+
+        let reloaded = Library::load(&path).unwrap();
+        assert!(reloaded.collections()[0].member_keys.is_empty());
+        assert_eq!(reloaded.collections()[1].member_keys, ["steam:400"]);
+
+        std::fs::remove_file(&path).ok();
+        std::fs::remove_file(collections_path_for(&path)).ok();
+    }
+
+    // --- imported store games ---
+
+    #[test]
+    fn imported_steam_game_launches_through_steam() {
+        let steam = SteamGame {
+            app_id: 620,
+            name: "Portal 2".to_string(),
+            install_dir: PathBuf::from("/steam/common/Portal 2"),
+            is_shortcut: false,
+            size_on_disk: None,
+            last_played: None,
+            cover_image: Some(PathBuf::from("/steam/cache/620.jpg")),
+            owner: None,
+        };
+        let game = CustomGame::from_steam(&steam);
+        assert_eq!(game.title, "Portal 2");
+        assert_eq!(game.executable, steam.install_dir);
+        assert_eq!(game.cover_image, steam.cover_image);
+        assert_eq!(game.launch_target(), LaunchTarget::steam(620));
+
+        let shortcut = SteamGame {
+            is_shortcut: true,
+            ..steam
+        };
+        assert_eq!(
+            CustomGame::from_steam(&shortcut).launch_target(),
+            LaunchTarget::steam_shortcut(620)
+        );
+    }
+
+    #[test]
+    fn imported_epic_game_launches_through_epic_and_survives_reload() {
+        let epic = EpicGame {
+            app_name: "Quail".to_string(),
+            display_name: "Quail Game".to_string(),
+            install_location: PathBuf::from("/epic/Quail"),
+            catalog_namespace: "ns".to_string(),
+            catalog_item_id: "item".to_string(),
+            cover_image: None,
+            launch_executable: None,
+            install_size: None,
+            app_version: None,
+        };
+        let path = temp_path();
+        let mut lib = Library::load(&path).unwrap();
+        let mut game = CustomGame::from_epic(&epic);
+        game.notes = Some("finish the DLC".to_string());
+        let id = lib.add(game).unwrap().id.clone();
+
+        let reloaded = Library::load(&path).unwrap();
+        let game = reloaded.get(&id).unwrap();
+        assert_eq!(game.notes.as_deref(), Some("finish the DLC"));
+        assert_eq!(
+            game.launch_target(),
+            LaunchTarget::epic_game(epic.launch_uri())
+        );
+        std::fs::remove_file(path).ok();
+    }
+
+    #[test]
+    fn legacy_entry_is_not_imported() {
+        let json = r#"{"id":"1","title":"Old","executable":"/o","cover_image":null,"tags":[],"notes":null}"#;
+        let game: CustomGame = serde_json::from_str(json).unwrap();
+        assert_eq!(game.imported_from, None);
+        assert!(matches!(
+            game.launch_target(),
+            LaunchTarget::Executable { .. }
+        ));
+    }
+
+    // --- tags ---
+
+    fn tagged(title: &str, tags: &[&str]) -> CustomGame {
+        CustomGame::new(
+            title,
+            format!("/games/{title}"),
+            None,
+            tags.iter().map(|t| t.to_string()).collect(),
+            None,
+        )
+    }
+
+    #[test]
+    fn all_tags_deduped_case_insensitively_and_sorted() {
+        let path = temp_path();
+        let mut lib = Library::load(&path).unwrap();
+        lib.add(tagged("Hollow Knight", &["Metroidvania", "indie"]))
+            .unwrap();
+        lib.add(tagged("Celeste", &["Indie", "platformer", " "]))
+            .unwrap();
+        lib.add(tagged("Dead Cells", &["metroidvania", "Roguelike"]))
+            .unwrap();
+
+        assert_eq!(
+            lib.all_tags(),
+            ["indie", "Metroidvania", "platformer", "Roguelike"]
+        );
+        std::fs::remove_file(path).ok();
+    }
+
+    #[test]
+    fn games_with_tag_matches_any_casing() {
+        let path = temp_path();
+        let mut lib = Library::load(&path).unwrap();
+        lib.add(tagged("Hollow Knight", &["Metroidvania", "indie"]))
+            .unwrap();
+        lib.add(tagged("Celeste", &["Indie"])).unwrap();
+        lib.add(tagged("Doom", &["FPS"])).unwrap();
+
+        let titles: Vec<_> = lib
+            .games_with_tag("INDIE")
+            .iter()
+            .map(|g| g.title.as_str())
+            .collect();
+        assert_eq!(titles, ["Hollow Knight", "Celeste"]);
+        assert!(lib.games_with_tag("racing").is_empty());
+        std::fs::remove_file(path).ok();
+    }
+
     // --- get ---
 
     #[test]
@@ -288,4 +3069,23 @@ mod tests {
         assert!(lib.get("no-such-id").is_none());
         std::fs::remove_file(path).ok();
     }
+
+    #[test]
+    fn get_reflects_updates_and_reloads() {
+        let path = temp_path();
+        let mut lib = Library::load(&path).unwrap();
+        let game = lib.add(make_game("Draft", "/draft")).unwrap().clone();
+
+        let mut other = Library::load(&path).unwrap();
+        other
+            .update(CustomGame {
+                title: "Final".to_string(),
+                ..game.clone()
+            })
+            .unwrap();
+        assert_eq!(lib.get(&game.id).unwrap().title, "Draft");
+        lib.reload().unwrap();
+        assert_eq!(lib.get(&game.id).unwrap().title, "Final");
+        std::fs::remove_file(path).ok();
+    }
 }
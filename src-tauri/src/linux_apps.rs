@@ -0,0 +1,367 @@
+use crate::library::CustomGame;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+/// A game found through a freedesktop `.desktop` launcher.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DesktopGame {
+    /// Display name from the `Name` key.
+    pub name: String,
+    /// Program from the `Exec` key: an absolute path or a name looked up on `PATH`.
+    pub executable: PathBuf,
+    /// Remaining `Exec` arguments, unquoted and with field codes (`%U`, `%f`, ...) removed.
+    pub args: Vec<String>,
+    /// `Icon` value: an absolute image path or a theme icon name.
+    pub icon: Option<String>,
+    /// Semicolon-separated `Categories`, e.g. `["Game", "ActionGame"]`.
+    pub categories: Vec<String>,
+    /// The `.desktop` file this entry came from.
+    pub desktop_file: PathBuf,
+}
+
+impl DesktopGame {
+    /// Whether the entry is in the `Game` category.
+    pub fn is_game(&self) -> bool {
+        self.categories.iter().any(|c| c == "Game")
+    }
+
+    /// Converts the entry into a library game that runs the same command line. Only an
+    /// absolute icon path becomes the cover; theme icon names can't be shown directly.
+    pub fn to_custom_game(&self) -> CustomGame {
+        let cover = self
+            .icon
+            .as_deref()
+            .map(PathBuf::from)
+            .filter(|p| p.is_absolute());
+        CustomGame {
+            args: self.args.clone(),
+            ..CustomGame::new(&self.name, &self.executable, cover, vec![], None)
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Public API
+// ---------------------------------------------------------------------------
+
+/// Returns games from the `.desktop` files in the user's and system application
+/// directories. A user entry hides a system entry with the same file name, as in menus.
+pub fn discover_games() -> Vec<DesktopGame> {
+    let mut games: Vec<DesktopGame> = Vec::new();
+    for dir in application_dirs() {
+        for game in discover_games_from(&dir) {
+            let shadowed = games
+                .iter()
+                .any(|g| g.desktop_file.file_name() == game.desktop_file.file_name());
+            if !shadowed {
+                games.push(game);
+            }
+        }
+    }
+    games
+}
+
+/// Discovers games from the `.desktop` files directly inside `dir` (used in tests).
+/// Entries outside the `Game` category, hidden or not launchable are skipped.
+pub fn discover_games_from(dir: &Path) -> Vec<DesktopGame> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return vec![];
+    };
+    let mut games: Vec<DesktopGame> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.extension().and_then(|e| e.to_str()) == Some("desktop"))
+        .filter_map(|p| {
+            let contents = std::fs::read_to_string(&p).ok()?;
+            parse_desktop_entry(&contents, &p)
+        })
+        .filter(DesktopGame::is_game)
+        .collect();
+    games.sort_by_key(|g| g.name.to_lowercase());
+    games
+}
+
+/// Parses the `[Desktop Entry]` group of a `.desktop` file. Returns `None` for entries
+/// that aren't applications, are marked `Hidden`/`NoDisplay`, or lack `Name` or `Exec`.
+pub fn parse_desktop_entry(contents: &str, path: &Path) -> Option<DesktopGame> {
+    let mut in_entry = false;
+    let mut name = None;
+    let mut exec = None;
+    let mut icon = None;
+    let mut categories = Vec::new();
+    let mut entry_type = None;
+    let mut hidden = false;
+
+    for line in contents.lines().map(str::trim) {
+        if line.starts_with('[') {
+            in_entry = line == "[Desktop Entry]";
+            continue;
+        }
+        if !in_entry || line.starts_with('#') {
+            continue;
+        }
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let value = unescape_value(value.trim());
+        // Localized keys like `Name[de]` don't match and are ignored.
+        match key.trim() {
+            "Name" => name = Some(value),
+            "Exec" => exec = Some(value),
+            "Icon" => icon = Some(value),
+            "Type" => entry_type = Some(value),
+            "Categories" => {
+                categories = value
+                    .split(';')
+                    .filter(|c| !c.is_empty())
+                    .map(String::from)
+                    .collect()
+            }
+            "Hidden" | "NoDisplay" => hidden |= value == "true",
+            _ => {}
+        }
+    }
+
+    if entry_type.as_deref() != Some("Application") || hidden {
+        return None;
+    }
+    let mut command = split_exec(&exec?).into_iter();
+    let executable = PathBuf::from(command.next()?);
+    Some(DesktopGame {
+        name: name.filter(|n| !n.is_empty())?,
+        executable,
+        args: command.collect(),
+        icon: icon.filter(|i| !i.is_empty()),
+        categories,
+        desktop_file: path.to_path_buf(),
+    })
+}
+
+// ---------------------------------------------------------------------------
+// Private helpers
+// ---------------------------------------------------------------------------
+
+/// Returns the `applications` directories to scan, user directory first.
+fn application_dirs() -> Vec<PathBuf> {
+    #[cfg(target_os = "linux")]
+    {
+        let mut dirs = Vec::new();
+        let data_home = std::env::var("XDG_DATA_HOME")
+            .map(PathBuf::from)
+            .ok()
+            .or_else(|| {
+                std::env::var("HOME")
+                    .ok()
+                    .map(|h| PathBuf::from(h).join(".local/share"))
+            });
+        dirs.extend(data_home);
+        let data_dirs = std::env::var("XDG_DATA_DIRS")
+            .ok()
+            .filter(|d| !d.is_empty())
+            .unwrap_or_else(|| "/usr/local/share:/usr/share".to_string());
+        dirs.extend(data_dirs.split(':').map(PathBuf::from));
+        dirs.into_iter().map(|d| d.join("applications")).collect()
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        vec![]
+    }
+}
+
+/// Resolves the value-level escapes of the desktop entry format (`\s`, `\n`, `\t`,
+/// `\r`). Backslashes, including an escaped `\\`, are left for [`split_exec`] to interpret.
+fn unescape_value(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('s') => out.push(' '),
+            Some('n') => out.push('\n'),
+            Some('t') => out.push('\t'),
+            Some('r') => out.push('\r'),
+            Some('\\') => out.push_str("\\\\"),
+            Some(other) => {
+                out.push('\\');
+                out.push(other);
+            }
+            None => out.push('\\'),
+        }
+    }
+    out
+}
+
+/// Splits an `Exec` value into arguments. Double-quoted arguments may contain spaces and
+/// the escapes `\"`, `` \` ``, `\$` and `\\`. Field codes such as `%U` or `%f` are
+/// dropped, since we launch without files or URLs, and `%%` becomes `%`.
+fn split_exec(exec: &str) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+    let mut has_arg = false;
+    let mut chars = exec.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => {
+                in_quotes = !in_quotes;
+                has_arg = true;
+            }
+            '\\' if in_quotes => {
+                if let Some(&next) = chars.peek() {
+                    if matches!(next, '"' | '`' | '$' | '\\') {
+                        current.push(next);
+                        chars.next();
+                        continue;
+                    }
+                }
+                current.push('\\');
+            }
+            '\\' => {
+                if let Some(next) = chars.next() {
+                    current.push(next);
+                }
+                has_arg = true;
+            }
+            c if c.is_whitespace() && !in_quotes => {
+                if has_arg || !current.is_empty() {
+                    args.push(std::mem::take(&mut current));
+                    has_arg = false;
+                }
+            }
+            '%' if !in_quotes => match chars.next() {
+                Some('%') => current.push('%'),
+                // Any other field code expands to nothing.
+                Some(_) => {}
+                None => current.push('%'),
+            },
+            c => {
+                current.push(c);
+                has_arg = true;
+            }
+        }
+    }
+    if has_arg || !current.is_empty() {
+        args.push(current);
+    }
+    args
+}
+
+// ============================================================
+// Tests
+// ============================================================
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    const SUPERTUX: &str = r#"[Desktop Entry]
+Version=1.0
+Type=Application
+Name=SuperTux
+Name[de]=SuperTux (Deutsch)
+Comment=Classic 2D jump'n run sidescroller game
+# The launcher accepts files to open
+Exec="/opt/Super Tux/bin/supertux2" --renderer=auto %U
+Icon=supertux
+Categories=Game;ArcadeGame;
+Keywords=platformer;
+
+[Desktop Action Editor]
+Name=Level Editor
+Exec=supertux2 --editor
+"#;
+
+    fn make_temp_dir(label: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("linux_apps_test_{}_{}", label, std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn parses_representative_entry() {
+        let path = Path::new("/usr/share/applications/supertux2.desktop");
+        let game = parse_desktop_entry(SUPERTUX, path).expect("should parse");
+        assert_eq!(game.name, "SuperTux");
+        assert_eq!(
+            game.executable,
+            PathBuf::from("/opt/Super Tux/bin/supertux2")
+        );
+        assert_eq!(game.args, ["--renderer=auto"]);
+        assert_eq!(game.icon.as_deref(), Some("supertux"));
+        assert_eq!(game.categories, ["Game", "ArcadeGame"]);
+        assert!(game.is_game());
+        assert_eq!(game.desktop_file, path);
+    }
+
+    #[test]
+    fn exec_field_codes_and_quoting() {
+        assert_eq!(split_exec("game %f"), ["game"]);
+        assert_eq!(split_exec("game %U --full"), ["game", "--full"]);
+        assert_eq!(
+            split_exec("game --progress=50%%"),
+            ["game", "--progress=50%"]
+        );
+        assert_eq!(
+            split_exec(r#"sh -c "echo \"hi\" \$HOME""#),
+            ["sh", "-c", r#"echo "hi" $HOME"#]
+        );
+        assert_eq!(split_exec(r#"game """#), ["game", ""]);
+        assert_eq!(
+            split_exec(&unescape_value(r#""/opt/My\sGame/run""#)),
+            ["/opt/My Game/run"]
+        );
+    }
+
+    #[test]
+    fn hidden_and_non_application_entries_skipped() {
+        let path = Path::new("/x.desktop");
+        let hidden = SUPERTUX.replace("Type=Application", "Type=Application\nNoDisplay=true");
+        assert_eq!(parse_desktop_entry(&hidden, path), None);
+        let link = SUPERTUX.replace("Type=Application", "Type=Link");
+        assert_eq!(parse_desktop_entry(&link, path), None);
+        let no_exec = "[Desktop Entry]\nType=Application\nName=Broken\n";
+        assert_eq!(parse_desktop_entry(no_exec, path), None);
+    }
+
+    #[test]
+    fn discovery_keeps_only_games() {
+        let dir = make_temp_dir("discover");
+        fs::write(dir.join("supertux2.desktop"), SUPERTUX).unwrap();
+        fs::write(
+            dir.join("gimp.desktop"),
+            "[Desktop Entry]\nType=Application\nName=GIMP\nExec=gimp %U\nCategories=Graphics;\n",
+        )
+        .unwrap();
+        fs::write(dir.join("notes.txt"), "not a desktop file").unwrap();
+
+        let games = discover_games_from(&dir);
+        let names: Vec<&str> = games.iter().map(|g| g.name.as_str()).collect();
+        assert_eq!(names, ["SuperTux"]);
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn converts_to_custom_game() {
+        let mut game = parse_desktop_entry(SUPERTUX, Path::new("/a.desktop")).unwrap();
+        let custom = game.to_custom_game();
+        assert_eq!(custom.title, "SuperTux");
+        assert_eq!(custom.args, ["--renderer=auto"]);
+        assert_eq!(custom.cover_image, None, "theme icon names aren't covers");
+
+        game.icon = Some("/usr/share/pixmaps/supertux.png".to_string());
+        assert_eq!(
+            game.to_custom_game().cover_image,
+            Some(PathBuf::from("/usr/share/pixmaps/supertux.png"))
+        );
+    }
+}
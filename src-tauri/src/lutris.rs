@@ -0,0 +1,297 @@
+use crate::launcher::{find_in_path, LaunchTarget};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+/// A game installed through Lutris, from its `games/<slug>-<id>.yml` config.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LutrisGame {
+    /// Lutris identifier used in `lutris:rungame/<slug>`.
+    pub slug: String,
+    /// Display name: the config's `name`, or one derived from the slug.
+    pub name: String,
+    /// Runner such as `wine` or `linux`, when the config names one.
+    pub runner: Option<String>,
+    /// Game executable or main file, when the config has one.
+    pub executable: Option<PathBuf>,
+    /// The `.yml` file this entry came from.
+    pub config_file: PathBuf,
+}
+
+impl LutrisGame {
+    /// Launches through the Lutris CLI when it is on `PATH`, so runners, Wine prefixes
+    /// and per-game options all apply. Without it, only native (`linux` runner) games
+    /// can run, by spawning their executable directly.
+    pub fn launch_target(&self) -> Option<LaunchTarget> {
+        self.launch_target_with(find_in_path("lutris", std::env::var_os("PATH").as_deref()))
+    }
+
+    fn launch_target_with(&self, lutris: Option<PathBuf>) -> Option<LaunchTarget> {
+        if let Some(lutris) = lutris {
+            return Some(LaunchTarget::executable_with(
+                lutris.to_string_lossy(),
+                crate::launcher::SpawnOptions {
+                    args: vec![format!("lutris:rungame/{}", self.slug)],
+                    ..Default::default()
+                },
+            ));
+        }
+        match (self.runner.as_deref(), &self.executable) {
+            (Some("linux"), Some(exe)) => Some(LaunchTarget::executable(exe.to_string_lossy())),
+            _ => None,
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Config schema (only fields we care about)
+// ---------------------------------------------------------------------------
+
+#[derive(Deserialize, Default)]
+struct GameConfig {
+    name: Option<String>,
+    runner: Option<String>,
+    #[serde(default)]
+    game: GameSection,
+    /// Other top-level sections; one named after the runner holds its options.
+    #[serde(flatten)]
+    sections: BTreeMap<String, serde_yaml::Value>,
+}
+
+#[derive(Deserialize, Default)]
+struct GameSection {
+    /// Executable for the `wine` and `linux` runners.
+    exe: Option<String>,
+    /// ROM or data file for emulator runners such as `libretro` and `mame`.
+    main_file: Option<String>,
+}
+
+/// Runners Lutris ships, matched against a config's top-level section names when it
+/// doesn't say `runner:` outright.
+const KNOWN_RUNNERS: &[&str] = &[
+    "dosbox", "flatpak", "libretro", "linux", "mame", "scummvm", "steam", "web", "wine",
+];
+
+// ---------------------------------------------------------------------------
+// Public API
+// ---------------------------------------------------------------------------
+
+/// Returns the games from the Lutris config directories (Linux only; empty elsewhere).
+pub fn discover_games() -> Vec<LutrisGame> {
+    config_dirs()
+        .iter()
+        .flat_map(|dir| discover_games_from(dir))
+        .collect()
+}
+
+/// Parses every `.yml` game config directly inside `config_dir`, sorted by name.
+/// Configs that can't be read or parsed are logged and skipped.
+pub fn discover_games_from(config_dir: &Path) -> Vec<LutrisGame> {
+    let Ok(entries) = std::fs::read_dir(config_dir) else {
+        return vec![];
+    };
+    let mut games: Vec<LutrisGame> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.extension().and_then(|e| e.to_str()) == Some("yml"))
+        .filter_map(|p| {
+            let contents = std::fs::read_to_string(&p).ok()?;
+            parse_game_config(&contents, &p)
+        })
+        .collect();
+    games.sort_by_key(|g| g.name.to_lowercase());
+    games
+}
+
+/// Parses one game config. Every field is optional; the slug comes from the file name.
+pub fn parse_game_config(contents: &str, path: &Path) -> Option<LutrisGame> {
+    let config: GameConfig = match serde_yaml::from_str(contents) {
+        Ok(config) => config,
+        Err(e) => {
+            log::warn!("Skipping unreadable Lutris config {:?}: {}", path, e);
+            return None;
+        }
+    };
+    let slug = slug_from_path(path)?;
+    let runner = config.runner.or_else(|| {
+        KNOWN_RUNNERS
+            .iter()
+            .find(|r| config.sections.contains_key(**r))
+            .map(|r| r.to_string())
+    });
+    Some(LutrisGame {
+        name: config
+            .name
+            .filter(|n| !n.trim().is_empty())
+            .unwrap_or_else(|| name_from_slug(&slug)),
+        slug,
+        runner,
+        executable: config
+            .game
+            .exe
+            .or(config.game.main_file)
+            .filter(|e| !e.is_empty())
+            .map(PathBuf::from),
+        config_file: path.to_path_buf(),
+    })
+}
+
+// ---------------------------------------------------------------------------
+// Private helpers
+// ---------------------------------------------------------------------------
+
+/// Lutris names configs `<slug>-<unix time>.yml`; older ones are just `<slug>.yml`.
+fn slug_from_path(path: &Path) -> Option<String> {
+    let stem = path.file_stem()?.to_str()?;
+    let slug = match stem.rsplit_once('-') {
+        Some((slug, id)) if !id.is_empty() && id.bytes().all(|b| b.is_ascii_digit()) => slug,
+        _ => stem,
+    };
+    Some(slug.to_string()).filter(|s| !s.is_empty())
+}
+
+/// `the-witcher-3` → `The Witcher 3`.
+fn name_from_slug(slug: &str) -> String {
+    slug.split('-')
+        .filter(|w| !w.is_empty())
+        .map(|w| {
+            let mut chars = w.chars();
+            chars
+                .next()
+                .map(|c| c.to_uppercase().chain(chars).collect::<String>())
+                .unwrap_or_default()
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Directories Lutris keeps game configs in: `~/.config/lutris/games`, and
+/// `~/.local/share/lutris/games` for newer releases.
+fn config_dirs() -> Vec<PathBuf> {
+    #[cfg(target_os = "linux")]
+    {
+        let home = std::env::var("HOME").map(PathBuf::from).ok();
+        let config_home = std::env::var("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .ok()
+            .or_else(|| home.as_ref().map(|h| h.join(".config")));
+        let data_home = std::env::var("XDG_DATA_HOME")
+            .map(PathBuf::from)
+            .ok()
+            .or_else(|| home.as_ref().map(|h| h.join(".local/share")));
+        config_home
+            .into_iter()
+            .chain(data_home)
+            .map(|d| d.join("lutris/games"))
+            .collect()
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        vec![]
+    }
+}
+
+// ============================================================
+// Tests
+// ============================================================
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    const WITCHER: &str = r#"
+game:
+  exe: /home/me/Games/the-witcher-3/drive_c/GOG Games/The Witcher 3/bin/x64/witcher3.exe
+  prefix: /home/me/Games/the-witcher-3
+system:
+  gamemode: true
+wine:
+  version: lutris-GE-Proton8-26-x86_64
+  dxvk: true
+"#;
+
+    const SUPERTUXKART: &str = r#"
+name: SuperTuxKart
+runner: linux
+game:
+  exe: /opt/supertuxkart/run_game.sh
+  args: --no-start-screen
+"#;
+
+    fn make_temp_dir() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("lutris_test_{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn parses_wine_config_without_name() {
+        let path = Path::new("/lutris/games/the-witcher-3-1699999999.yml");
+        let game = parse_game_config(WITCHER, path).expect("should parse");
+        assert_eq!(game.slug, "the-witcher-3");
+        assert_eq!(game.name, "The Witcher 3");
+        assert_eq!(game.runner.as_deref(), Some("wine"));
+        assert_eq!(
+            game.executable,
+            Some(PathBuf::from(
+                "/home/me/Games/the-witcher-3/drive_c/GOG Games/The Witcher 3/bin/x64/witcher3.exe"
+            ))
+        );
+    }
+
+    #[test]
+    fn missing_fields_and_bad_yaml_are_tolerated() {
+        let bare = parse_game_config("system: {}\n", Path::new("/g/celeste.yml")).unwrap();
+        assert_eq!(bare.slug, "celeste");
+        assert_eq!(bare.name, "Celeste");
+        assert_eq!(bare.runner, None);
+        assert_eq!(bare.executable, None);
+        assert_eq!(
+            parse_game_config("game: [unclosed", Path::new("/g/x.yml")),
+            None
+        );
+    }
+
+    #[test]
+    fn discovers_configs_sorted_by_name() {
+        let dir = make_temp_dir();
+        fs::write(dir.join("the-witcher-3-1699999999.yml"), WITCHER).unwrap();
+        fs::write(dir.join("supertuxkart-1700000000.yml"), SUPERTUXKART).unwrap();
+        fs::write(dir.join("notes.txt"), "not a config").unwrap();
+
+        let games = discover_games_from(&dir);
+        let names: Vec<&str> = games.iter().map(|g| g.name.as_str()).collect();
+        assert_eq!(names, ["SuperTuxKart", "The Witcher 3"]);
+        assert_eq!(games[0].runner.as_deref(), Some("linux"));
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn launches_through_lutris_cli_or_native_exe() {
+        let path = Path::new("/g/supertuxkart-1700000000.yml");
+        let native = parse_game_config(SUPERTUXKART, path).unwrap();
+        let lutris = PathBuf::from("/usr/bin/lutris");
+        assert_eq!(
+            native.launch_target_with(Some(lutris)),
+            Some(LaunchTarget::executable_with(
+                "/usr/bin/lutris",
+                crate::launcher::SpawnOptions {
+                    args: vec!["lutris:rungame/supertuxkart".to_string()],
+                    ..Default::default()
+                }
+            ))
+        );
+        assert_eq!(
+            native.launch_target_with(None),
+            Some(LaunchTarget::executable("/opt/supertuxkart/run_game.sh"))
+        );
+
+        let wine = parse_game_config(WITCHER, Path::new("/g/the-witcher-3.yml")).unwrap();
+        assert_eq!(wine.launch_target_with(None), None, "wine needs Lutris");
+    }
+}
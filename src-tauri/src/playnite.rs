@@ -0,0 +1,250 @@
+use crate::library::CustomGame;
+use crate::steam::split_launch_options;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+// ---------------------------------------------------------------------------
+// Export schema (only fields we care about)
+// ---------------------------------------------------------------------------
+
+/// One game from a Playnite JSON library export. Playnite writes PascalCase keys.
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ExportedGame {
+    name: Option<String>,
+    install_directory: Option<String>,
+    #[serde(default)]
+    game_actions: Option<Vec<GameAction>>,
+    #[serde(default)]
+    categories: Option<Vec<Named>>,
+    #[serde(default)]
+    tags: Option<Vec<Named>>,
+    source: Option<Named>,
+    notes: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct GameAction {
+    /// `"File"` (or `0`) for a program; URL and emulator actions aren't importable.
+    #[serde(rename = "Type")]
+    kind: Option<serde_json::Value>,
+    path: Option<String>,
+    arguments: Option<String>,
+    working_dir: Option<String>,
+    #[serde(default)]
+    is_play_action: bool,
+}
+
+/// Categories, tags and sources are exported either as `{"Name": ...}` objects or,
+/// by some exporter add-ons, as plain strings.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Named {
+    Plain(String),
+    Object {
+        #[serde(rename = "Name")]
+        name: Option<String>,
+    },
+}
+
+impl Named {
+    fn name(&self) -> Option<&str> {
+        match self {
+            Named::Plain(name) => Some(name.as_str()),
+            Named::Object { name } => name.as_deref(),
+        }
+        .map(str::trim)
+        .filter(|n| !n.is_empty())
+    }
+}
+
+impl GameAction {
+    fn is_file(&self) -> bool {
+        match &self.kind {
+            None => true,
+            Some(serde_json::Value::String(kind)) => kind.eq_ignore_ascii_case("file"),
+            Some(serde_json::Value::Number(kind)) => kind.as_u64() == Some(0),
+            Some(_) => false,
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Public API
+// ---------------------------------------------------------------------------
+
+/// Turns a Playnite JSON export (an array of games) into custom games.
+///
+/// Each game runs the program of its play action (or its first file action), with
+/// `{InstallDir}` expanded and relative paths resolved against the install directory.
+/// Categories, tags and the source (`Steam`, `GOG`, ...) all become tags. Games with no
+/// runnable program, such as uninstalled ones or those launched by URL, are skipped.
+pub fn parse_export(json: &str) -> Result<Vec<CustomGame>, serde_json::Error> {
+    let exported: Vec<ExportedGame> = serde_json::from_str(json)?;
+    let total = exported.len();
+    let games: Vec<CustomGame> = exported.into_iter().filter_map(to_custom_game).collect();
+    log::info!(
+        "Playnite export: {} of {} game(s) have a runnable program",
+        games.len(),
+        total
+    );
+    Ok(games)
+}
+
+// ---------------------------------------------------------------------------
+// Private helpers
+// ---------------------------------------------------------------------------
+
+fn to_custom_game(exported: ExportedGame) -> Option<CustomGame> {
+    let title = exported.name.as_deref().map(str::trim).unwrap_or_default();
+    if title.is_empty() {
+        return None;
+    }
+    let install_dir = exported
+        .install_directory
+        .as_deref()
+        .filter(|d| !d.is_empty());
+    let actions = exported.game_actions.unwrap_or_default();
+    let mut runnable = actions
+        .iter()
+        .filter(|a| a.is_file() && a.path.as_deref().is_some_and(|p| !p.is_empty()));
+    let action = runnable
+        .clone()
+        .find(|a| a.is_play_action)
+        .or_else(|| runnable.next());
+    let Some(action) = action else {
+        log::debug!("Skipping Playnite game without a program: {:?}", title);
+        return None;
+    };
+    let executable = resolve(action.path.as_deref()?, install_dir);
+
+    let mut tags: Vec<String> = Vec::new();
+    let names = exported
+        .categories
+        .iter()
+        .chain(&exported.tags)
+        .flatten()
+        .chain(&exported.source);
+    for name in names.filter_map(Named::name) {
+        if !tags.iter().any(|t| t.eq_ignore_ascii_case(name)) {
+            tags.push(name.to_string());
+        }
+    }
+
+    let notes = exported.notes.filter(|n| !n.trim().is_empty());
+    let mut game = CustomGame::new(title, executable, None, tags, notes);
+    game.args = action
+        .arguments
+        .as_deref()
+        .map(split_launch_options)
+        .unwrap_or_default();
+    game.working_dir = action
+        .working_dir
+        .as_deref()
+        .filter(|d| !d.is_empty())
+        .map(|d| resolve(d, install_dir))
+        .or_else(|| install_dir.map(PathBuf::from));
+    Some(game)
+}
+
+/// Expands Playnite's `{InstallDir}` variable and makes a relative path absolute
+/// against the install directory.
+fn resolve(path: &str, install_dir: Option<&str>) -> PathBuf {
+    let Some(install_dir) = install_dir else {
+        return PathBuf::from(path);
+    };
+    let expanded = path.replace("{InstallDir}", install_dir);
+    if is_absolute(&expanded) {
+        PathBuf::from(expanded)
+    } else {
+        Path::new(install_dir).join(expanded)
+    }
+}
+
+/// Playnite paths are Windows paths, so a drive letter or UNC prefix counts as
+/// absolute on every host.
+fn is_absolute(path: &str) -> bool {
+    let bytes = path.as_bytes();
+    Path::new(path).is_absolute()
+        || path.starts_with("\\\\")
+        || (bytes.len() >= 3 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':')
+}
+
+// ============================================================
+// Tests
+// ============================================================
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXPORT: &str = r#"[
+      {
+        "Name": "Hollow Knight",
+        "InstallDirectory": "D:\\Games\\Hollow Knight",
+        "IsInstalled": true,
+        "GameActions": [
+          { "Name": "Manual", "Type": "URL", "Path": "https://example.com", "IsPlayAction": false },
+          { "Name": "Play", "Type": "File", "Path": "{InstallDir}\\hollow_knight.exe",
+            "Arguments": "-screen-fullscreen 1", "WorkingDir": "{InstallDir}", "IsPlayAction": true }
+        ],
+        "Categories": [ { "Name": "Metroidvania" } ],
+        "Tags": [ { "Name": "Finished" }, { "Name": "metroidvania" } ],
+        "Source": { "Name": "GOG" },
+        "Notes": "Steel Soul run pending"
+      },
+      {
+        "Name": "Doom",
+        "InstallDirectory": "C:\\Games\\Doom",
+        "GameActions": [ { "Type": 0, "Path": "gzdoom.exe", "IsPlayAction": true } ],
+        "Categories": null,
+        "Source": "Manual"
+      },
+      {
+        "Name": "Portal 2",
+        "InstallDirectory": null,
+        "GameActions": [ { "Type": "URL", "Path": "steam://rungameid/620", "IsPlayAction": true } ],
+        "Source": { "Name": "Steam" }
+      },
+      { "Name": "Not Installed", "GameActions": null }
+    ]"#;
+
+    #[test]
+    fn parses_runnable_games_with_tags_and_notes() {
+        let games = parse_export(EXPORT).unwrap();
+        let titles: Vec<&str> = games.iter().map(|g| g.title.as_str()).collect();
+        assert_eq!(titles, ["Hollow Knight", "Doom"]);
+
+        let hk = &games[0];
+        assert_eq!(
+            hk.executable,
+            PathBuf::from("D:\\Games\\Hollow Knight\\hollow_knight.exe")
+        );
+        assert_eq!(hk.args, ["-screen-fullscreen", "1"]);
+        assert_eq!(
+            hk.working_dir,
+            Some(PathBuf::from("D:\\Games\\Hollow Knight"))
+        );
+        assert_eq!(hk.tags, ["Metroidvania", "Finished", "GOG"]);
+        assert_eq!(hk.notes.as_deref(), Some("Steel Soul run pending"));
+    }
+
+    #[test]
+    fn relative_paths_resolve_against_install_dir() {
+        let games = parse_export(EXPORT).unwrap();
+        let doom = &games[1];
+        assert_eq!(
+            doom.executable,
+            Path::new("C:\\Games\\Doom").join("gzdoom.exe")
+        );
+        assert_eq!(doom.tags, ["Manual"]);
+        assert_eq!(doom.notes, None);
+        assert_eq!(doom.working_dir, Some(PathBuf::from("C:\\Games\\Doom")));
+    }
+
+    #[test]
+    fn rejects_exports_that_are_not_a_game_list() {
+        assert!(parse_export("{\"Name\": \"Doom\"}").is_err());
+        assert!(parse_export("[]").unwrap().is_empty());
+    }
+}
@@ -0,0 +1,198 @@
+use crate::library::SortOrder;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use thiserror::Error;
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Error)]
+pub enum SettingsError {
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Serialization error: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+/// App-wide user preferences.
+///
+/// Fields missing from an older settings file take their default values.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct Settings {
+    /// Show dot-files in the file explorer when a listing doesn't say otherwise.
+    pub show_hidden: bool,
+    /// Steam folder to use instead of the platform default, e.g. on an external drive.
+    pub steam_root_override: Option<PathBuf>,
+    /// Order custom games are listed in when the frontend doesn't ask for one.
+    pub sort_order: SortOrder,
+    /// Copy picked covers, heroes and screenshots into the library's managed `covers`
+    /// directory.
+    pub manage_covers: bool,
+    /// Leave Steam apps that look like tools (Proton, runtimes, soundtracks) out of
+    /// discovery; see [`crate::steam::is_probably_tool`].
+    pub hide_steam_tools: bool,
+    /// Steam app ids the user hid from discovery, whether tools or games.
+    pub hidden_steam_apps: Vec<u32>,
+}
+
+/// The user's [`Settings`], persisted as JSON at a fixed path.
+#[derive(Debug)]
+pub struct SettingsStore {
+    path: PathBuf,
+    settings: Settings,
+}
+
+// ---------------------------------------------------------------------------
+// Public API
+// ---------------------------------------------------------------------------
+
+impl SettingsStore {
+    /// Loads settings from `path`. A missing file is created with the defaults; an
+    /// unreadable or corrupt one is logged and left alone, and the defaults are used for
+    /// this session.
+    pub fn load(path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        if !path.exists() {
+            log::info!("No settings file at {:?}, writing defaults", path);
+            let store = Self {
+                path,
+                settings: Settings::default(),
+            };
+            if let Err(e) = store.persist() {
+                log::warn!("Failed to write default settings {:?}: {}", store.path, e);
+            }
+            return store;
+        }
+        let settings = match std::fs::read_to_string(&path) {
+            Ok(contents) => serde_json::from_str(&contents).unwrap_or_else(|e| {
+                log::warn!("Ignoring corrupt settings file {:?}: {}", path, e);
+                Settings::default()
+            }),
+            Err(e) => {
+                log::warn!("Ignoring unreadable settings file {:?}: {}", path, e);
+                Settings::default()
+            }
+        };
+        Self { path, settings }
+    }
+
+    pub fn settings(&self) -> &Settings {
+        &self.settings
+    }
+
+    /// Replaces the settings and writes them to disk.
+    pub fn update(&mut self, settings: Settings) -> Result<&Settings, SettingsError> {
+        log::info!("Updating settings: {:?}", settings);
+        self.settings = settings;
+        self.persist()?;
+        Ok(&self.settings)
+    }
+
+    /// Changes settings in place with `edit` and writes them to disk. Unlike
+    /// [`SettingsStore::update`], fields `edit` doesn't touch keep their current values
+    /// even when another caller changed them since this one last read the settings.
+    pub fn update_with(
+        &mut self,
+        edit: impl FnOnce(&mut Settings),
+    ) -> Result<&Settings, SettingsError> {
+        edit(&mut self.settings);
+        log::info!("Updating settings: {:?}", self.settings);
+        self.persist()?;
+        Ok(&self.settings)
+    }
+
+    fn persist(&self) -> Result<(), SettingsError> {
+        let json = serde_json::to_string_pretty(&self.settings)?;
+        crate::library::write_atomic(&self.path, &json)?;
+        Ok(())
+    }
+}
+
+// ============================================================
+// Tests
+// ============================================================
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    fn temp_dir() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("settings_test_{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn missing_file_yields_defaults_and_is_written() {
+        let dir = temp_dir();
+        let path = dir.join("settings.json");
+
+        let store = SettingsStore::load(&path);
+        assert_eq!(store.settings(), &Settings::default());
+        assert_eq!(store.settings().sort_order, SortOrder::TitleAsc);
+        assert!(!store.settings().manage_covers);
+        assert!(path.is_file(), "defaults should be written on first load");
+
+        std::fs::remove_dir_all(dir).ok();
+    }
+
+    #[test]
+    fn update_round_trips() {
+        let dir = temp_dir();
+        let path = dir.join("settings.json");
+        let mut store = SettingsStore::load(&path);
+
+        let settings = Settings {
+            show_hidden: true,
+            steam_root_override: Some(PathBuf::from("/mnt/games/Steam")),
+            sort_order: SortOrder::RecentlyAdded,
+            manage_covers: true,
+            hide_steam_tools: true,
+            hidden_steam_apps: vec![228980],
+        };
+        store.update(settings.clone()).unwrap();
+        let reloaded = SettingsStore::load(&path);
+        assert_eq!(reloaded.settings(), &settings);
+
+        std::fs::remove_dir_all(dir).ok();
+    }
+
+    #[test]
+    fn update_with_changes_only_the_edited_field() {
+        let dir = temp_dir();
+        let path = dir.join("settings.json");
+        let mut store = SettingsStore::load(&path);
+        store
+            .update_with(|s| s.sort_order = SortOrder::TitleDesc)
+            .unwrap();
+        store.update_with(|s| s.manage_covers = true).unwrap();
+
+        let reloaded = SettingsStore::load(&path);
+        assert_eq!(reloaded.settings().sort_order, SortOrder::TitleDesc);
+        assert!(reloaded.settings().manage_covers);
+
+        std::fs::remove_dir_all(dir).ok();
+    }
+
+    #[test]
+    fn corrupt_or_partial_files_fall_back_to_defaults() {
+        let dir = temp_dir();
+        let path = dir.join("settings.json");
+
+        std::fs::write(&path, "{}").unwrap();
+        assert_eq!(SettingsStore::load(&path).settings(), &Settings::default());
+        std::fs::write(&path, "{ not json").unwrap();
+        assert_eq!(SettingsStore::load(&path).settings(), &Settings::default());
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "{ not json",
+            "a corrupt file is left for the user to fix"
+        );
+        // A directory where the file should be can't be read at all.
+        assert_eq!(SettingsStore::load(&dir).settings(), &Settings::default());
+
+        std::fs::remove_dir_all(dir).ok();
+    }
+}
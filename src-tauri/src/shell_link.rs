@@ -0,0 +1,274 @@
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+/// What a Windows `.lnk` shortcut points at, per the MS-SHLLINK format.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShellLink {
+    /// Absolute path of the link target, e.g. `C:\Games\Celeste\Celeste.exe`.
+    pub target: PathBuf,
+    /// Command-line arguments stored in the link, split into words.
+    pub args: Vec<String>,
+    /// Directory the target should start in, when the link sets one.
+    pub working_dir: Option<PathBuf>,
+}
+
+#[derive(Debug, Error)]
+pub enum ShellLinkError {
+    #[error("Not a Windows shortcut file")]
+    NotAShellLink,
+    #[error("Shortcut file is truncated")]
+    Truncated,
+    #[error("Shortcut has no file target")]
+    NoTarget,
+    #[error("Failed to read shortcut: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// `ShellLinkHeader` size and the `LinkCLSID` every shortcut carries.
+const HEADER_SIZE: usize = 0x4C;
+const LINK_CLSID: [u8; 16] = [
+    0x01, 0x14, 0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0xC0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x46,
+];
+
+// `LinkFlags` bits.
+const HAS_LINK_TARGET_ID_LIST: u32 = 0x01;
+const HAS_LINK_INFO: u32 = 0x02;
+const HAS_NAME: u32 = 0x04;
+const HAS_RELATIVE_PATH: u32 = 0x08;
+const HAS_WORKING_DIR: u32 = 0x10;
+const HAS_ARGUMENTS: u32 = 0x20;
+const IS_UNICODE: u32 = 0x80;
+
+/// `LinkInfoFlags` bit: the link info carries a volume id and local base path.
+const VOLUME_ID_AND_LOCAL_BASE_PATH: u32 = 0x01;
+
+// ---------------------------------------------------------------------------
+// Public API
+// ---------------------------------------------------------------------------
+
+/// Returns whether `path` has a `.lnk` extension (any case).
+pub fn is_shell_link(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("lnk"))
+}
+
+/// Reads and parses the shortcut at `path`. A target stored only as a relative path is
+/// resolved against the shortcut's own directory.
+pub fn resolve(path: &Path) -> Result<ShellLink, ShellLinkError> {
+    let bytes = std::fs::read(path)?;
+    let mut link = parse(&bytes)?;
+    if link.target.is_relative() {
+        if let Some(dir) = path.parent() {
+            link.target = dir.join(&link.target);
+        }
+    }
+    log::info!("Resolved shortcut {:?} -> {:?}", path, link.target);
+    Ok(link)
+}
+
+/// Parses the bytes of a `.lnk` file. The target comes from the link info's local base
+/// path when present, otherwise from the relative path string.
+pub fn parse(bytes: &[u8]) -> Result<ShellLink, ShellLinkError> {
+    let mut reader = Reader { bytes, pos: 0 };
+    if reader.u32()? as usize != HEADER_SIZE || reader.take(16)? != LINK_CLSID {
+        return Err(ShellLinkError::NotAShellLink);
+    }
+    let flags = reader.u32()?;
+    reader.pos = HEADER_SIZE;
+
+    if flags & HAS_LINK_TARGET_ID_LIST != 0 {
+        let size = reader.u16()? as usize;
+        reader.take(size)?;
+    }
+    let mut target = None;
+    if flags & HAS_LINK_INFO != 0 {
+        let start = reader.pos;
+        let size = reader.u32()? as usize;
+        let info = bytes
+            .get(start..start + size)
+            .ok_or(ShellLinkError::Truncated)?;
+        target = local_base_path(info)?;
+        reader.pos = start + size;
+    }
+
+    let unicode = flags & IS_UNICODE != 0;
+    let mut next_string = |flag: u32| -> Result<Option<String>, ShellLinkError> {
+        if flags & flag == 0 {
+            return Ok(None);
+        }
+        reader.string_data(unicode).map(Some)
+    };
+    next_string(HAS_NAME)?;
+    let relative_path = next_string(HAS_RELATIVE_PATH)?;
+    let working_dir = next_string(HAS_WORKING_DIR)?;
+    let arguments = next_string(HAS_ARGUMENTS)?;
+
+    let target = target
+        .or(relative_path)
+        .filter(|t| !t.is_empty())
+        .ok_or(ShellLinkError::NoTarget)?;
+    Ok(ShellLink {
+        target: PathBuf::from(target),
+        args: arguments.as_deref().map(split_args).unwrap_or_default(),
+        working_dir: working_dir.filter(|d| !d.is_empty()).map(PathBuf::from),
+    })
+}
+
+// ---------------------------------------------------------------------------
+// Private helpers
+// ---------------------------------------------------------------------------
+
+/// Little-endian cursor over the shortcut bytes.
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], ShellLinkError> {
+        let slice = self
+            .bytes
+            .get(self.pos..self.pos + len)
+            .ok_or(ShellLinkError::Truncated)?;
+        self.pos += len;
+        Ok(slice)
+    }
+
+    fn u16(&mut self) -> Result<u16, ShellLinkError> {
+        let b = self.take(2)?;
+        Ok(u16::from_le_bytes([b[0], b[1]]))
+    }
+
+    fn u32(&mut self) -> Result<u32, ShellLinkError> {
+        let b = self.take(4)?;
+        Ok(u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+    }
+
+    /// A `StringData` entry: a character count followed by UTF-16LE or ANSI characters.
+    fn string_data(&mut self, unicode: bool) -> Result<String, ShellLinkError> {
+        let count = self.u16()? as usize;
+        if unicode {
+            let units: Vec<u16> = self
+                .take(count * 2)?
+                .chunks_exact(2)
+                .map(|c| u16::from_le_bytes([c[0], c[1]]))
+                .collect();
+            Ok(String::from_utf16_lossy(&units))
+        } else {
+            Ok(String::from_utf8_lossy(self.take(count)?).into_owned())
+        }
+    }
+}
+
+/// Reads the `LocalBasePath` (plus `CommonPathSuffix`) from a `LinkInfo` structure.
+/// Returns `None` for network-only links.
+fn local_base_path(info: &[u8]) -> Result<Option<String>, ShellLinkError> {
+    let field = |offset: usize| -> Result<usize, ShellLinkError> {
+        let b = info
+            .get(offset..offset + 4)
+            .ok_or(ShellLinkError::Truncated)?;
+        Ok(u32::from_le_bytes([b[0], b[1], b[2], b[3]]) as usize)
+    };
+    if field(8)? as u32 & VOLUME_ID_AND_LOCAL_BASE_PATH == 0 {
+        return Ok(None);
+    }
+    let base = c_string(info, field(16)?)?;
+    let suffix = c_string(info, field(24)?)?;
+    Ok(Some(base + &suffix))
+}
+
+/// The NUL-terminated ANSI string starting at `offset`.
+fn c_string(bytes: &[u8], offset: usize) -> Result<String, ShellLinkError> {
+    let rest = bytes.get(offset..).ok_or(ShellLinkError::Truncated)?;
+    let end = rest
+        .iter()
+        .position(|&b| b == 0)
+        .ok_or(ShellLinkError::Truncated)?;
+    Ok(String::from_utf8_lossy(&rest[..end]).into_owned())
+}
+
+/// Splits a Windows command line into words: whitespace separates words, double quotes
+/// group them, and `\"` is a literal quote.
+fn split_args(command_line: &str) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+    let mut has_arg = false;
+    let mut chars = command_line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if chars.peek() == Some(&'"') => {
+                current.push('"');
+                chars.next();
+                has_arg = true;
+            }
+            '"' => {
+                in_quotes = !in_quotes;
+                has_arg = true;
+            }
+            c if c.is_whitespace() && !in_quotes => {
+                if has_arg {
+                    args.push(std::mem::take(&mut current));
+                    has_arg = false;
+                }
+            }
+            c => {
+                current.push(c);
+                has_arg = true;
+            }
+        }
+    }
+    if has_arg {
+        args.push(current);
+    }
+    args
+}
+
+// ============================================================
+// Tests
+// ============================================================
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Shortcut to `C:\Games\Celeste\Celeste.exe` with an item ID list, local link info,
+    /// a name, a working directory and arguments, all stored as Unicode.
+    const CELESTE_LNK: &[u8] = include_bytes!("testdata/celeste.lnk");
+
+    #[test]
+    fn parses_target_args_and_working_dir() {
+        let link = parse(CELESTE_LNK).expect("fixture should parse");
+        assert_eq!(link.target, PathBuf::from(r"C:\Games\Celeste\Celeste.exe"));
+        assert_eq!(link.args, ["--windowed", "--profile=Player One"]);
+        assert_eq!(link.working_dir, Some(PathBuf::from(r"C:\Games\Celeste")));
+    }
+
+    #[test]
+    fn rejects_other_files_and_truncation() {
+        assert!(matches!(
+            parse(b"MZ\x90\x00not a shortcut at all, just some bytes"),
+            Err(ShellLinkError::NotAShellLink)
+        ));
+        assert!(matches!(
+            parse(&CELESTE_LNK[..120]),
+            Err(ShellLinkError::Truncated)
+        ));
+    }
+
+    #[test]
+    fn splits_windows_command_lines() {
+        assert_eq!(split_args(r#"-a "b c" \"d\""#), ["-a", "b c", "\"d\""]);
+        assert_eq!(split_args(r#"  "" x "#), ["", "x"]);
+        assert!(split_args("").is_empty());
+    }
+
+    #[test]
+    fn lnk_extension_detection() {
+        assert!(is_shell_link(Path::new(r"C:\Start Menu\Celeste.LNK")));
+        assert!(!is_shell_link(Path::new(r"C:\Games\Celeste.exe")));
+    }
+}
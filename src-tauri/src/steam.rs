@@ -1,5 +1,8 @@
+use crate::launcher::{LaunchTarget, SpawnOptions};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
+use std::time::SystemTime;
 use thiserror::Error;
 use walkdir::WalkDir;
 
@@ -7,6 +10,8 @@ use walkdir::WalkDir;
 pub enum SteamError {
     #[error("Steam installation not found")]
     NotFound,
+    #[error("Steam folder {} has no steamapps/libraryfolders.vdf", .0.display())]
+    InvalidRoot(PathBuf),
     #[error("Failed to read file: {0}")]
     Io(#[from] std::io::Error),
 }
@@ -16,16 +21,181 @@ pub struct SteamGame {
     pub app_id: u32,
     pub name: String,
     pub install_dir: PathBuf,
+    /// True for non-Steam games the user added to Steam as shortcuts.
+    #[serde(default)]
+    pub is_shortcut: bool,
+    /// Bytes on disk from the manifest `SizeOnDisk` field, when present.
+    #[serde(default)]
+    pub size_on_disk: Option<u64>,
+    /// Unix timestamp of the last play session, when Steam recorded one.
+    #[serde(default)]
+    pub last_played: Option<u64>,
+    /// Cover art from Steam's local library cache, when it has been downloaded.
+    #[serde(default)]
+    pub cover_image: Option<PathBuf>,
+    /// For shortcuts, the persona name of the Steam account that added it.
+    #[serde(default)]
+    pub owner: Option<String>,
 }
 
 impl SteamGame {
+    /// Returns `steam://run/<appid>` for real games, or `steam://rungameid/<id>` for shortcuts.
     pub fn launch_uri(&self) -> String {
-        format!("steam://run/{}", self.app_id)
+        if self.is_shortcut {
+            format!("steam://rungameid/{}", shortcut_game_id(self.app_id))
+        } else {
+            format!("steam://run/{}", self.app_id)
+        }
+    }
+
+    /// Stable identity for state kept about the game outside Steam (favorites,
+    /// collections, playtime): `steam:<appid>`, or `steam-shortcut:<id>` for shortcuts
+    /// so their generated ids never collide with real app ids.
+    pub fn external_key(&self) -> String {
+        if self.is_shortcut {
+            format!("steam-shortcut:{}", self.app_id)
+        } else {
+            format!("steam:{}", self.app_id)
+        }
+    }
+}
+
+/// Knobs for [`discover_games_at`]. The defaults match what the library grid wants.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct DiscoveryOptions {
+    /// Keep manifests that are queued, downloading, or pending an update.
+    pub include_incomplete: bool,
+    /// Keep games whose install directory is missing, e.g. a manifest left behind on an
+    /// unplugged library drive. Off by default since those games can't launch.
+    pub include_missing: bool,
+    /// Drop apps that look like tools rather than games (Proton, runtimes, servers,
+    /// soundtracks); see [`is_probably_tool`]. Off by default so nothing disappears
+    /// unexpectedly.
+    pub hide_tools: bool,
+}
+
+/// `StateFlags` bit Steam sets once an app is fully installed.
+const STATE_FULLY_INSTALLED: u32 = 4;
+
+/// App ids of Steam tools that show up as installed apps: Steamworks Common
+/// Redistributables, SteamVR, the Steam Linux Runtimes, Proton Experimental and the
+/// anti-cheat runtimes. Proton releases are also caught by name.
+pub const TOOL_APP_IDS: &[u32] = &[
+    228980, 250820, 1070560, 1391110, 1628350, 1493710, 1161040, 1826330,
+];
+
+/// Lowercase name fragments that mark an app as a tool rather than a game.
+const TOOL_NAME_HINTS: &[&str] = &[
+    "proton",
+    "soundtrack",
+    "dedicated server",
+    "steam linux runtime",
+    "redistributable",
+    "sdk",
+];
+
+/// A non-Steam game the user added to Steam, parsed from `userdata/<id>/config/shortcuts.vdf`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ShortcutGame {
+    pub app_id: u32,
+    pub app_name: String,
+    /// Executable path with Steam's surrounding quotes removed.
+    pub exe: String,
+    /// Working directory with Steam's surrounding quotes removed.
+    pub start_dir: String,
+    /// Extra command-line arguments passed to the executable.
+    pub launch_options: String,
+    /// True when the user hid the shortcut in their Steam library.
+    #[serde(default)]
+    pub is_hidden: bool,
+    /// Unix timestamp from `LastPlayTime`, or `None` if never played.
+    #[serde(default)]
+    pub last_played: Option<u64>,
+    /// Custom grid art from `userdata/<id>/config/grid`, when the user set one.
+    #[serde(default)]
+    pub cover_image: Option<PathBuf>,
+    /// Steam collection tags from the nested `tags` map, in file order.
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Persona name of the account whose `userdata` folder holds the shortcut, when
+    /// `loginusers.vdf` lists it.
+    #[serde(default)]
+    pub owner: Option<String>,
+}
+
+impl ShortcutGame {
+    /// How to launch the shortcut. It goes through `steam://rungameid/` so the overlay
+    /// and Steam Input work, unless `bypass_steam` asks to run `exe` directly with its
+    /// start directory and launch options. `force_via_steam` wins over `bypass_steam`.
+    pub fn launch_target(&self, force_via_steam: bool, bypass_steam: bool) -> LaunchTarget {
+        if force_via_steam || !bypass_steam {
+            return LaunchTarget::steam_shortcut(self.app_id);
+        }
+        LaunchTarget::executable_with(
+            &self.exe,
+            SpawnOptions {
+                args: split_launch_options(&self.launch_options),
+                working_dir: Some(PathBuf::from(&self.start_dir))
+                    .filter(|d| !d.as_os_str().is_empty()),
+                ..Default::default()
+            },
+        )
+    }
+}
+
+impl From<ShortcutGame> for SteamGame {
+    fn from(s: ShortcutGame) -> Self {
+        SteamGame {
+            app_id: s.app_id,
+            name: s.app_name,
+            install_dir: PathBuf::from(s.start_dir),
+            is_shortcut: true,
+            size_on_disk: None,
+            last_played: s.last_played,
+            cover_image: s.cover_image,
+            owner: s.owner,
+        }
+    }
+}
+
+/// A library folder listed in `libraryfolders.vdf`, for the "where are my games" view.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct SteamLibrary {
+    /// The library's `steamapps` directory.
+    pub path: PathBuf,
+    /// False when the folder is missing, e.g. on an unplugged drive.
+    pub exists: bool,
+}
+
+/// A Steam account that has signed in on this machine, from `config/loginusers.vdf`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SteamUser {
+    /// 64-bit SteamID, the key of the user's block in `loginusers.vdf`.
+    pub steam_id: u64,
+    pub account_name: String,
+    pub persona_name: String,
+}
+
+impl SteamUser {
+    /// The 32-bit account id (low half of the SteamID) used for `userdata/<id>` folders.
+    pub fn account_id(&self) -> u32 {
+        (self.steam_id & 0xFFFF_FFFF) as u32
     }
 }
 
+/// Returns the 64-bit game id Steam uses to address a non-Steam shortcut:
+/// the 32-bit app id in the high half and `0x02000000` in the low half.
+pub fn shortcut_game_id(app_id: u32) -> u64 {
+    ((app_id as u64) << 32) | 0x0200_0000
+}
+
+/// Returns the Steam store page for a real app id. Shortcuts have no store page.
+pub fn store_url(app_id: u32) -> String {
+    format!("https://store.steampowered.com/app/{app_id}")
+}
+
 /// Returns the default Steam root path for the current OS.
-fn default_steam_root() -> Option<PathBuf> {
+pub fn default_steam_root() -> Option<PathBuf> {
     #[cfg(target_os = "macos")]
     {
         let home = std::env::var("HOME").ok()?;
@@ -34,12 +204,7 @@ fn default_steam_root() -> Option<PathBuf> {
     #[cfg(target_os = "linux")]
     {
         let home = std::env::var("HOME").ok()?;
-        // Check both common locations
-        let xdg = PathBuf::from(home.clone()).join(".steam/steam");
-        if xdg.exists() {
-            return Some(xdg);
-        }
-        Some(PathBuf::from(home).join(".local/share/Steam"))
+        Some(linux_steam_root(Path::new(&home)))
     }
     #[cfg(target_os = "windows")]
     {
@@ -51,40 +216,134 @@ fn default_steam_root() -> Option<PathBuf> {
     }
 }
 
-/// Finds all Steam library folder paths by parsing `libraryfolders.vdf`.
+/// Returns the Steam root to scan: `override_root` when the user set one, otherwise
+/// [`default_steam_root`]. An override is only accepted if it holds
+/// `steamapps/libraryfolders.vdf`, so a mistyped folder is reported instead of
+/// silently yielding no games.
+pub fn resolve_steam_root(override_root: Option<&Path>) -> Result<PathBuf, SteamError> {
+    let Some(root) = override_root else {
+        return default_steam_root().ok_or(SteamError::NotFound);
+    };
+    if root.join("steamapps/libraryfolders.vdf").is_file() {
+        Ok(root.to_path_buf())
+    } else {
+        log::warn!("Steam root override {:?} is not a Steam folder", root);
+        Err(SteamError::InvalidRoot(root.to_path_buf()))
+    }
+}
+
+/// Flatpak application id of the Steam client.
+pub const FLATPAK_STEAM_ID: &str = "com.valvesoftware.Steam";
+
+/// Steam roots under a Linux home directory, in probe order: native installs first,
+/// then the Flatpak and Snap sandboxes.
+#[cfg(target_os = "linux")]
+const LINUX_STEAM_ROOTS: &[&str] = &[
+    ".steam/steam",
+    ".local/share/Steam",
+    ".var/app/com.valvesoftware.Steam/data/Steam",
+    "snap/steam/common/.local/share/Steam",
+];
+
+/// Resolves the Steam root under a Linux home directory: the first of
+/// [`LINUX_STEAM_ROOTS`] that exists, or `~/.local/share/Steam` when none does.
+#[cfg(target_os = "linux")]
+fn linux_steam_root(home: &Path) -> PathBuf {
+    LINUX_STEAM_ROOTS
+        .iter()
+        .map(|root| home.join(root))
+        .find(|root| root.exists())
+        .unwrap_or_else(|| home.join(".local/share/Steam"))
+}
+
+/// Whether `steam_root` lives in the Flatpak Steam sandbox (`~/.var/app/<id>/...`).
+pub fn is_flatpak_root(steam_root: &Path) -> bool {
+    let mut components = steam_root.components().map(|c| c.as_os_str());
+    while let Some(component) = components.next() {
+        if component == ".var"
+            && components.next() == Some("app".as_ref())
+            && components.next() == Some(FLATPAK_STEAM_ID.as_ref())
+        {
+            return true;
+        }
+    }
+    false
+}
+
+/// Finds the mounted Steam library folder paths by parsing `libraryfolders.vdf`.
 pub fn find_library_paths(steam_root: &Path) -> Result<Vec<PathBuf>, SteamError> {
     let vdf_path = steam_root.join("steamapps/libraryfolders.vdf");
-    let contents = std::fs::read_to_string(&vdf_path)?;
+    let contents = read_text(&vdf_path)?;
     parse_library_paths_from_vdf(&contents, steam_root)
 }
 
-/// Parses library folder paths from the contents of `libraryfolders.vdf`.
+/// Lists every library folder `libraryfolders.vdf` declares, flagging missing ones,
+/// including unmounted drives that [`find_library_paths`] leaves out.
+/// A root without `steamapps/libraryfolders.vdf` is reported as [`SteamError::InvalidRoot`].
+pub fn library_folders(steam_root: &Path) -> Result<Vec<SteamLibrary>, SteamError> {
+    let vdf_path = steam_root.join("steamapps/libraryfolders.vdf");
+    if !vdf_path.is_file() {
+        return Err(SteamError::InvalidRoot(steam_root.to_path_buf()));
+    }
+    let contents = read_text(&vdf_path)?;
+    Ok(library_paths(&contents, steam_root, |_| true)
+        .into_iter()
+        .map(|path| SteamLibrary {
+            exists: path.is_dir(),
+            path,
+        })
+        .collect())
+}
+
+/// Parses the mounted library folder paths from the contents of `libraryfolders.vdf`.
 /// The steam root's own `steamapps/` directory is always included.
+///
+/// A library counts as mounted when its entry says `"mounted" "1"`, or, for entries
+/// without that key, when its folder exists.
 pub fn parse_library_paths_from_vdf(
     vdf: &str,
     steam_root: &Path,
 ) -> Result<Vec<PathBuf>, SteamError> {
-    let mut paths: Vec<PathBuf> = vec![steam_root.join("steamapps")];
+    Ok(library_paths(vdf, steam_root, |entry| {
+        let mounted = entry
+            .mounted
+            .unwrap_or_else(|| Path::new(&entry.path).is_dir());
+        if !mounted {
+            log::info!("Skipping unmounted Steam library {:?}", entry.path);
+        }
+        mounted
+    }))
+}
 
-    // Each library folder entry looks like:   "path"   "/some/path"
-    for line in vdf.lines() {
-        let trimmed = line.trim();
-        if trimmed.starts_with("\"path\"") {
-            // Extract the value between the second pair of quotes
-            if let Some(value) = extract_quoted_value(trimmed, 1) {
-                let lib_path = PathBuf::from(value).join("steamapps");
-                if !paths.contains(&lib_path) {
-                    paths.push(lib_path);
-                }
-            }
+/// The root's `steamapps/` followed by the `steamapps/` of each declared library that
+/// `keep` accepts, without duplicates.
+fn library_paths(
+    vdf: &str,
+    steam_root: &Path,
+    keep: impl Fn(&LibraryEntry) -> bool,
+) -> Vec<PathBuf> {
+    let mut paths: Vec<PathBuf> = vec![steam_root.join("steamapps")];
+    for entry in parse_library_entries(vdf) {
+        if !cfg!(windows) && is_windows_absolute(&entry.path) {
+            // Copied over from a Windows install; kept so it's listed as missing.
+            log::warn!(
+                "Steam library {:?} can't be resolved on this system",
+                entry.path
+            );
+        }
+        if !keep(&entry) {
+            continue;
+        }
+        let lib_path = PathBuf::from(entry.path).join("steamapps");
+        if !paths.contains(&lib_path) {
+            paths.push(lib_path);
         }
     }
-
-    Ok(paths)
+    paths
 }
 
 /// Reads all `appmanifest_*.acf` files in a steamapps directory and returns the games found.
-pub fn read_games_from_library(steamapps_dir: &Path) -> Vec<SteamGame> {
+pub fn read_games_from_library(steamapps_dir: &Path, options: DiscoveryOptions) -> Vec<SteamGame> {
     WalkDir::new(steamapps_dir)
         .max_depth(1)
         .into_iter()
@@ -95,63 +354,631 @@ pub fn read_games_from_library(steamapps_dir: &Path) -> Vec<SteamGame> {
                 .map(|n| n.starts_with("appmanifest_") && n.ends_with(".acf"))
                 .unwrap_or(false)
         })
-        .filter_map(|e| parse_acf_file(e.path()))
+        .filter_map(|e| parse_acf_file(e.path(), options))
         .collect()
 }
 
-/// Parses a single `appmanifest_*.acf` file into a [`SteamGame`].
-pub fn parse_acf_file(path: &Path) -> Option<SteamGame> {
-    let contents = std::fs::read_to_string(path).ok()?;
+/// Parses a single `appmanifest_*.acf` file into a [`SteamGame`], skipping
+/// incomplete installs unless `options.include_incomplete` is set.
+pub fn parse_acf_file(path: &Path, options: DiscoveryOptions) -> Option<SteamGame> {
+    let contents = read_text(path).ok()?;
+    if !options.include_incomplete && !is_fully_installed(&contents) {
+        log::info!("Skipping incomplete Steam install: {:?}", path);
+        return None;
+    }
     parse_acf(&contents, path.parent()?)
 }
 
+/// Returns whether the manifest's `StateFlags` has the fully-installed bit set.
+/// Manifests without `StateFlags` are assumed installed.
+pub fn is_fully_installed(contents: &str) -> bool {
+    match find_acf_value(contents, "StateFlags").and_then(|v| v.parse::<u32>().ok()) {
+        Some(flags) => flags & STATE_FULLY_INSTALLED != 0,
+        None => true,
+    }
+}
+
 /// Parses the ACF content and constructs a [`SteamGame`].
 pub fn parse_acf(contents: &str, steamapps_dir: &Path) -> Option<SteamGame> {
     let app_id = find_acf_value(contents, "appid")?.parse::<u32>().ok()?;
     let name = find_acf_value(contents, "name")?;
     let install_dir_name = find_acf_value(contents, "installdir")?;
+    let Some(install_dir_name) = relative_install_dir(&install_dir_name) else {
+        log::warn!(
+            "Skipping app {}: installdir {:?} can't be resolved on this system",
+            app_id,
+            install_dir_name
+        );
+        return None;
+    };
     let install_dir = steamapps_dir.join("common").join(install_dir_name);
+    let size_on_disk = find_acf_value(contents, "SizeOnDisk").and_then(|v| v.parse().ok());
+    // Newer manifests record `LastPlayed`; older ones only have `LastUpdated`.
+    let last_played = find_acf_value(contents, "LastPlayed")
+        .or_else(|| find_acf_value(contents, "LastUpdated"))
+        .and_then(|v| v.parse().ok())
+        .filter(|&t: &u64| t > 0);
 
     Some(SteamGame {
         app_id,
         name,
         install_dir,
+        is_shortcut: false,
+        size_on_disk,
+        last_played,
+        cover_image: None,
+        owner: None,
     })
 }
 
 /// Discovers all installed Steam games on the system.
 pub fn discover_games() -> Result<Vec<SteamGame>, SteamError> {
     let root = default_steam_root().ok_or(SteamError::NotFound)?;
-    discover_games_at(&root)
+    discover_games_at(&root, DiscoveryOptions::default())
 }
 
 /// Discovers all installed Steam games starting from a specific Steam root.
-pub fn discover_games_at(steam_root: &Path) -> Result<Vec<SteamGame>, SteamError> {
+pub fn discover_games_at(
+    steam_root: &Path,
+    options: DiscoveryOptions,
+) -> Result<Vec<SteamGame>, SteamError> {
     if !steam_root.exists() {
         return Err(SteamError::NotFound);
     }
     let library_paths = find_library_paths(steam_root)?;
+
+    // Scan each library on its own thread; results are joined in library order so
+    // the first-seen-wins dedup below stays deterministic.
+    let per_library: Vec<Vec<SteamGame>> = std::thread::scope(|scope| {
+        let handles: Vec<_> = library_paths
+            .iter()
+            .map(|dir| scope.spawn(move || read_games_from_library(dir, options)))
+            .collect();
+        handles
+            .into_iter()
+            .map(|h| h.join().unwrap_or_default())
+            .collect()
+    });
+
+    // Drop stale manifests before dedup so a live copy in a later library still wins.
     let mut seen = std::collections::HashSet::new();
-    let games: Vec<SteamGame> = library_paths
-        .iter()
-        .flat_map(|dir| read_games_from_library(dir))
+    let games: Vec<SteamGame> = per_library
+        .into_iter()
+        .flatten()
+        .filter(|g| is_listed(g, options))
         .filter(|g| seen.insert(g.app_id))
+        .map(|g| with_cover_image(steam_root, g))
         .collect();
 
     Ok(games)
 }
 
+/// Re-reads only the manifests in one library's `steamapps_dir`, e.g. after the file
+/// watcher saw it change, with the same filters as [`discover_games_at`]. Games also
+/// installed in another library aren't deduplicated here; fold the result into the
+/// cached full list with [`DiscoveryCache::merge_library`].
+pub fn rescan_library(
+    steam_root: &Path,
+    steamapps_dir: &Path,
+    options: DiscoveryOptions,
+) -> Vec<SteamGame> {
+    let games: Vec<SteamGame> = read_games_from_library(steamapps_dir, options)
+        .into_iter()
+        .filter(|g| is_listed(g, options))
+        .map(|g| with_cover_image(steam_root, g))
+        .collect();
+    log::info!(
+        "Rescanned Steam library {:?}: {} game(s)",
+        steamapps_dir,
+        games.len()
+    );
+    games
+}
+
+/// Merges rescanned games into `existing`: a game with the same app id (and shortcut
+/// flag) is replaced in place, and games not seen before are appended in order.
+pub fn merge_games(existing: Vec<SteamGame>, new: Vec<SteamGame>) -> Vec<SteamGame> {
+    let mut merged = existing;
+    for game in new {
+        match merged
+            .iter_mut()
+            .find(|g| g.app_id == game.app_id && g.is_shortcut == game.is_shortcut)
+        {
+            Some(slot) => *slot = game,
+            None => merged.push(game),
+        }
+    }
+    merged
+}
+
+/// Whether discovery lists `game` under `options`: its install dir must exist (unless
+/// missing ones are included) and it must not be a hidden tool.
+fn is_listed(game: &SteamGame, options: DiscoveryOptions) -> bool {
+    if !options.include_missing && !game.install_dir.exists() {
+        log::warn!(
+            "Skipping Steam game {:?} (app {}): install dir {:?} is missing",
+            game.name,
+            game.app_id,
+            game.install_dir
+        );
+        return false;
+    }
+    if options.hide_tools && is_probably_tool(game) {
+        log::debug!("Hiding Steam tool {:?} (app {})", game.name, game.app_id);
+        return false;
+    }
+    true
+}
+
+/// Drops the games whose app id is in `hidden`, the user's own denylist.
+pub fn without_hidden_apps(mut games: Vec<SteamGame>, hidden: &[u32]) -> Vec<SteamGame> {
+    games.retain(|g| !hidden.contains(&g.app_id));
+    games
+}
+
+fn with_cover_image(steam_root: &Path, game: SteamGame) -> SteamGame {
+    SteamGame {
+        cover_image: find_cover_image(steam_root, game.app_id),
+        ..game
+    }
+}
+
+/// Whether an installed app is likely a tool, runtime, server or soundtrack rather than a
+/// game: its id is in [`TOOL_APP_IDS`] or its name contains a word such as `Proton`,
+/// `Soundtrack` or `Dedicated Server`.
+pub fn is_probably_tool(game: &SteamGame) -> bool {
+    if TOOL_APP_IDS.contains(&game.app_id) {
+        return true;
+    }
+    // Match whole words so e.g. "Protonaut" isn't mistaken for Proton.
+    let words: Vec<String> = game
+        .name
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(str::to_lowercase)
+        .collect();
+    let name = format!(" {} ", words.join(" "));
+    TOOL_NAME_HINTS
+        .iter()
+        .any(|hint| name.contains(&format!(" {hint} ")))
+}
+
+/// Artwork names Steam caches per app, best fit for a portrait grid first.
+const LIBRARY_CACHE_ART: &[&str] = &[
+    "library_600x900_2x",
+    "library_600x900",
+    "header",
+    "library_hero",
+];
+
+/// Returns the first cached artwork for `app_id` under `<steam_root>/appcache/librarycache`.
+///
+/// Older clients store `<appid>_<name>` files flat in the cache; newer ones use an
+/// `<appid>/` subdirectory per app. Both layouts are checked, and each name may be saved
+/// as any of the [`crate::library::COVER_EXTENSIONS`].
+pub fn find_cover_image(steam_root: &Path, app_id: u32) -> Option<PathBuf> {
+    let cache = steam_root.join("appcache/librarycache");
+    LIBRARY_CACHE_ART.iter().find_map(|name| {
+        crate::library::COVER_EXTENSIONS.iter().find_map(|ext| {
+            [
+                cache.join(format!("{app_id}_{name}.{ext}")),
+                cache.join(app_id.to_string()).join(format!("{name}.{ext}")),
+            ]
+            .into_iter()
+            .find(|p| p.is_file())
+        })
+    })
+}
+
+/// Caches discovery results per Steam root until the on-disk manifests change.
+#[derive(Debug, Default)]
+pub struct DiscoveryCache {
+    entries: HashMap<PathBuf, CacheEntry>,
+}
+
+#[derive(Debug)]
+struct CacheEntry {
+    fingerprint: Option<SystemTime>,
+    games: Vec<SteamGame>,
+}
+
+impl DiscoveryCache {
+    /// Returns the cached games for `steam_root` if no manifest changed since they were stored.
+    pub fn get(&self, steam_root: &Path) -> Option<Vec<SteamGame>> {
+        let entry = self.entries.get(steam_root)?;
+        (entry.fingerprint == manifest_fingerprint(steam_root)).then(|| entry.games.clone())
+    }
+
+    /// Stores `games` for `steam_root`, stamped with the current manifest fingerprint.
+    pub fn insert(&mut self, steam_root: &Path, games: Vec<SteamGame>) {
+        let fingerprint = manifest_fingerprint(steam_root);
+        self.entries
+            .insert(steam_root.to_path_buf(), CacheEntry { fingerprint, games });
+    }
+
+    /// Folds a [`rescan_library`] of `steamapps_dir` into the games cached for
+    /// `steam_root`: rescanned games replace cached ones by app id (see [`merge_games`]),
+    /// and cached games from that library the rescan no longer lists are dropped. The
+    /// entry is re-stamped, so the change that prompted the rescan doesn't force a full
+    /// walk. Does nothing when the root has no cached games.
+    pub fn merge_library(
+        &mut self,
+        steam_root: &Path,
+        steamapps_dir: &Path,
+        rescanned: Vec<SteamGame>,
+    ) {
+        let Some(entry) = self.entries.get_mut(steam_root) else {
+            return;
+        };
+        let kept: Vec<SteamGame> = std::mem::take(&mut entry.games)
+            .into_iter()
+            .filter(|g| {
+                g.is_shortcut
+                    || !g.install_dir.starts_with(steamapps_dir)
+                    || rescanned.iter().any(|r| r.app_id == g.app_id)
+            })
+            .collect();
+        entry.games = merge_games(kept, rescanned);
+        entry.fingerprint = manifest_fingerprint(steam_root);
+    }
+
+    /// Forgets every cached result, e.g. after the discovery settings changed.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+/// Returns the newest mtime among `libraryfolders.vdf`, every library's `steamapps`
+/// directory and `appmanifest_*.acf` files, and the users' `shortcuts.vdf` files.
+/// Directory mtimes are included so uninstalls (deleted manifests) are noticed too.
+pub fn manifest_fingerprint(steam_root: &Path) -> Option<SystemTime> {
+    let mtime = |p: &Path| std::fs::metadata(p).and_then(|m| m.modified()).ok();
+    let mut newest = mtime(&steam_root.join("steamapps/libraryfolders.vdf"));
+
+    for dir in find_library_paths(steam_root).unwrap_or_default() {
+        newest = newest.max(mtime(&dir));
+        let manifests = WalkDir::new(&dir)
+            .max_depth(1)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| {
+                e.file_name()
+                    .to_str()
+                    .map(|n| n.starts_with("appmanifest_") && n.ends_with(".acf"))
+                    .unwrap_or(false)
+            });
+        for entry in manifests {
+            newest = newest.max(mtime(entry.path()));
+        }
+    }
+
+    if let Ok(users) = std::fs::read_dir(steam_root.join("userdata")) {
+        for user in users.flatten() {
+            newest = newest.max(mtime(&user.path().join("config/shortcuts.vdf")));
+        }
+    }
+
+    newest
+}
+
+/// Reads the users listed in `<steam_root>/config/loginusers.vdf`; empty if it is missing.
+pub fn find_login_users(steam_root: &Path) -> Vec<SteamUser> {
+    let path = steam_root.join("config/loginusers.vdf");
+    match read_text(&path) {
+        Ok(contents) => parse_login_users(&contents),
+        Err(e) => {
+            log::info!("No Steam login users at {:?}: {}", path, e);
+            Vec::new()
+        }
+    }
+}
+
+/// Parses `loginusers.vdf`, whose `"users"` map is keyed by 64-bit SteamID with
+/// `AccountName` / `PersonaName` fields in each block.
+pub fn parse_login_users(vdf: &str) -> Vec<SteamUser> {
+    let mut users: Vec<SteamUser> = Vec::new();
+    for line in vdf.lines() {
+        let trimmed = line.trim();
+        let key = extract_quoted_value(trimmed, 0);
+        let value = extract_quoted_value(trimmed, 1);
+        match (key, value) {
+            // A lone numeric key opens a new user block.
+            (Some(key), None) => {
+                if let Ok(steam_id) = key.parse::<u64>() {
+                    users.push(SteamUser {
+                        steam_id,
+                        account_name: String::new(),
+                        persona_name: String::new(),
+                    });
+                }
+            }
+            (Some(key), Some(value)) => {
+                let Some(user) = users.last_mut() else {
+                    continue;
+                };
+                if key.eq_ignore_ascii_case("AccountName") {
+                    user.account_name = value;
+                } else if key.eq_ignore_ascii_case("PersonaName") {
+                    user.persona_name = value;
+                }
+            }
+            _ => {}
+        }
+    }
+    users
+}
+
+/// Returns the persona name of the user owning `shortcuts_vdf`
+/// (`userdata/<account_id>/config/shortcuts.vdf`), if that user is known.
+pub fn persona_for_shortcuts<'a>(users: &'a [SteamUser], shortcuts_vdf: &Path) -> Option<&'a str> {
+    let account_id: u32 = shortcuts_vdf
+        .parent()?
+        .parent()?
+        .file_name()?
+        .to_str()?
+        .parse()
+        .ok()?;
+    users
+        .iter()
+        .find(|u| u.account_id() == account_id)
+        .map(|u| u.persona_name.as_str())
+}
+
+/// Discovers non-Steam shortcuts from every `userdata/<id>/config/shortcuts.vdf` under the root,
+/// each labelled with the persona name of the account it belongs to.
+/// Shortcuts the user hid in Steam are dropped unless `include_hidden` is set.
+pub fn discover_shortcut_games(steam_root: &Path, include_hidden: bool) -> Vec<ShortcutGame> {
+    let Ok(users) = std::fs::read_dir(steam_root.join("userdata")) else {
+        return Vec::new();
+    };
+    let login_users = find_login_users(steam_root);
+    let mut games = Vec::new();
+    for user in users.flatten() {
+        let config = user.path().join("config");
+        let vdf_path = config.join("shortcuts.vdf");
+        let grid_dir = config.join("grid");
+        let owner = persona_for_shortcuts(&login_users, &vdf_path).map(str::to_string);
+        match std::fs::read(&vdf_path) {
+            Ok(bytes) => games.extend(
+                parse_shortcuts_vdf(&bytes)
+                    .into_iter()
+                    .filter(|g| include_hidden || !g.is_hidden)
+                    .map(|g| ShortcutGame {
+                        cover_image: find_grid_image(&grid_dir, g.app_id),
+                        owner: owner.clone(),
+                        ..g
+                    }),
+            ),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => log::warn!("Failed to read {:?}: {}", vdf_path, e),
+        }
+    }
+    games
+}
+
+/// Parses the binary `shortcuts.vdf` format into [`ShortcutGame`]s.
+///
+/// The file is a root map `"shortcuts"` whose children are maps keyed `"0"`, `"1"`, ...
+/// Entries without an app id or name are skipped; a truncated file yields whatever
+/// parsed cleanly before the damage.
+pub fn parse_shortcuts_vdf(bytes: &[u8]) -> Vec<ShortcutGame> {
+    let mut reader = BinaryVdfReader { bytes, pos: 0 };
+    let root = reader.read_map();
+    let Some((_, VdfValue::Map(shortcuts))) = root.into_iter().next() else {
+        return Vec::new();
+    };
+
+    shortcuts
+        .into_iter()
+        .filter_map(|(_, entry)| match entry {
+            VdfValue::Map(fields) => shortcut_from_fields(&fields),
+            _ => None,
+        })
+        .collect()
+}
+
+fn shortcut_from_fields(fields: &[(String, VdfValue)]) -> Option<ShortcutGame> {
+    let string = |key: &str| {
+        fields.iter().find_map(|(k, v)| match v {
+            VdfValue::String(s) if k.eq_ignore_ascii_case(key) => Some(s.clone()),
+            _ => None,
+        })
+    };
+    let int = |key: &str| {
+        fields.iter().find_map(|(k, v)| match v {
+            VdfValue::Int(n) if k.eq_ignore_ascii_case(key) => Some(*n),
+            _ => None,
+        })
+    };
+    let app_id = int("appid")?;
+    let app_name = string("AppName").filter(|s| !s.is_empty())?;
+
+    Some(ShortcutGame {
+        app_id,
+        app_name,
+        exe: unquote(&string("Exe").unwrap_or_default()),
+        start_dir: unquote(&string("StartDir").unwrap_or_default()),
+        launch_options: string("LaunchOptions").unwrap_or_default(),
+        // Steam writes IsHidden as an int32 flag, not a single byte.
+        is_hidden: int("IsHidden").unwrap_or(0) != 0,
+        last_played: int("LastPlayTime").filter(|&t| t > 0).map(u64::from),
+        cover_image: None,
+        tags: tags(fields),
+        owner: None,
+    })
+}
+
+/// Collects the string values of a shortcut's nested `tags` map (keyed `"0"`, `"1"`, ...).
+fn tags(fields: &[(String, VdfValue)]) -> Vec<String> {
+    let Some((_, VdfValue::Map(tags))) = fields
+        .iter()
+        .find(|(k, v)| k.eq_ignore_ascii_case("tags") && matches!(v, VdfValue::Map(_)))
+    else {
+        return Vec::new();
+    };
+    tags.iter()
+        .filter_map(|(_, v)| match v {
+            VdfValue::String(s) if !s.is_empty() => Some(s.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Image extensions Steam accepts for custom grid art.
+const GRID_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "webp"];
+
+/// Returns the custom grid art for a shortcut from a user's `config/grid` directory.
+///
+/// Portrait art is saved as `<appid>p.<ext>` and preferred; the bare `<appid>.<ext>` is the
+/// horizontal capsule and used as a fallback. `_hero` and `_logo` variants are ignored.
+pub fn find_grid_image(grid_dir: &Path, app_id: u32) -> Option<PathBuf> {
+    [format!("{app_id}p"), app_id.to_string()]
+        .iter()
+        .flat_map(|stem| {
+            GRID_EXTENSIONS
+                .iter()
+                .map(move |ext| grid_dir.join(format!("{stem}.{ext}")))
+        })
+        .find(|p| p.is_file())
+}
+
 // --- helpers ---
 
+/// A value in Steam's binary VDF format.
+#[derive(Debug, Clone, PartialEq)]
+enum VdfValue {
+    Map(Vec<(String, VdfValue)>),
+    String(String),
+    Int(u32),
+    Float(f32),
+}
+
+const VDF_MAP: u8 = 0x00;
+const VDF_STRING: u8 = 0x01;
+const VDF_INT: u8 = 0x02;
+const VDF_FLOAT: u8 = 0x03;
+const VDF_END_MAP: u8 = 0x08;
+
+struct BinaryVdfReader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl BinaryVdfReader<'_> {
+    /// Reads `(type, key, value)` entries until the matching `END_MAP` or end of input.
+    /// Nested maps are read recursively so their entries never leak into the parent.
+    fn read_map(&mut self) -> Vec<(String, VdfValue)> {
+        let mut entries = Vec::new();
+        while let Some(&kind) = self.bytes.get(self.pos) {
+            self.pos += 1;
+            if kind == VDF_END_MAP {
+                break;
+            }
+            let Some(key) = self.read_cstring() else { break };
+            let value = match kind {
+                VDF_MAP => VdfValue::Map(self.read_map()),
+                VDF_STRING => match self.read_cstring() {
+                    Some(s) => VdfValue::String(s),
+                    None => break,
+                },
+                VDF_INT => match self.read_u32() {
+                    Some(n) => VdfValue::Int(n),
+                    None => break,
+                },
+                VDF_FLOAT => match self.read_u32() {
+                    Some(n) => VdfValue::Float(f32::from_bits(n)),
+                    None => break,
+                },
+                other => {
+                    log::warn!("Unknown binary VDF type 0x{:02x}; stopping parse", other);
+                    break;
+                }
+            };
+            entries.push((key, value));
+        }
+        entries
+    }
+
+    fn read_cstring(&mut self) -> Option<String> {
+        let rest = self.bytes.get(self.pos..)?;
+        let len = rest.iter().position(|&b| b == 0)?;
+        self.pos += len + 1;
+        Some(String::from_utf8_lossy(&rest[..len]).into_owned())
+    }
+
+    fn read_u32(&mut self) -> Option<u32> {
+        let raw = self.bytes.get(self.pos..self.pos + 4)?;
+        self.pos += 4;
+        Some(u32::from_le_bytes(raw.try_into().ok()?))
+    }
+}
+
+/// Strips the surrounding double quotes Steam adds to shortcut paths.
+fn unquote(value: &str) -> String {
+    value.trim().trim_matches('"').to_string()
+}
+
+/// Splits a shortcut's `LaunchOptions` (or any argument string) into arguments at
+/// whitespace; double quotes group words containing spaces.
+pub(crate) fn split_launch_options(options: &str) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+    let mut has_arg = false;
+    for c in options.chars() {
+        match c {
+            '"' => {
+                in_quotes = !in_quotes;
+                has_arg = true;
+            }
+            c if c.is_whitespace() && !in_quotes => {
+                if has_arg {
+                    args.push(std::mem::take(&mut current));
+                    has_arg = false;
+                }
+            }
+            c => {
+                current.push(c);
+                has_arg = true;
+            }
+        }
+    }
+    if has_arg {
+        args.push(current);
+    }
+    args
+}
+
+/// Turns an `installdir` value into a relative path for this host. Manifests written on
+/// Windows may use backslashes (`Game\Bin`), which are split into components here.
+/// Returns `None` for absolute Windows paths, which don't name anything under `common`.
+fn relative_install_dir(value: &str) -> Option<PathBuf> {
+    if is_windows_absolute(value) {
+        return None;
+    }
+    Some(
+        value
+            .split(['\\', '/'])
+            .filter(|part| !part.is_empty())
+            .collect(),
+    )
+}
+
+/// Whether `value` is a drive (`D:\...`) or UNC (`\\server\...`) path.
+fn is_windows_absolute(value: &str) -> bool {
+    let bytes = value.as_bytes();
+    let drive = bytes.len() >= 2 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':';
+    drive || value.starts_with("\\\\")
+}
+
 /// Extracts the nth (0-indexed) quoted string value from a line.
 fn extract_quoted_value(line: &str, index: usize) -> Option<String> {
-    let mut chars = line.chars().peekable();
+    let mut chars = line.chars();
     let mut found = 0;
     loop {
         // Find next opening quote
         chars.find(|&c| c == '"')?;
-        // Collect until closing quote
-        let value: String = chars.by_ref().take_while(|&c| c != '"').collect();
+        let value = read_quoted(&mut chars);
         if found == index {
             return Some(value);
         }
@@ -159,6 +986,136 @@ fn extract_quoted_value(line: &str, index: usize) -> Option<String> {
     }
 }
 
+/// Reads a quoted VDF string up to its closing (unescaped) quote, the opening quote
+/// already consumed.
+///
+/// Steam's text VDF escapes with backslashes inside quotes: `\"`, `\\`, `\n` and `\t` are
+/// decoded; any other backslash is kept literally so unescaped paths survive.
+fn read_quoted(chars: &mut impl Iterator<Item = char>) -> String {
+    let mut value = String::new();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => break,
+            '\\' => match chars.next() {
+                Some('"') => value.push('"'),
+                Some('\\') => value.push('\\'),
+                Some('n') => value.push('\n'),
+                Some('t') => value.push('\t'),
+                Some(other) => {
+                    value.push('\\');
+                    value.push(other);
+                }
+                None => value.push('\\'),
+            },
+            c => value.push(c),
+        }
+    }
+    value
+}
+
+/// A library folder declared in `libraryfolders.vdf`.
+struct LibraryEntry {
+    /// The library's folder, containing its `steamapps/`.
+    path: String,
+    /// The entry's `mounted` flag; older files don't have one.
+    mounted: Option<bool>,
+}
+
+/// A token of Steam's text VDF: a quoted string or a brace.
+enum VdfToken {
+    Str(String),
+    Open,
+    Close,
+}
+
+/// Splits text VDF into tokens, skipping whitespace, `//` comments and anything
+/// unquoted (such as `[$WIN32]` conditionals).
+fn vdf_tokens(vdf: &str) -> Vec<VdfToken> {
+    let mut tokens = Vec::new();
+    let mut chars = vdf.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' => tokens.push(VdfToken::Open),
+            '}' => tokens.push(VdfToken::Close),
+            '"' => tokens.push(VdfToken::Str(read_quoted(&mut chars))),
+            '/' if chars.peek() == Some(&'/') => {
+                chars.find(|&c| c == '\n');
+            }
+            _ => {}
+        }
+    }
+    tokens
+}
+
+/// Reads the `path` and `mounted` keys of each numbered section directly under
+/// `"libraryfolders"`. Keys in deeper sections, like the app ids under `"apps"`, are
+/// ignored, as are sections without a `path`.
+fn parse_library_entries(vdf: &str) -> Vec<LibraryEntry> {
+    let mut entries = Vec::new();
+    let mut depth = 0usize;
+    let mut path = None;
+    let mut mounted = None;
+    let mut key: Option<String> = None;
+    for token in vdf_tokens(vdf) {
+        match token {
+            VdfToken::Open => {
+                depth += 1;
+                key = None;
+                if depth == 2 {
+                    (path, mounted) = (None, None);
+                }
+            }
+            VdfToken::Close => {
+                if depth == 2 {
+                    if let Some(path) = path.take() {
+                        entries.push(LibraryEntry {
+                            path,
+                            mounted: mounted.take(),
+                        });
+                    }
+                }
+                depth = depth.saturating_sub(1);
+                key = None;
+            }
+            VdfToken::Str(value) => match key.take() {
+                None => key = Some(value),
+                Some(k) if depth == 2 && k.eq_ignore_ascii_case("path") => path = Some(value),
+                Some(k) if depth == 2 && k.eq_ignore_ascii_case("mounted") => {
+                    mounted = Some(value.trim() == "1");
+                }
+                Some(_) => {}
+            },
+        }
+    }
+    entries
+}
+
+/// Reads a text file Steam wrote, tolerating a UTF-8 BOM and UTF-16 (LE or BE, with BOM).
+fn read_text(path: &Path) -> std::io::Result<String> {
+    let bytes = std::fs::read(path)?;
+    decode_text(&bytes).ok_or_else(|| {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("{} is not valid UTF-8 or UTF-16", path.display()),
+        )
+    })
+}
+
+/// Decodes `bytes` as UTF-16 when it starts with a UTF-16 BOM, otherwise as UTF-8 with
+/// any leading BOM stripped. Returns `None` for invalid content.
+fn decode_text(bytes: &[u8]) -> Option<String> {
+    let utf16 = |rest: &[u8], from: fn([u8; 2]) -> u16| {
+        let units: Vec<u16> = rest.chunks_exact(2).map(|c| from([c[0], c[1]])).collect();
+        String::from_utf16(&units).ok()
+    };
+    match bytes {
+        [0xFF, 0xFE, rest @ ..] => utf16(rest, u16::from_le_bytes),
+        [0xFE, 0xFF, rest @ ..] => utf16(rest, u16::from_be_bytes),
+        [0xEF, 0xBB, 0xBF, rest @ ..] => String::from_utf8(rest.to_vec()).ok(),
+        _ => String::from_utf8(bytes.to_vec()).ok(),
+    }
+}
+
 /// Finds the value for a key in an ACF file (simple key-value line: `"key"  "value"`).
 fn find_acf_value(contents: &str, key: &str) -> Option<String> {
     for line in contents.lines() {
@@ -178,38 +1135,169 @@ mod tests {
     use super::*;
     use std::path::PathBuf;
 
-    // --- extract_quoted_value ---
+    // --- decode_text / BOM handling ---
+
+    fn utf16le_with_bom(text: &str) -> Vec<u8> {
+        let mut bytes = vec![0xFF, 0xFE];
+        bytes.extend(text.encode_utf16().flat_map(u16::to_le_bytes));
+        bytes
+    }
 
     #[test]
-    fn extracts_first_quoted_value() {
-        assert_eq!(
-            extract_quoted_value(r#""path"   "/home/user/Steam""#, 0),
-            Some("path".to_string())
-        );
+    fn decode_text_strips_utf8_bom() {
+        let mut bytes = vec![0xEF, 0xBB, 0xBF];
+        bytes.extend_from_slice(b"\"AppState\"");
+        assert_eq!(decode_text(&bytes).as_deref(), Some("\"AppState\""));
     }
 
     #[test]
-    fn extracts_second_quoted_value() {
+    fn decode_text_reads_utf16_both_endiannesses() {
         assert_eq!(
-            extract_quoted_value(r#""path"   "/home/user/Steam""#, 1),
-            Some("/home/user/Steam".to_string())
+            decode_text(&utf16le_with_bom("Café")).as_deref(),
+            Some("Café")
         );
+        let mut be = vec![0xFE, 0xFF];
+        be.extend("Café".encode_utf16().flat_map(u16::to_be_bytes));
+        assert_eq!(decode_text(&be).as_deref(), Some("Café"));
     }
 
     #[test]
-    fn returns_none_when_index_out_of_bounds() {
-        assert_eq!(extract_quoted_value(r#""only_one""#, 1), None);
+    fn decode_text_rejects_invalid_utf8() {
+        assert_eq!(decode_text(&[0xC3, 0x28]), None);
     }
 
-    // --- find_acf_value ---
+    #[test]
+    fn bom_prefixed_manifest_parses_first_key() {
+        let dir = std::env::temp_dir().join(format!("steam_bom_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let manifest = dir.join("appmanifest_620.acf");
+        let mut bytes = vec![0xEF, 0xBB, 0xBF];
+        bytes.extend_from_slice(
+            b"\"appid\"\t\t\"620\"\n\"name\"\t\t\"Portal 2\"\n\"installdir\"\t\t\"Portal 2\"\n",
+        );
+        std::fs::write(&manifest, bytes).unwrap();
+
+        let game = parse_acf_file(&manifest, DiscoveryOptions::default()).unwrap();
+        assert_eq!(game.app_id, 620);
+        assert_eq!(game.name, "Portal 2");
+
+        std::fs::remove_dir_all(dir).ok();
+    }
 
     #[test]
-    fn finds_appid_in_acf() {
-        let acf = r#"
-            "AppState"
-            {
-                "appid"     "440"
-                "name"      "Team Fortress 2"
+    fn utf16_libraryfolders_vdf_is_read() {
+        let root = std::env::temp_dir().join(format!("steam_utf16_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(root.join("steamapps")).unwrap();
+        let vdf = "\"libraryfolders\"\n{\n\t\"1\"\n\t{\n\t\t\"path\"\t\t\"/mnt/games\"\n\t\t\"mounted\"\t\t\"1\"\n\t}\n}\n";
+        std::fs::write(
+            root.join("steamapps/libraryfolders.vdf"),
+            utf16le_with_bom(vdf),
+        )
+        .unwrap();
+
+        let paths = find_library_paths(&root).unwrap();
+        assert!(paths.contains(&PathBuf::from("/mnt/games/steamapps")));
+
+        std::fs::remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn library_folders_flag_missing_drives() {
+        let root = fake_steam_root();
+        let external = root.join("external");
+        std::fs::create_dir_all(external.join("steamapps")).unwrap();
+        let unplugged = root.join("unplugged");
+        let vdf = format!(
+            "\"libraryfolders\"\n{{\n\t\"0\"\n\t{{\n\t\t\"path\"\t\t\"{}\"\n\t}}\n\t\"1\"\n\t{{\n\t\t\"path\"\t\t\"{}\"\n\t}}\n}}\n",
+            external.display(),
+            unplugged.display()
+        );
+        std::fs::write(root.join("steamapps/libraryfolders.vdf"), vdf).unwrap();
+
+        let libraries = library_folders(&root).unwrap();
+        let summary: Vec<(PathBuf, bool)> =
+            libraries.into_iter().map(|l| (l.path, l.exists)).collect();
+        assert_eq!(
+            summary,
+            [
+                (root.join("steamapps"), true),
+                (external.join("steamapps"), true),
+                (unplugged.join("steamapps"), false),
+            ]
+        );
+
+        std::fs::remove_file(root.join("steamapps/libraryfolders.vdf")).unwrap();
+        assert!(matches!(
+            library_folders(&root),
+            Err(SteamError::InvalidRoot(_))
+        ));
+        std::fs::remove_dir_all(root).ok();
+    }
+
+    // --- extract_quoted_value ---
+
+    #[test]
+    fn extracts_first_quoted_value() {
+        assert_eq!(
+            extract_quoted_value(r#""path"   "/home/user/Steam""#, 0),
+            Some("path".to_string())
+        );
+    }
+
+    #[test]
+    fn extracts_second_quoted_value() {
+        assert_eq!(
+            extract_quoted_value(r#""path"   "/home/user/Steam""#, 1),
+            Some("/home/user/Steam".to_string())
+        );
+    }
+
+    #[test]
+    fn returns_none_when_index_out_of_bounds() {
+        assert_eq!(extract_quoted_value(r#""only_one""#, 1), None);
+    }
+
+    #[test]
+    fn decodes_escaped_quotes_in_value() {
+        assert_eq!(
+            extract_quoted_value(r#""name"  "Portal \"2\"""#, 1),
+            Some(r#"Portal "2""#.to_string())
+        );
+    }
+
+    #[test]
+    fn decodes_doubled_backslashes_in_path() {
+        assert_eq!(
+            extract_quoted_value(r#""path"  "C:\\Program Files (x86)\\Steam""#, 1),
+            Some(r"C:\Program Files (x86)\Steam".to_string())
+        );
+    }
+
+    #[test]
+    fn decodes_newline_and_tab_escapes() {
+        assert_eq!(
+            extract_quoted_value(r#""k"  "a\tb\nc""#, 1),
+            Some("a\tb\nc".to_string())
+        );
+    }
+
+    #[test]
+    fn unknown_escape_kept_literally() {
+        assert_eq!(
+            extract_quoted_value(r#""path"  "D:\Games""#, 1),
+            Some(r"D:\Games".to_string())
+        );
+    }
+
+    // --- find_acf_value ---
+
+    #[test]
+    fn finds_appid_in_acf() {
+        let acf = r#"
+            "AppState"
+            {
+                "appid"     "440"
+                "name"      "Team Fortress 2"
                 "installdir"    "Team Fortress 2"
             }
         "#;
@@ -232,6 +1320,69 @@ mod tests {
         );
     }
 
+    #[test]
+    fn finds_escaped_name_in_acf() {
+        let acf = r#"
+            "AppState"
+            {
+                "appid"     "620"
+                "name"      "Portal \"2\""
+            }
+        "#;
+        assert_eq!(
+            find_acf_value(acf, "name"),
+            Some(r#"Portal "2""#.to_string())
+        );
+    }
+
+    #[test]
+    fn library_paths_unescape_windows_backslashes() {
+        let vdf = r#"
+            "libraryfolders"
+            {
+                "1"
+                {
+                    "path"      "D:\\SteamLibrary"
+                    "mounted"   "1"
+                }
+            }
+        "#;
+        let paths = parse_library_paths_from_vdf(vdf, Path::new("/steam")).unwrap();
+        assert_eq!(
+            paths[1],
+            PathBuf::from(r"D:\SteamLibrary").join("steamapps")
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn backslash_installdir_is_split_into_components() {
+        let acf = r#"
+            "AppState"
+            {
+                "appid"         "220"
+                "name"          "Half-Life 2"
+                "installdir"    "Half-Life 2\\bin"
+            }
+        "#;
+        let game = parse_acf(acf, Path::new("/mnt/win/steamapps")).expect("should parse");
+        assert_eq!(
+            game.install_dir,
+            PathBuf::from("/mnt/win/steamapps/common/Half-Life 2/bin")
+        );
+    }
+
+    #[test]
+    fn absolute_windows_installdir_is_unresolvable() {
+        for dir in [r"C:\\Games\\Half-Life 2", r"\\\\nas\\games\\hl2"] {
+            let acf =
+                format!("\"appid\" \"220\"\n\"name\" \"Half-Life 2\"\n\"installdir\" \"{dir}\"\n");
+            assert_eq!(parse_acf(&acf, Path::new("/steamapps")), None, "{dir}");
+        }
+        assert!(is_windows_absolute(r"D:\SteamLibrary"));
+        assert!(!is_windows_absolute("Half-Life 2"));
+    }
+
     #[test]
     fn returns_none_for_missing_key() {
         let acf = r#""appid" "440""#;
@@ -260,12 +1411,476 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parses_size_and_last_played_when_present() {
+        let acf = r#"
+            "AppState"
+            {
+                "appid"         "570"
+                "name"          "Dota 2"
+                "installdir"    "dota 2 beta"
+                "LastUpdated"   "1690000000"
+                "SizeOnDisk"    "37035409466"
+                "LastPlayed"    "1700000000"
+            }
+        "#;
+        let game = parse_acf(acf, Path::new("/fake/steamapps")).unwrap();
+        assert_eq!(game.size_on_disk, Some(37_035_409_466));
+        assert_eq!(game.last_played, Some(1_700_000_000));
+    }
+
+    #[test]
+    fn last_played_falls_back_to_last_updated() {
+        let acf = r#"
+            "appid"         "570"
+            "name"          "Dota 2"
+            "installdir"    "dota 2 beta"
+            "LastUpdated"   "1690000000"
+        "#;
+        let game = parse_acf(acf, Path::new("/fake/steamapps")).unwrap();
+        assert_eq!(game.last_played, Some(1_690_000_000));
+    }
+
+    #[test]
+    fn size_and_last_played_absent_in_old_manifests() {
+        let acf = r#"
+            "appid"         "570"
+            "name"          "Dota 2"
+            "installdir"    "dota 2 beta"
+        "#;
+        let game = parse_acf(acf, Path::new("/fake/steamapps")).unwrap();
+        assert_eq!(game.size_on_disk, None);
+        assert_eq!(game.last_played, None);
+    }
+
     #[test]
     fn returns_none_for_invalid_app_id() {
         let acf = r#""appid" "not_a_number" "name" "Broken""#;
         assert!(parse_acf(acf, Path::new("/fake")).is_none());
     }
 
+    // --- StateFlags ---
+
+    fn write_manifest(steamapps: &Path, app_id: u32, name: &str, state_flags: u32) {
+        let acf = format!(
+            r#""AppState"
+{{
+    "appid"         "{app_id}"
+    "name"          "{name}"
+    "StateFlags"    "{state_flags}"
+    "installdir"    "{name}"
+}}"#
+        );
+        std::fs::write(steamapps.join(format!("appmanifest_{app_id}.acf")), acf).unwrap();
+        std::fs::create_dir_all(steamapps.join("common").join(name)).unwrap();
+    }
+
+    fn fake_steam_root() -> PathBuf {
+        let root = std::env::temp_dir().join(format!("steam_root_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(root.join("steamapps")).unwrap();
+        std::fs::write(
+            root.join("steamapps/libraryfolders.vdf"),
+            r#""libraryfolders" { }"#,
+        )
+        .unwrap();
+        root
+    }
+
+    #[test]
+    fn steam_root_override_is_used_when_valid() {
+        let root = fake_steam_root();
+        assert_eq!(resolve_steam_root(Some(&root)).unwrap(), root);
+        std::fs::remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn steam_root_override_without_library_file_is_an_error() {
+        let missing = std::env::temp_dir().join(format!("no_steam_{}", uuid::Uuid::new_v4()));
+        assert!(matches!(
+            resolve_steam_root(Some(&missing)),
+            Err(SteamError::InvalidRoot(p)) if p == missing
+        ));
+
+        // An existing folder that isn't a Steam root is rejected too.
+        std::fs::create_dir_all(missing.join("steamapps")).unwrap();
+        assert!(matches!(
+            resolve_steam_root(Some(&missing)),
+            Err(SteamError::InvalidRoot(_))
+        ));
+        std::fs::remove_dir_all(missing).ok();
+    }
+
+    #[test]
+    fn cover_image_prefers_portrait_art() {
+        let root = fake_steam_root();
+        let cache = root.join("appcache/librarycache");
+        std::fs::create_dir_all(&cache).unwrap();
+        std::fs::write(cache.join("440_header.jpg"), b"jpg").unwrap();
+        std::fs::write(cache.join("440_library_600x900.jpg"), b"jpg").unwrap();
+        std::fs::write(cache.join("570_header.jpg"), b"jpg").unwrap();
+
+        assert_eq!(
+            find_cover_image(&root, 440),
+            Some(cache.join("440_library_600x900.jpg"))
+        );
+        assert_eq!(
+            find_cover_image(&root, 570),
+            Some(cache.join("570_header.jpg"))
+        );
+        assert_eq!(find_cover_image(&root, 730), None);
+
+        std::fs::remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn cover_image_found_in_per_app_subdirectory() {
+        let root = fake_steam_root();
+        let app_cache = root.join("appcache/librarycache/1091500");
+        std::fs::create_dir_all(&app_cache).unwrap();
+        std::fs::write(app_cache.join("library_600x900.jpg"), b"jpg").unwrap();
+
+        assert_eq!(
+            find_cover_image(&root, 1091500),
+            Some(app_cache.join("library_600x900.jpg"))
+        );
+
+        std::fs::remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn cover_image_accepts_webp_and_avif() {
+        let root = fake_steam_root();
+        let cache = root.join("appcache/librarycache");
+        std::fs::create_dir_all(cache.join("620")).unwrap();
+        std::fs::write(cache.join("400_library_600x900.webp"), b"webp").unwrap();
+        std::fs::write(cache.join("620/header.avif"), b"avif").unwrap();
+        std::fs::write(cache.join("620_library_hero.txt"), b"txt").unwrap();
+
+        assert_eq!(
+            find_cover_image(&root, 400),
+            Some(cache.join("400_library_600x900.webp"))
+        );
+        assert_eq!(
+            find_cover_image(&root, 620),
+            Some(cache.join("620/header.avif"))
+        );
+
+        std::fs::remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn discovery_attaches_cover_images() {
+        let root = fake_steam_root();
+        write_manifest(&root.join("steamapps"), 10, "With Art", 4);
+        write_manifest(&root.join("steamapps"), 20, "Without Art", 4);
+        let cache = root.join("appcache/librarycache");
+        std::fs::create_dir_all(&cache).unwrap();
+        std::fs::write(cache.join("10_library_600x900.jpg"), b"jpg").unwrap();
+
+        let mut games = discover_games_at(&root, DiscoveryOptions::default()).unwrap();
+        games.sort_by_key(|g| g.app_id);
+        assert_eq!(
+            games[0].cover_image,
+            Some(cache.join("10_library_600x900.jpg"))
+        );
+        assert_eq!(games[1].cover_image, None);
+
+        std::fs::remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn fully_installed_flag_detected() {
+        assert!(is_fully_installed(r#""StateFlags" "4""#));
+        assert!(!is_fully_installed(r#""StateFlags" "1026""#));
+        assert!(is_fully_installed(r#""appid" "440""#));
+    }
+
+    #[test]
+    fn incomplete_installs_skipped_by_default() {
+        let root = fake_steam_root();
+        write_manifest(&root.join("steamapps"), 10, "Installed", 4);
+        write_manifest(&root.join("steamapps"), 20, "Downloading", 1026);
+
+        let games = discover_games_at(&root, DiscoveryOptions::default()).unwrap();
+        assert_eq!(games.len(), 1);
+        assert_eq!(games[0].app_id, 10);
+
+        let options = DiscoveryOptions {
+            include_incomplete: true,
+            ..Default::default()
+        };
+        let all = discover_games_at(&root, options).unwrap();
+        assert_eq!(all.len(), 2);
+
+        std::fs::remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn games_with_missing_install_dir_skipped_by_default() {
+        let root = fake_steam_root();
+        let steamapps = root.join("steamapps");
+        write_manifest(&steamapps, 10, "Present", 4);
+        write_manifest(&steamapps, 20, "Unplugged", 4);
+        std::fs::remove_dir_all(steamapps.join("common/Unplugged")).unwrap();
+
+        let games = discover_games_at(&root, DiscoveryOptions::default()).unwrap();
+        assert_eq!(games.len(), 1);
+        assert_eq!(games[0].name, "Present");
+
+        let options = DiscoveryOptions {
+            include_missing: true,
+            ..Default::default()
+        };
+        let mut all = discover_games_at(&root, options).unwrap();
+        all.sort_by_key(|g| g.app_id);
+        assert_eq!(all.len(), 2);
+        assert_eq!(all[1].name, "Unplugged");
+
+        std::fs::remove_dir_all(root).ok();
+    }
+
+    fn steam_app(app_id: u32, name: &str) -> SteamGame {
+        SteamGame {
+            app_id,
+            name: name.to_string(),
+            install_dir: PathBuf::from("/fake"),
+            is_shortcut: false,
+            size_on_disk: None,
+            last_played: None,
+            cover_image: None,
+            owner: None,
+        }
+    }
+
+    #[test]
+    fn tools_detected_by_id_and_name() {
+        let tools = [
+            steam_app(1493710, "Proton Experimental"),
+            steam_app(2805730, "Proton 9.0"),
+            steam_app(1628350, "Steam Linux Runtime 3.0 (sniper)"),
+            steam_app(228980, "Steamworks Common Redistributables"),
+            steam_app(740, "Counter-Strike Global Offensive - Dedicated Server"),
+            steam_app(505080, "Hollow Knight Soundtrack"),
+        ];
+        for tool in &tools {
+            assert!(is_probably_tool(tool), "{} should be a tool", tool.name);
+        }
+        for game in [
+            steam_app(367520, "Hollow Knight"),
+            steam_app(9999, "Protonaut"),
+            steam_app(220, "Half-Life 2"),
+        ] {
+            assert!(!is_probably_tool(&game), "{} is a game", game.name);
+        }
+    }
+
+    #[test]
+    fn hide_tools_filters_discovery() {
+        let root = fake_steam_root();
+        let steamapps = root.join("steamapps");
+        write_manifest(&steamapps, 1493710, "Proton Experimental", 4);
+        write_manifest(&steamapps, 367520, "Hollow Knight", 4);
+
+        let all = discover_games_at(&root, DiscoveryOptions::default()).unwrap();
+        assert_eq!(all.len(), 2, "tools are kept unless asked otherwise");
+
+        let options = DiscoveryOptions {
+            hide_tools: true,
+            ..Default::default()
+        };
+        let games = discover_games_at(&root, options).unwrap();
+        let names: Vec<&str> = games.iter().map(|g| g.name.as_str()).collect();
+        assert_eq!(names, ["Hollow Knight"]);
+
+        assert!(without_hidden_apps(games, &[367520]).is_empty());
+        let kept = without_hidden_apps(all, &[367520]);
+        assert_eq!(kept.len(), 1);
+        assert_eq!(kept[0].app_id, 1493710);
+
+        std::fs::remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn parallel_scan_matches_serial_order_and_dedup() {
+        let root = fake_steam_root();
+        let extra_a = root.join("drive_a");
+        let extra_b = root.join("drive_b");
+        std::fs::write(
+            root.join("steamapps/libraryfolders.vdf"),
+            format!(
+                r#""libraryfolders"
+{{
+    "1"
+    {{
+        "path"  "{}"
+    }}
+    "2"
+    {{
+        "path"  "{}"
+    }}
+}}"#,
+                extra_a.display(),
+                extra_b.display()
+            ),
+        )
+        .unwrap();
+        for dir in [&extra_a, &extra_b] {
+            std::fs::create_dir_all(dir.join("steamapps")).unwrap();
+        }
+        write_manifest(&root.join("steamapps"), 1, "Root", 4);
+        write_manifest(&extra_a.join("steamapps"), 2, "DriveA", 4);
+        write_manifest(&extra_a.join("steamapps"), 1, "DuplicateOfRoot", 4);
+        write_manifest(&extra_b.join("steamapps"), 3, "DriveB", 4);
+        write_manifest(&extra_b.join("steamapps"), 2, "DuplicateOfA", 4);
+
+        let options = DiscoveryOptions::default();
+        let mut seen = std::collections::HashSet::new();
+        let serial: Vec<SteamGame> = find_library_paths(&root)
+            .unwrap()
+            .iter()
+            .flat_map(|dir| read_games_from_library(dir, options))
+            .filter(|g| seen.insert(g.app_id))
+            .collect();
+
+        let parallel = discover_games_at(&root, options).unwrap();
+        assert_eq!(parallel, serial);
+        assert_eq!(parallel.len(), 3);
+        let names: Vec<&str> = parallel.iter().map(|g| g.name.as_str()).collect();
+        assert!(names.contains(&"Root"));
+        assert!(names.contains(&"DriveA"));
+        assert!(!names.contains(&"DuplicateOfRoot"));
+        assert!(!names.contains(&"DuplicateOfA"));
+
+        std::fs::remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn rescan_reads_only_the_given_library() {
+        let root = fake_steam_root();
+        let extra = root.join("extra");
+        std::fs::create_dir_all(extra.join("steamapps")).unwrap();
+        write_manifest(&root.join("steamapps"), 1, "Root", 4);
+        write_manifest(&extra.join("steamapps"), 2, "Extra", 4);
+        write_manifest(&extra.join("steamapps"), 3, "Downloading", 1026);
+
+        let games = rescan_library(&root, &extra.join("steamapps"), DiscoveryOptions::default());
+        let names: Vec<&str> = games.iter().map(|g| g.name.as_str()).collect();
+        assert_eq!(names, ["Extra"]);
+
+        std::fs::remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn merge_games_replaces_by_app_id_and_appends_new() {
+        let game = |app_id: u32, name: &str, is_shortcut: bool| SteamGame {
+            app_id,
+            name: name.to_string(),
+            install_dir: PathBuf::from("/fake"),
+            is_shortcut,
+            size_on_disk: None,
+            last_played: None,
+            cover_image: None,
+            owner: None,
+        };
+        let existing = vec![
+            game(10, "Old Ten", false),
+            game(20, "Twenty", false),
+            game(10, "Shortcut Ten", true),
+        ];
+        let rescanned = vec![game(10, "New Ten", false), game(30, "Thirty", false)];
+
+        let merged = merge_games(existing, rescanned);
+        let names: Vec<&str> = merged.iter().map(|g| g.name.as_str()).collect();
+        assert_eq!(names, ["New Ten", "Twenty", "Shortcut Ten", "Thirty"]);
+    }
+
+    // --- DiscoveryCache ---
+
+    fn set_mtime(path: &Path, secs_after_epoch: u64) {
+        let file = std::fs::File::options().write(true).open(path).unwrap();
+        file.set_modified(
+            SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(secs_after_epoch),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn cache_hit_when_nothing_changed() {
+        let root = fake_steam_root();
+        write_manifest(&root.join("steamapps"), 10, "Cached", 4);
+
+        let mut cache = DiscoveryCache::default();
+        assert!(cache.get(&root).is_none(), "empty cache should miss");
+
+        let games = discover_games_at(&root, DiscoveryOptions::default()).unwrap();
+        cache.insert(&root, games.clone());
+        assert_eq!(cache.get(&root), Some(games));
+
+        std::fs::remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn touching_manifest_invalidates_cache() {
+        let root = fake_steam_root();
+        let steamapps = root.join("steamapps");
+        write_manifest(&steamapps, 10, "Cached", 4);
+        let manifest = steamapps.join("appmanifest_10.acf");
+        set_mtime(&manifest, 1_000);
+        set_mtime(&steamapps.join("libraryfolders.vdf"), 1_000);
+
+        let mut cache = DiscoveryCache::default();
+        cache.insert(&root, vec![]);
+        assert!(cache.get(&root).is_some());
+
+        set_mtime(&manifest, 2_000_000_000);
+        assert!(
+            cache.get(&root).is_none(),
+            "newer manifest should invalidate"
+        );
+
+        std::fs::remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn rescanned_library_is_merged_into_the_cache() {
+        let root = fake_steam_root();
+        let steamapps = root.join("steamapps");
+        write_manifest(&steamapps, 10, "Kept", 4);
+        write_manifest(&steamapps, 20, "Uninstalled", 4);
+
+        let mut cache = DiscoveryCache::default();
+        cache.merge_library(&root, &steamapps, vec![]);
+        assert!(cache.get(&root).is_none(), "nothing cached, nothing merged");
+
+        let games = discover_games_at(&root, DiscoveryOptions::default()).unwrap();
+        cache.insert(&root, games);
+        std::fs::remove_file(steamapps.join("appmanifest_20.acf")).unwrap();
+        write_manifest(&steamapps, 30, "Installed", 4);
+
+        let rescanned = rescan_library(&root, &steamapps, DiscoveryOptions::default());
+        cache.merge_library(&root, &steamapps, rescanned);
+        let cached = cache.get(&root).expect("merge re-stamps the entry");
+        let mut names: Vec<&str> = cached.iter().map(|g| g.name.as_str()).collect();
+        names.sort();
+        assert_eq!(names, ["Installed", "Kept"]);
+
+        std::fs::remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn cache_is_keyed_by_root() {
+        let root_a = fake_steam_root();
+        let root_b = fake_steam_root();
+
+        let mut cache = DiscoveryCache::default();
+        cache.insert(&root_a, vec![]);
+        assert!(cache.get(&root_a).is_some());
+        assert!(cache.get(&root_b).is_none());
+
+        std::fs::remove_dir_all(root_a).ok();
+        std::fs::remove_dir_all(root_b).ok();
+    }
+
     // --- parse_library_paths_from_vdf ---
 
     #[test]
@@ -276,6 +1891,7 @@ mod tests {
                 "0"
                 {
                     "path"  "/mnt/games"
+                    "mounted"  "1"
                 }
             }
         "#;
@@ -307,6 +1923,500 @@ mod tests {
         assert_eq!(paths.len(), unique.len(), "paths should be deduplicated");
     }
 
+    #[test]
+    fn skips_unmounted_libraries_and_nested_paths() {
+        let root = fake_steam_root();
+        let present = root.join("present");
+        std::fs::create_dir_all(&present).unwrap();
+        let vdf = format!(
+            r#"
+            "libraryfolders"
+            {{
+                "0"
+                {{
+                    "path"      "{present}"
+                    "contentid" "123"
+                    "apps"
+                    {{
+                        "path"  "/not/a/library"
+                        "620"   "12345"
+                    }}
+                }}
+                "1"
+                {{
+                    "path"      "/media/usb/SteamLibrary"
+                    "mounted"   "1"
+                }}
+                "2"
+                {{
+                    "path"      "/media/old/SteamLibrary"
+                    "mounted"   "0"
+                }}
+                "3"
+                {{
+                    // "path"   "/commented/out"
+                    "path"      "{missing}"
+                }}
+            }}
+            "#,
+            present = present.display(),
+            missing = root.join("missing").display(),
+        );
+
+        let paths = parse_library_paths_from_vdf(&vdf, &root).unwrap();
+        assert_eq!(
+            paths,
+            [
+                root.join("steamapps"),
+                present.join("steamapps"),
+                PathBuf::from("/media/usb/SteamLibrary/steamapps"),
+            ]
+        );
+
+        std::fs::write(root.join("steamapps/libraryfolders.vdf"), &vdf).unwrap();
+        let declared: Vec<PathBuf> = library_folders(&root)
+            .unwrap()
+            .into_iter()
+            .map(|l| l.path)
+            .collect();
+        assert_eq!(declared.len(), 5, "unmounted libraries are still listed");
+
+        std::fs::remove_dir_all(root).ok();
+    }
+
+    // --- linux_steam_root ---
+
+    #[cfg(target_os = "linux")]
+    fn temp_home() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("steam_home_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn linux_root_prefers_dot_steam_when_present() {
+        let home = temp_home();
+        std::fs::create_dir_all(home.join(".steam/steam")).unwrap();
+        std::fs::create_dir_all(home.join(".local/share/Steam")).unwrap();
+
+        assert_eq!(linux_steam_root(&home), home.join(".steam/steam"));
+        std::fs::remove_dir_all(home).ok();
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn linux_root_falls_back_to_local_share() {
+        let home = temp_home();
+        assert_eq!(linux_steam_root(&home), home.join(".local/share/Steam"));
+        std::fs::remove_dir_all(home).ok();
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn linux_root_probes_flatpak_then_snap() {
+        let home = temp_home();
+        let flatpak = home.join(".var/app/com.valvesoftware.Steam/data/Steam");
+        let snap = home.join("snap/steam/common/.local/share/Steam");
+
+        std::fs::create_dir_all(&snap).unwrap();
+        assert_eq!(linux_steam_root(&home), snap);
+        std::fs::create_dir_all(&flatpak).unwrap();
+        assert_eq!(linux_steam_root(&home), flatpak, "Flatpak outranks Snap");
+        std::fs::create_dir_all(home.join(".local/share/Steam")).unwrap();
+        assert_eq!(
+            linux_steam_root(&home),
+            home.join(".local/share/Steam"),
+            "a native install outranks both sandboxes"
+        );
+        std::fs::remove_dir_all(home).ok();
+    }
+
+    #[test]
+    fn flatpak_root_detection() {
+        assert!(is_flatpak_root(Path::new(
+            "/home/u/.var/app/com.valvesoftware.Steam/data/Steam"
+        )));
+        assert!(!is_flatpak_root(Path::new("/home/u/.local/share/Steam")));
+        assert!(!is_flatpak_root(Path::new(
+            "/home/u/.var/app/org.other.App/data/Steam"
+        )));
+    }
+
+    // --- parse_shortcuts_vdf ---
+
+    fn vdf_string(key: &str, value: &str) -> Vec<u8> {
+        let mut out = vec![VDF_STRING];
+        out.extend_from_slice(key.as_bytes());
+        out.push(0);
+        out.extend_from_slice(value.as_bytes());
+        out.push(0);
+        out
+    }
+
+    fn vdf_int(key: &str, value: u32) -> Vec<u8> {
+        let mut out = vec![VDF_INT];
+        out.extend_from_slice(key.as_bytes());
+        out.push(0);
+        out.extend_from_slice(&value.to_le_bytes());
+        out
+    }
+
+    fn vdf_map(key: &str, body: Vec<Vec<u8>>) -> Vec<u8> {
+        let mut out = vec![VDF_MAP];
+        out.extend_from_slice(key.as_bytes());
+        out.push(0);
+        out.extend(body.into_iter().flatten());
+        out.push(VDF_END_MAP);
+        out
+    }
+
+    fn shortcuts_blob(entries: Vec<Vec<u8>>) -> Vec<u8> {
+        let mut out = vdf_map("shortcuts", entries);
+        out.push(VDF_END_MAP);
+        out
+    }
+
+    fn emulator_shortcut() -> ShortcutGame {
+        ShortcutGame {
+            app_id: 3_000_000_000,
+            app_name: "RetroArch".to_string(),
+            exe: "/usr/bin/retroarch".to_string(),
+            start_dir: "/home/me/roms".to_string(),
+            launch_options: r#"-L snes9x "Super Metroid.sfc""#.to_string(),
+            is_hidden: false,
+            last_played: None,
+            cover_image: None,
+            tags: vec![],
+            owner: None,
+        }
+    }
+
+    #[test]
+    fn shortcuts_launch_through_steam_by_default() {
+        let shortcut = emulator_shortcut();
+        let via_steam = LaunchTarget::steam_shortcut(3_000_000_000);
+        assert_eq!(shortcut.launch_target(false, false), via_steam);
+        assert_eq!(shortcut.launch_target(true, false), via_steam);
+        assert_eq!(
+            shortcut.launch_target(true, true),
+            via_steam,
+            "forcing Steam wins over bypassing it"
+        );
+    }
+
+    #[test]
+    fn bypassed_shortcut_runs_exe_directly() {
+        let target = emulator_shortcut().launch_target(false, true);
+        assert_eq!(
+            target,
+            LaunchTarget::executable_with(
+                "/usr/bin/retroarch",
+                SpawnOptions {
+                    args: vec![
+                        "-L".to_string(),
+                        "snes9x".to_string(),
+                        "Super Metroid.sfc".to_string()
+                    ],
+                    working_dir: Some(PathBuf::from("/home/me/roms")),
+                    ..Default::default()
+                }
+            )
+        );
+
+        let bare = ShortcutGame {
+            start_dir: String::new(),
+            launch_options: String::new(),
+            ..emulator_shortcut()
+        };
+        assert_eq!(
+            bare.launch_target(false, true),
+            LaunchTarget::executable("/usr/bin/retroarch")
+        );
+    }
+
+    #[test]
+    fn parses_shortcut_with_start_dir_and_launch_options() {
+        let blob = shortcuts_blob(vec![vdf_map(
+            "0",
+            vec![
+                vdf_int("appid", 3_123_456_789),
+                vdf_string("AppName", "RetroArch"),
+                vdf_string("Exe", "\"/usr/bin/retroarch\""),
+                vdf_string("StartDir", "\"/home/user/roms\""),
+                vdf_string("icon", ""),
+                vdf_string("LaunchOptions", "-L snes9x --fullscreen"),
+            ],
+        )]);
+
+        let games = parse_shortcuts_vdf(&blob);
+        assert_eq!(
+            games,
+            vec![ShortcutGame {
+                app_id: 3_123_456_789,
+                app_name: "RetroArch".to_string(),
+                exe: "/usr/bin/retroarch".to_string(),
+                start_dir: "/home/user/roms".to_string(),
+                launch_options: "-L snes9x --fullscreen".to_string(),
+                is_hidden: false,
+                last_played: None,
+                cover_image: None,
+                tags: vec![],
+                owner: None,
+            }]
+        );
+    }
+
+    #[test]
+    fn shortcut_missing_optional_fields_defaults_to_empty() {
+        let blob = shortcuts_blob(vec![vdf_map(
+            "0",
+            vec![vdf_int("appid", 7), vdf_string("AppName", "Bare")],
+        )]);
+
+        let games = parse_shortcuts_vdf(&blob);
+        assert_eq!(games.len(), 1);
+        assert_eq!(games[0].start_dir, "");
+        assert_eq!(games[0].launch_options, "");
+    }
+
+    #[test]
+    fn truncated_shortcuts_vdf_keeps_complete_entries() {
+        let mut blob = shortcuts_blob(vec![
+            vdf_map("0", vec![vdf_int("appid", 1), vdf_string("AppName", "Whole")]),
+            vdf_map("1", vec![vdf_int("appid", 2), vdf_string("AppName", "Cut")]),
+        ]);
+        blob.truncate(blob.len() - 8);
+
+        let games = parse_shortcuts_vdf(&blob);
+        assert_eq!(games[0].app_name, "Whole");
+    }
+
+    #[test]
+    fn nested_tags_map_parsed_without_leaking_entries() {
+        let blob = shortcuts_blob(vec![
+            vdf_map(
+                "0",
+                vec![
+                    vdf_int("appid", 1),
+                    vdf_string("AppName", "Dolphin"),
+                    vdf_map(
+                        "tags",
+                        vec![vdf_string("0", "Emulators"), vdf_string("1", "Favorites")],
+                    ),
+                    vdf_int("LastPlayTime", 1_700_000_000),
+                ],
+            ),
+            vdf_map(
+                "1",
+                vec![vdf_int("appid", 2), vdf_string("AppName", "PCSX2")],
+            ),
+        ]);
+
+        let games = parse_shortcuts_vdf(&blob);
+        assert_eq!(games.len(), 2);
+        assert_eq!(games[0].tags, ["Emulators", "Favorites"]);
+        // Fields after the nested map still belong to the same shortcut.
+        assert_eq!(games[0].last_played, Some(1_700_000_000));
+        assert_eq!(games[1].app_name, "PCSX2");
+        assert!(games[1].tags.is_empty());
+    }
+
+    // --- loginusers.vdf ---
+
+    const LOGIN_USERS: &str = r#"
+"users"
+{
+	"76561197960287930"
+	{
+		"AccountName"		"gaben"
+		"PersonaName"		"Gabe \"G\" Newell"
+		"RememberPassword"		"1"
+		"MostRecent"		"1"
+		"Timestamp"		"1700000000"
+	}
+	"76561198000000001"
+	{
+		"AccountName"		"player_two"
+		"PersonaName"		"P2"
+		"MostRecent"		"0"
+	}
+}
+"#;
+
+    #[test]
+    fn parses_login_users() {
+        let users = parse_login_users(LOGIN_USERS);
+        assert_eq!(
+            users,
+            vec![
+                SteamUser {
+                    steam_id: 76561197960287930,
+                    account_name: "gaben".to_string(),
+                    persona_name: r#"Gabe "G" Newell"#.to_string(),
+                },
+                SteamUser {
+                    steam_id: 76561198000000001,
+                    account_name: "player_two".to_string(),
+                    persona_name: "P2".to_string(),
+                },
+            ]
+        );
+        assert_eq!(users[0].account_id(), 22202);
+    }
+
+    #[test]
+    fn shortcuts_folder_correlated_to_persona() {
+        let users = parse_login_users(LOGIN_USERS);
+        let vdf = Path::new("/steam/userdata/22202/config/shortcuts.vdf");
+        assert_eq!(
+            persona_for_shortcuts(&users, vdf),
+            Some(r#"Gabe "G" Newell"#)
+        );
+        let unknown = Path::new("/steam/userdata/999/config/shortcuts.vdf");
+        assert_eq!(persona_for_shortcuts(&users, unknown), None);
+    }
+
+    #[test]
+    fn missing_loginusers_returns_empty() {
+        let root = fake_steam_root();
+        assert!(find_login_users(&root).is_empty());
+        std::fs::remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn discovered_shortcuts_are_labelled_with_their_owner() {
+        let root = std::env::temp_dir().join(format!("steam_root_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(root.join("config")).unwrap();
+        std::fs::write(root.join("config/loginusers.vdf"), LOGIN_USERS).unwrap();
+        for account_id in ["22202", "999"] {
+            let config = root.join("userdata").join(account_id).join("config");
+            std::fs::create_dir_all(&config).unwrap();
+            let blob = shortcuts_blob(vec![vdf_map(
+                "0",
+                vec![
+                    vdf_int("appid", account_id.parse().unwrap()),
+                    vdf_string("AppName", "Emu"),
+                ],
+            )]);
+            std::fs::write(config.join("shortcuts.vdf"), blob).unwrap();
+        }
+
+        let games = discover_shortcut_games(&root, false);
+        let owner = |app_id| {
+            let game = games.iter().find(|g| g.app_id == app_id).unwrap();
+            game.owner.clone()
+        };
+        assert_eq!(owner(22202).as_deref(), Some(r#"Gabe "G" Newell"#));
+        assert_eq!(owner(999), None, "account missing from loginusers.vdf");
+
+        std::fs::remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn hidden_shortcuts_excluded_by_default() {
+        let root = std::env::temp_dir().join(format!("steam_root_{}", uuid::Uuid::new_v4()));
+        let config = root.join("userdata/12345/config");
+        std::fs::create_dir_all(&config).unwrap();
+        let blob = shortcuts_blob(vec![
+            vdf_map(
+                "0",
+                vec![
+                    vdf_int("appid", 1),
+                    vdf_string("AppName", "Hidden"),
+                    vdf_int("IsHidden", 1),
+                ],
+            ),
+            vdf_map(
+                "1",
+                vec![
+                    vdf_int("appid", 2),
+                    vdf_string("AppName", "Visible"),
+                    vdf_int("IsHidden", 0),
+                ],
+            ),
+        ]);
+        std::fs::write(config.join("shortcuts.vdf"), blob).unwrap();
+
+        let visible = discover_shortcut_games(&root, false);
+        assert_eq!(visible.len(), 1);
+        assert_eq!(visible[0].app_name, "Visible");
+
+        let all = discover_shortcut_games(&root, true);
+        assert_eq!(all.len(), 2);
+        assert!(all.iter().any(|g| g.app_name == "Hidden" && g.is_hidden));
+
+        std::fs::remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn shortcut_converts_to_steam_game() {
+        let game = SteamGame::from(ShortcutGame {
+            app_id: 42,
+            app_name: "Emu".to_string(),
+            exe: "/usr/bin/emu".to_string(),
+            start_dir: "/usr/bin".to_string(),
+            launch_options: String::new(),
+            is_hidden: false,
+            last_played: Some(1_700_000_000),
+            cover_image: Some(PathBuf::from("/grid/42p.png")),
+            tags: vec![],
+            owner: Some("P2".to_string()),
+        });
+        assert!(game.is_shortcut);
+        assert_eq!(game.owner.as_deref(), Some("P2"));
+        assert_eq!(game.last_played, Some(1_700_000_000));
+        assert_eq!(game.name, "Emu");
+        assert_eq!(game.install_dir, PathBuf::from("/usr/bin"));
+        assert_eq!(game.cover_image, Some(PathBuf::from("/grid/42p.png")));
+    }
+
+    #[test]
+    fn grid_image_prefers_portrait_variant() {
+        let grid = std::env::temp_dir().join(format!("steam_grid_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&grid).unwrap();
+        for name in [
+            "77.png",
+            "77p.jpg",
+            "77_hero.png",
+            "77_logo.png",
+            "88.webp",
+            "99_hero.png",
+        ] {
+            std::fs::write(grid.join(name), b"img").unwrap();
+        }
+
+        assert_eq!(find_grid_image(&grid, 77), Some(grid.join("77p.jpg")));
+        assert_eq!(find_grid_image(&grid, 88), Some(grid.join("88.webp")));
+        assert_eq!(find_grid_image(&grid, 99), None, "hero art is not a cover");
+
+        std::fs::remove_dir_all(grid).ok();
+    }
+
+    #[test]
+    fn discovered_shortcuts_pick_up_grid_art() {
+        let root = std::env::temp_dir().join(format!("steam_root_{}", uuid::Uuid::new_v4()));
+        let config = root.join("userdata/12345/config");
+        std::fs::create_dir_all(config.join("grid")).unwrap();
+        let blob = shortcuts_blob(vec![
+            vdf_map("0", vec![vdf_int("appid", 5), vdf_string("AppName", "Art")]),
+            vdf_map(
+                "1",
+                vec![vdf_int("appid", 6), vdf_string("AppName", "No Art")],
+            ),
+        ]);
+        std::fs::write(config.join("shortcuts.vdf"), blob).unwrap();
+        std::fs::write(config.join("grid/5p.png"), b"png").unwrap();
+
+        let games = discover_shortcut_games(&root, false);
+        let art = games.iter().find(|g| g.app_id == 5).unwrap();
+        assert_eq!(art.cover_image, Some(config.join("grid/5p.png")));
+        let no_art = games.iter().find(|g| g.app_id == 6).unwrap();
+        assert_eq!(no_art.cover_image, None);
+
+        std::fs::remove_dir_all(root).ok();
+    }
+
     // --- SteamGame helpers ---
 
     #[test]
@@ -315,7 +2425,48 @@ mod tests {
             app_id: 440,
             name: "Team Fortress 2".to_string(),
             install_dir: PathBuf::from("/fake"),
+            is_shortcut: false,
+            size_on_disk: None,
+            last_played: None,
+            cover_image: None,
+            owner: None,
         };
         assert_eq!(game.launch_uri(), "steam://run/440");
+        assert_eq!(game.external_key(), "steam:440");
+    }
+
+    #[test]
+    fn shortcut_launch_uri_format() {
+        let game = SteamGame {
+            app_id: 1,
+            name: "Shortcut".to_string(),
+            install_dir: PathBuf::from("/fake"),
+            is_shortcut: true,
+            size_on_disk: None,
+            last_played: None,
+            cover_image: None,
+            owner: None,
+        };
+        assert_eq!(game.launch_uri(), "steam://rungameid/4328521728");
+        assert_eq!(game.external_key(), "steam-shortcut:1");
+    }
+
+    #[test]
+    fn shortcut_game_id_puts_app_id_in_high_half() {
+        // 1234567890 = 0x499602D2, so the id is 0x499602D2_02000000.
+        assert_eq!(shortcut_game_id(1_234_567_890), 0x4996_02D2_0200_0000);
+        let game = SteamGame {
+            app_id: 1_234_567_890,
+            name: "Shortcut".to_string(),
+            install_dir: PathBuf::from("/fake"),
+            is_shortcut: true,
+            size_on_disk: None,
+            last_played: None,
+            cover_image: None,
+            owner: None,
+        };
+        assert_eq!(game.launch_uri(), "steam://rungameid/5302428712275279872");
+        // The largest app id must not overflow into the low half.
+        assert_eq!(shortcut_game_id(u32::MAX), 0xFFFF_FFFF_0200_0000);
     }
 }
@@ -0,0 +1,156 @@
+use image::imageops::FilterType;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+// ---------------------------------------------------------------------------
+// Public API
+// ---------------------------------------------------------------------------
+
+/// Returns a PNG copy of the image at `source` scaled down to at most `max_width` pixels
+/// wide, cached in `cache_dir`. The cache key covers the source path, its modification
+/// time and the width, so an edited cover gets a fresh thumbnail. Images already narrower
+/// than `max_width` are re-encoded at their own size.
+///
+/// Returns `None` when the source can't be read or decoded (missing file, unsupported
+/// format, corrupt data) or the thumbnail can't be written; the caller falls back to
+/// the original image.
+pub fn thumbnail(cache_dir: &Path, source: &Path, max_width: u32) -> Option<PathBuf> {
+    let max_width = max_width.max(1);
+    let modified = std::fs::metadata(source)
+        .and_then(|m| m.modified())
+        .map_err(|e| log::debug!("No thumbnail for {:?}: {}", source, e))
+        .ok()?;
+    let cached = cache_dir.join(cache_file_name(source, modified, max_width));
+    if cached.is_file() {
+        log::debug!("Thumbnail cache hit for {:?}", source);
+        return Some(cached);
+    }
+
+    let image = image::open(source)
+        .map_err(|e| log::warn!("Cannot decode cover {:?}: {}", source, e))
+        .ok()?;
+    let image = if image.width() > max_width {
+        let height = (u64::from(image.height()) * u64::from(max_width) / u64::from(image.width()))
+            .max(1) as u32;
+        image.resize_exact(max_width, height, FilterType::Triangle)
+    } else {
+        image
+    };
+
+    // Write to a temp file and rename, so a concurrent request never reads a half-written PNG.
+    let tmp = cached.with_extension("png.tmp");
+    let written = std::fs::create_dir_all(cache_dir)
+        .map_err(image::ImageError::IoError)
+        .and_then(|_| image.save_with_format(&tmp, image::ImageFormat::Png))
+        .and_then(|_| std::fs::rename(&tmp, &cached).map_err(image::ImageError::IoError));
+    if let Err(e) = written {
+        log::warn!("Failed to write thumbnail for {:?}: {}", source, e);
+        std::fs::remove_file(&tmp).ok();
+        return None;
+    }
+    log::info!("Created {}px thumbnail for {:?}", max_width, source);
+    Some(cached)
+}
+
+// ---------------------------------------------------------------------------
+// Private helpers
+// ---------------------------------------------------------------------------
+
+fn cache_file_name(source: &Path, modified: std::time::SystemTime, max_width: u32) -> String {
+    let mut hasher = DefaultHasher::new();
+    source.hash(&mut hasher);
+    modified
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or_default()
+        .hash(&mut hasher);
+    format!("{:016x}_{}.png", hasher.finish(), max_width)
+}
+
+// ============================================================
+// Tests
+// ============================================================
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{ImageBuffer, Rgb};
+    use uuid::Uuid;
+
+    fn temp_dir() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("thumb_test_{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn write_png(path: &Path, width: u32, height: u32) {
+        ImageBuffer::from_pixel(width, height, Rgb([200u8, 40, 40]))
+            .save(path)
+            .unwrap();
+    }
+
+    #[test]
+    fn downscales_and_keeps_aspect_ratio() {
+        let dir = temp_dir();
+        let source = dir.join("cover.png");
+        write_png(&source, 600, 900);
+
+        let thumb = thumbnail(&dir.join("cache"), &source, 200).unwrap();
+        let image = image::open(&thumb).unwrap();
+        assert_eq!((image.width(), image.height()), (200, 300));
+
+        std::fs::remove_dir_all(dir).ok();
+    }
+
+    #[test]
+    fn reuses_cache_until_source_changes() {
+        let dir = temp_dir();
+        let cache = dir.join("cache");
+        let source = dir.join("cover.png");
+        write_png(&source, 400, 600);
+
+        let first = thumbnail(&cache, &source, 100).unwrap();
+        let created = std::fs::metadata(&first).unwrap().modified().unwrap();
+        let second = thumbnail(&cache, &source, 100).unwrap();
+        assert_eq!(first, second);
+        assert_eq!(
+            std::fs::metadata(&second).unwrap().modified().unwrap(),
+            created
+        );
+
+        let other_width = thumbnail(&cache, &source, 50).unwrap();
+        assert_ne!(first, other_width);
+
+        let later = std::fs::metadata(&source).unwrap().modified().unwrap()
+            + std::time::Duration::from_secs(60);
+        std::fs::File::options()
+            .write(true)
+            .open(&source)
+            .unwrap()
+            .set_modified(later)
+            .unwrap();
+        let refreshed = thumbnail(&cache, &source, 100).unwrap();
+        assert_ne!(first, refreshed);
+
+        std::fs::remove_dir_all(dir).ok();
+    }
+
+    #[test]
+    fn corrupt_or_missing_images_yield_none() {
+        let dir = temp_dir();
+        let corrupt = dir.join("cover.png");
+        std::fs::write(&corrupt, b"\x89PNG\r\n\x1a\nnot really a png").unwrap();
+        let text = dir.join("notes.txt");
+        std::fs::write(&text, "hello").unwrap();
+
+        assert_eq!(thumbnail(&dir.join("cache"), &corrupt, 100), None);
+        assert_eq!(thumbnail(&dir.join("cache"), &text, 100), None);
+        assert_eq!(
+            thumbnail(&dir.join("cache"), &dir.join("gone.png"), 100),
+            None
+        );
+
+        std::fs::remove_dir_all(dir).ok();
+    }
+}
@@ -0,0 +1,233 @@
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::time::{Duration, Instant};
+
+/// Quiet period after the last relevant change before `on_change` fires.
+pub const DEBOUNCE_WINDOW: Duration = Duration::from_millis(500);
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+/// Watches the custom library file and Steam's `steamapps` folders, calling back once
+/// per burst of changes. Dropping it stops the watch and its worker thread.
+pub struct LibraryWatcher {
+    _watcher: RecommendedWatcher,
+}
+
+/// Which watched files a burst of changes touched.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Changes {
+    /// The library file or one of its sidecar files.
+    pub library: bool,
+    /// A Steam app manifest or `libraryfolders.vdf`.
+    pub steam: bool,
+}
+
+/// Trailing-edge debounce: a burst of events yields one signal once `window` has
+/// passed without another event.
+#[derive(Debug)]
+struct Debouncer {
+    window: Duration,
+    last_event: Option<Instant>,
+}
+
+// ---------------------------------------------------------------------------
+// Public API
+// ---------------------------------------------------------------------------
+
+impl LibraryWatcher {
+    /// Starts watching. The library file's parent directory is watched rather than the
+    /// file itself, since saves replace the file by renaming a temp file over it.
+    pub fn start(
+        library_file: &Path,
+        steamapps_dirs: &[PathBuf],
+        on_change: impl Fn(Changes) + Send + 'static,
+    ) -> notify::Result<Self> {
+        let (tx, rx) = mpsc::channel();
+        let mut watcher = notify::recommended_watcher(tx)?;
+
+        if let Some(dir) = library_file.parent() {
+            std::fs::create_dir_all(dir)?;
+            watcher.watch(dir, RecursiveMode::NonRecursive)?;
+        }
+        for dir in steamapps_dirs {
+            if let Err(e) = watcher.watch(dir, RecursiveMode::NonRecursive) {
+                log::warn!("Not watching Steam library {:?}: {}", dir, e);
+            }
+        }
+
+        let library_file = library_file.to_path_buf();
+        std::thread::spawn(move || {
+            let mut debouncer = Debouncer::new(DEBOUNCE_WINDOW);
+            let mut changes = Changes::default();
+            loop {
+                let received = match debouncer.remaining(Instant::now()) {
+                    Some(wait) => rx.recv_timeout(wait),
+                    None => rx.recv().map_err(|_| RecvTimeoutError::Disconnected),
+                };
+                match received {
+                    Ok(Ok(event)) => {
+                        for path in &event.paths {
+                            let touched = classify(path, &library_file);
+                            if touched != Changes::default() {
+                                changes.library |= touched.library;
+                                changes.steam |= touched.steam;
+                                debouncer.record(Instant::now());
+                            }
+                        }
+                    }
+                    Ok(Err(e)) => log::warn!("File watcher error: {}", e),
+                    Err(RecvTimeoutError::Timeout) => {}
+                    Err(RecvTimeoutError::Disconnected) => break,
+                }
+                if debouncer.ready(Instant::now()) {
+                    on_change(std::mem::take(&mut changes));
+                }
+            }
+            log::info!("Library watcher stopped");
+        });
+
+        Ok(Self { _watcher: watcher })
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Private helpers
+// ---------------------------------------------------------------------------
+
+impl Debouncer {
+    fn new(window: Duration) -> Self {
+        Self {
+            window,
+            last_event: None,
+        }
+    }
+
+    fn record(&mut self, now: Instant) {
+        self.last_event = Some(now);
+    }
+
+    /// Time left until a pending burst settles, or `None` when nothing is pending.
+    fn remaining(&self, now: Instant) -> Option<Duration> {
+        let last = self.last_event?;
+        Some(
+            self.window
+                .saturating_sub(now.saturating_duration_since(last)),
+        )
+    }
+
+    /// Returns `true` once per burst, when the quiet period has elapsed.
+    fn ready(&mut self, now: Instant) -> bool {
+        if self.remaining(now) == Some(Duration::ZERO) {
+            self.last_event = None;
+            return true;
+        }
+        false
+    }
+}
+
+/// What a changed path matters to: the library file (or its temp/sidecar files), or a
+/// Steam app manifest or `libraryfolders.vdf`. Anything else touches nothing.
+fn classify(path: &Path, library_file: &Path) -> Changes {
+    let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+        return Changes::default();
+    };
+    let library_name = library_file.file_stem().and_then(|n| n.to_str());
+    let in_library_dir = path.parent() == library_file.parent();
+    Changes {
+        library: in_library_dir && library_name.is_some_and(|stem| name.starts_with(stem)),
+        steam: (name.starts_with("appmanifest_") && name.ends_with(".acf"))
+            || name == "libraryfolders.vdf",
+    }
+}
+
+// ============================================================
+// Tests
+// ============================================================
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WINDOW: Duration = Duration::from_millis(500);
+
+    fn ms(n: u64) -> Duration {
+        Duration::from_millis(n)
+    }
+
+    #[test]
+    fn burst_of_events_fires_once_after_quiet_period() {
+        let start = Instant::now();
+        let mut debouncer = Debouncer::new(WINDOW);
+        for offset in [0, 100, 200, 300] {
+            debouncer.record(start + ms(offset));
+            assert!(!debouncer.ready(start + ms(offset)));
+        }
+
+        assert!(!debouncer.ready(start + ms(700)), "still inside the window");
+        assert!(debouncer.ready(start + ms(800)));
+        assert!(
+            !debouncer.ready(start + ms(2000)),
+            "fires only once per burst"
+        );
+    }
+
+    #[test]
+    fn separate_bursts_fire_separately() {
+        let start = Instant::now();
+        let mut debouncer = Debouncer::new(WINDOW);
+        debouncer.record(start);
+        assert!(debouncer.ready(start + ms(600)));
+        debouncer.record(start + ms(1000));
+        assert!(debouncer.ready(start + ms(1500)));
+    }
+
+    #[test]
+    fn remaining_counts_down_from_last_event() {
+        let start = Instant::now();
+        let mut debouncer = Debouncer::new(WINDOW);
+        assert_eq!(debouncer.remaining(start), None);
+        debouncer.record(start);
+        debouncer.record(start + ms(200));
+        assert_eq!(debouncer.remaining(start + ms(300)), Some(ms(400)));
+        assert_eq!(debouncer.remaining(start + ms(900)), Some(Duration::ZERO));
+    }
+
+    #[test]
+    fn relevant_paths() {
+        let library = Path::new("/data/app/custom_games.json");
+        let library_change = Changes {
+            library: true,
+            steam: false,
+        };
+        let steam_change = Changes {
+            library: false,
+            steam: true,
+        };
+        let classified = |path: &str| classify(Path::new(path), library);
+        assert_eq!(classify(library, library), library_change);
+        assert_eq!(
+            classified("/data/app/custom_games.json.tmp"),
+            library_change
+        );
+        assert_eq!(
+            classified("/data/app/custom_games.collections.json"),
+            library_change
+        );
+        assert_eq!(
+            classified("/steam/steamapps/appmanifest_620.acf"),
+            steam_change
+        );
+        assert_eq!(
+            classified("/steam/steamapps/libraryfolders.vdf"),
+            steam_change
+        );
+        assert_eq!(classified("/data/app/bookmarks.json"), Changes::default());
+        assert_eq!(classified("/data/app/logs/logs.log"), Changes::default());
+        assert_eq!(
+            classified("/steam/steamapps/downloading/state.bin"),
+            Changes::default()
+        );
+    }
+}
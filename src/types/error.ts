@@ -0,0 +1,15 @@
+/** Mirrors Rust `CommandError`: what every failing `invoke` rejects with */
+export interface CommandError {
+  /** Stable machine-readable code, e.g. `steam_not_found`, `permission_denied` */
+  code: string;
+  message: string;
+}
+
+export function isCommandError(e: unknown): e is CommandError {
+  return typeof e === "object" && e !== null && "code" in e && "message" in e;
+}
+
+/** Human-readable text for an `invoke` rejection */
+export function errorMessage(e: unknown): string {
+  return isCommandError(e) ? e.message : String(e);
+}
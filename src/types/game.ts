@@ -6,6 +6,12 @@ export interface SteamGame {
   app_id: number;
   name: string;
   install_dir: string;
+  is_shortcut: boolean;
+  size_on_disk: number | null;
+  last_played: number | null;
+  cover_image: string | null;
+  /** For shortcuts, the persona name of the Steam account that added it */
+  owner: string | null;
 }
 
 export interface CustomGame {
@@ -13,10 +19,40 @@ export interface CustomGame {
   title: string;
   executable: string;
   cover_image: string | null;
+  /** Wide background image for the detail view */
+  hero_image: string | null;
+  screenshots: string[];
   tags: string[];
   notes: string | null;
+  /** How to render `notes` */
+  notes_format: NotesFormat;
+  playtime_secs: number;
+  /** Unix seconds when the last tracked session ended */
+  last_played: number | null;
+  /** Unix seconds when the game was added; 0 for older entries */
+  added_at: number;
+  args: string[];
+  working_dir: string | null;
+  env: Record<string, string>;
+  wrappers: string[];
+  runner: string | null;
+  /** Shell command run before launch; a failure aborts the launch */
+  pre_launch: string | null;
+  /** Shell command run after the game exits */
+  post_exit: string | null;
+  favorite: boolean;
+  /** Set for games imported from Steam/Epic; they launch through that store */
+  imported_from: GameSource | null;
 }
 
+/** Mirrors Rust `NotesFormat` */
+export type NotesFormat = "plain" | "markdown";
+
+/** Mirrors Rust `GameSource` */
+export type GameSource =
+  | { store: "steam"; app_id: number; is_shortcut: boolean }
+  | { store: "epic"; app_name: string; launch_uri: string };
+
 export interface EpicGame {
   app_name: string;
   display_name: string;
@@ -24,17 +60,122 @@ export interface EpicGame {
   catalog_namespace: string;
   catalog_item_id: string;
   cover_image: string | null;
+  launch_executable: string | null;
+  install_size: number | null;
+  app_version: string | null;
+}
+
+/** Mirrors Rust `DesktopGame` (Linux `.desktop` launchers) */
+export interface DesktopGame {
+  name: string;
+  executable: string;
+  args: string[];
+  icon: string | null;
+  categories: string[];
+  desktop_file: string;
+}
+
+/** Mirrors Rust `RomGame`: a ROM launched as `emulator [emulator_args...] <rom>` */
+export interface RomGame {
+  title: string;
+  rom: string;
+  emulator: string;
+  emulator_args: string[];
+}
+
+/** Mirrors Rust `LutrisGame` (Linux, from `~/.config/lutris/games/*.yml`) */
+export interface LutrisGame {
+  slug: string;
+  name: string;
+  runner: string | null;
+  executable: string | null;
+  config_file: string;
+}
+
+/**
+ * Result of `get_all_games`: any backend game tagged with its `source` and its stable
+ * `key` (`steam:<appid>`, `steam-shortcut:<id>`, `epic:<app_name>` or `custom-<uuid>`),
+ * the form favorites and collections use.
+ */
+export type SourcedGame = { key: string } & (
+  | ({ source: "steam" } & SteamGame)
+  | ({ source: "epic" } & EpicGame)
+  | ({ source: "custom" } & CustomGame)
+);
+
+/** Mirrors Rust `Collection` */
+export interface Collection {
+  id: string;
+  name: string;
+  /** `custom-<uuid>`, `steam:<appid>`, `steam-shortcut:<id>` or `epic:<app_name>` */
+  member_keys: string[];
+}
+
+/** Mirrors Rust `SortOrder` */
+export type SortOrder =
+  | "title_asc"
+  | "title_desc"
+  | "recently_played"
+  | "recently_added";
+
+/** Result of the `add_game` command; `warnings` are non-fatal (e.g. a shared title). */
+export interface AddGameResult {
+  game: CustomGame;
+  warnings: string[];
+}
+
+/** Mirrors Rust `StorageUsage`: bytes used per source, from `library_storage`. */
+export interface StorageUsage {
+  steam_bytes: number;
+  epic_bytes: number;
+  custom_bytes: number;
+  total_bytes: number;
+  games_without_size: number;
+}
+
+/** Mirrors Rust `LibraryIssue`: a custom game with missing files. */
+export interface LibraryIssue {
+  id: string;
+  title: string;
+  executable_missing: boolean;
+  cover_missing: boolean;
+  hero_missing: boolean;
+  /** How many of the game's screenshots are gone */
+  screenshots_missing: number;
+}
+
+/** Mirrors Rust `LaunchPlan`: what `preview_launch` says a launch would run. */
+export type LaunchPlan =
+  | { kind: "uri"; uri: string }
+  | {
+      kind: "command";
+      program: string;
+      args: string[];
+      working_dir: string | null;
+      env: [string, string][];
+    };
+
+/** Result of the `scan_and_import` command. */
+export interface ImportSummary {
+  added: CustomGame[];
+  skipped_duplicates: number;
+  /** The scan hit its executable limit, so some games may not have been found. */
+  truncated: boolean;
 }
 
 /** Unified view model used throughout the UI */
 export interface Game {
-  /** Stable key: `steam-<appid>`, `epic-<app_name>`, or `custom-<uuid>` */
+  /** The backend's stable key (see `SourcedGame`), used by favorites and collections */
   key: string;
   title: string;
   platform: Platform;
   coverImage: string | null;
   /** steam app id — present for Steam games */
   appId?: number;
+  /** true when the Steam entry is a non-Steam shortcut */
+  isShortcut?: boolean;
+  /** Steam account a shortcut belongs to */
+  owner?: string;
   /** executable path — present for custom games */
   executable?: string;
   /** pre-computed Epic launcher URI — present for Epic games */
@@ -45,17 +186,33 @@ export interface Game {
 export type SortOption = "alpha" | "recentlyAdded";
 export type PlatformFilter = "all" | "steam" | "epic" | "custom";
 
-export function fromSteamGame(g: SteamGame): Game {
+export function fromSourcedGame(g: SourcedGame): Game {
+  switch (g.source) {
+    case "steam":
+      return fromSteamGame(g);
+    case "epic":
+      return fromEpicGame(g);
+    case "custom":
+      return fromCustomGame(g);
+  }
+}
+
+export function fromSteamGame(g: SteamGame & { key: string }): Game {
   return {
-    key: `steam-${g.app_id}`,
+    key: g.key,
     title: g.name,
     platform: "steam",
-    coverImage: `https://cdn.cloudflare.steamstatic.com/steam/apps/${g.app_id}/library_600x900.jpg`,
+    coverImage: g.cover_image
+      ? convertFileSrc(g.cover_image)
+      : `https://cdn.cloudflare.steamstatic.com/steam/apps/${g.app_id}/library_600x900.jpg`,
     appId: g.app_id,
+    isShortcut: g.is_shortcut,
+    owner: g.owner ?? undefined,
     tags: [],
   };
 }
 
+/** Custom games keep the backend's `custom-<uuid>` key, also for ones `add_game` returns. */
 export function fromCustomGame(g: CustomGame): Game {
   return {
     key: `custom-${g.id}`,
@@ -72,9 +229,9 @@ function resolveCustomCover(path: string | null): string | null {
   return convertFileSrc(path);
 }
 
-export function fromEpicGame(g: EpicGame): Game {
+export function fromEpicGame(g: EpicGame & { key: string }): Game {
   return {
-    key: `epic-${g.app_name}`,
+    key: g.key,
     title: g.display_name,
     platform: "epic",
     coverImage: g.cover_image ? convertFileSrc(g.cover_image) : null,
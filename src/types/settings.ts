@@ -0,0 +1,17 @@
+import type { SortOrder } from "./game";
+
+/** Mirrors Rust `Settings`: app-wide preferences from `get_settings` */
+export interface Settings {
+  /** Show dot-files in the file explorer by default */
+  show_hidden: boolean;
+  /** Steam folder to scan instead of the platform default */
+  steam_root_override: string | null;
+  /** Order custom games are listed in by default */
+  sort_order: SortOrder;
+  /** Copy picked covers into the app's own covers folder */
+  manage_covers: boolean;
+  /** Leave Steam tools (Proton, runtimes, soundtracks) out of the library */
+  hide_steam_tools: boolean;
+  /** Steam app ids the user hid from the library */
+  hidden_steam_apps: number[];
+}